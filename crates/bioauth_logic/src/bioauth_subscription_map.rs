@@ -1,4 +1,7 @@
-use std::{collections::HashMap, hash::Hash};
+use std::{
+    collections::{BTreeSet, HashMap},
+    hash::Hash,
+};
 
 use crate::ChatId;
 
@@ -6,7 +9,21 @@ use crate::ChatId;
 pub struct BioauthNotificationState {
     pub last_block_number_notified: u32,
     pub next_block_number_to_notify: u32,
-    pub alerted_at: Option<u64>,
+    /// The `expires_at` of the authentication period the alerts are tracked for.
+    pub alerted_expires_at: Option<u64>,
+    /// The alert thresholds already fired in the authentication period.
+    pub alerted_thresholds_in_mins: BTreeSet<u64>,
+    /// Whether the last processed block has no active authentication for the validator.
+    pub is_lost: bool,
+    /// Whether the loss is acknowledged, the lost notification is not repeated until the
+    /// validator is restored.
+    pub acknowledged: bool,
+    /// The moment in milliseconds the lost notification is not repeated until.
+    pub snoozed_until: Option<u64>,
+    /// The moment in milliseconds the validator was found lost at.
+    pub lost_at: Option<u64>,
+    /// The number of the escalation levels the loss is already escalated to.
+    pub escalated_levels: usize,
 }
 
 #[derive(Debug)]
@@ -45,6 +62,14 @@ where
         self.0.get(key)
     }
 
+    pub fn get_state_mut(
+        &mut self,
+        key: &Key,
+        chat_id: ChatId,
+    ) -> Option<&mut BioauthNotificationState> {
+        self.0.get_mut(key)?.get_mut(&chat_id)
+    }
+
     pub fn subscribe(&mut self, key: Key, chat_id: ChatId, state: BioauthNotificationState) {
         let entry = self.0.entry(key);
 
@@ -79,6 +104,19 @@ where
         }
     }
 
+    /// Move the states to the new chat, the ones of the subscriptions it already has are kept.
+    pub fn migrate_chat(&mut self, from_chat_id: ChatId, to_chat_id: ChatId) {
+        for (_, states) in self.0.iter_mut() {
+            if let Some(state) = states.remove(&from_chat_id) {
+                states.entry(to_chat_id).or_insert(state);
+            }
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Key, &HashMap<ChatId, BioauthNotificationState>)> {
+        self.0.iter()
+    }
+
     pub fn iter_mut(&mut self) -> BioauthSubscriptionMapIter<'_, Key> {
         BioauthSubscriptionMapIter {
             inner: self.0.iter_mut(),
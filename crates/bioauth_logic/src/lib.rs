@@ -3,20 +3,59 @@
 use std::{
     collections::{HashMap, HashSet},
     hash::Hash,
-    time::{SystemTime, UNIX_EPOCH},
 };
 
 use bioauth_settings::BioauthSettingsMap;
+pub use bioauth_subscription_map::BioauthNotificationState;
 use bioauth_subscription_map::BioauthSubscriptionMap;
+pub use clock::{Clock, ManualClock, SystemClock};
 
 pub type ChatId = i64;
 
 mod bioauth_subscription_map;
+mod clock;
+
+/// The reason the notification was not delivered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryError {
+    /// The delivery can succeed later, e.g. on network issues or flood control.
+    Retryable,
+    /// The delivery never succeeds, e.g. the bot is blocked or kicked from the chat.
+    Permanent,
+}
 
 #[derive(Debug)]
-pub enum FailedNotification {
-    BioauthLostNotificationFailed { chat_id: i64 },
-    BioauthSoonExpiredAlertFailed { chat_id: i64 },
+pub enum FailedNotification<BioauthPublicKey> {
+    BioauthLostNotificationFailed {
+        chat_id: i64,
+        bioauth_public_key: BioauthPublicKey,
+        error: DeliveryError,
+    },
+    BioauthSoonExpiredAlertFailed {
+        chat_id: i64,
+        bioauth_public_key: BioauthPublicKey,
+        error: DeliveryError,
+    },
+    /// The notification that is not retried, e.g. a bioauth event one.
+    OtherNotificationFailed { chat_id: i64, error: DeliveryError },
+}
+
+impl<BioauthPublicKey> FailedNotification<BioauthPublicKey> {
+    pub fn chat_id(&self) -> i64 {
+        match self {
+            FailedNotification::BioauthLostNotificationFailed { chat_id, .. }
+            | FailedNotification::BioauthSoonExpiredAlertFailed { chat_id, .. }
+            | FailedNotification::OtherNotificationFailed { chat_id, .. } => *chat_id,
+        }
+    }
+
+    pub fn error(&self) -> DeliveryError {
+        match self {
+            FailedNotification::BioauthLostNotificationFailed { error, .. }
+            | FailedNotification::BioauthSoonExpiredAlertFailed { error, .. }
+            | FailedNotification::OtherNotificationFailed { error, .. } => *error,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -24,33 +63,85 @@ pub enum Notification<BioauthPublicKey> {
     BioauthLostNotification {
         chat_id: i64,
         bioauth_public_key: BioauthPublicKey,
+        block_number: u32,
     },
     BioauthSoonExpiredAlert {
         chat_id: i64,
         bioauth_public_key: BioauthPublicKey,
+        expires_at: u64,
+    },
+    /// The validator is active again after the bioauth was lost.
+    BioauthRestored {
+        chat_id: i64,
+        bioauth_public_key: BioauthPublicKey,
+        expires_at: u64,
+    },
+    BioauthAuthenticatedNotification {
+        chat_id: i64,
+        bioauth_public_key: BioauthPublicKey,
+        block_number: u32,
+    },
+    BioauthExpiredNotification {
+        chat_id: i64,
+        bioauth_public_key: BioauthPublicKey,
+        block_number: u32,
+    },
+    BioauthRemovedNotification {
+        chat_id: i64,
+        bioauth_public_key: BioauthPublicKey,
+        block_number: u32,
+    },
+    /// The loss is not acknowledged in time and is sent to the backup chat.
+    BioauthLossEscalated {
+        chat_id: i64,
+        bioauth_public_key: BioauthPublicKey,
+        from_chat_id: i64,
+    },
+}
+
+/// The bioauth pallet event emitted in the block.
+#[derive(Debug)]
+pub enum BioauthEvent<BioauthPublicKey> {
+    NewAuthentication {
+        bioauth_public_key: BioauthPublicKey,
+    },
+    AuthenticationExpired {
+        bioauth_public_key: BioauthPublicKey,
+    },
+    AuthenticationRemoved {
+        bioauth_public_key: BioauthPublicKey,
     },
 }
 
 #[derive(Debug)]
-pub struct BioauthLogic<BioauthPublicKey> {
+pub struct BioauthLogic<BioauthPublicKey, C = SystemClock> {
     pub bioauth_subscription_map: BioauthSubscriptionMap<BioauthPublicKey>,
+    pub clock: C,
+    /// The timestamp of the last processed block in milliseconds.
+    pub last_block_timestamp: Option<u64>,
 }
 
 #[derive(Debug)]
 pub struct InitParamBioauth<BioauthPublicKey> {
     pub bioauth_public_key: BioauthPublicKey,
     pub t_chat_id: ChatId,
+    /// The notification state persisted before the restart.
+    pub state: BioauthNotificationState,
 }
 
 #[derive(Debug)]
-pub struct InitParams<BioauthPublicKey> {
+pub struct InitParams<BioauthPublicKey, C = SystemClock> {
     pub bioauths: Vec<InitParamBioauth<BioauthPublicKey>>,
+    pub clock: C,
 }
 
 #[derive(Debug)]
 pub struct NewBlockParams<'a, BioauthPublicKey> {
     pub block_number: u32,
+    /// The chain moment of the block in milliseconds, the clock is used if it is unknown.
+    pub block_timestamp: Option<u64>,
     pub active_authentications_map: &'a HashMap<BioauthPublicKey, u64>,
+    pub bioauth_events: &'a [BioauthEvent<BioauthPublicKey>],
     pub bioauth_settings_map: &'a BioauthSettingsMap<BioauthPublicKey>,
 }
 
@@ -66,11 +157,12 @@ pub struct Subscrition {
     pub expired_at: Option<u64>,
 }
 
-impl<BioauthPublicKey> BioauthLogic<BioauthPublicKey>
+impl<BioauthPublicKey, C> BioauthLogic<BioauthPublicKey, C>
 where
     BioauthPublicKey: Eq + Hash + Copy,
+    C: Clock,
 {
-    pub fn init(params: InitParams<BioauthPublicKey>) -> Self {
+    pub fn init(params: InitParams<BioauthPublicKey, C>) -> Self {
         tracing::info!("BioauthLogic init");
         let mut bioauth_subscription_map = BioauthSubscriptionMap::new();
 
@@ -78,40 +170,55 @@ where
             bioauth_subscription_map.subscribe(
                 bioauth.bioauth_public_key,
                 bioauth.t_chat_id,
-                bioauth_subscription_map::BioauthNotificationState::default(),
+                bioauth.state,
             );
         }
 
         BioauthLogic {
             bioauth_subscription_map,
+            clock: params.clock,
+            last_block_timestamp: None,
         }
     }
 
-    pub fn communicate_notification_failures(&mut self, failures: &[FailedNotification]) {
-        let mut failed_notifications_chat_ids = HashSet::new();
-        let mut failed_alerts_chat_ids = HashSet::new();
-
+    /// Roll back the notification state of the retryable failures so they are sent again.
+    pub fn communicate_notification_failures(
+        &mut self,
+        failures: &[FailedNotification<BioauthPublicKey>],
+    ) {
         for failure in failures {
-            match failure {
-                FailedNotification::BioauthLostNotificationFailed { chat_id } => {
-                    failed_notifications_chat_ids.insert(*chat_id);
-                }
-                FailedNotification::BioauthSoonExpiredAlertFailed { chat_id } => {
-                    failed_alerts_chat_ids.insert(*chat_id);
-                }
+            if failure.error() != DeliveryError::Retryable {
+                continue;
             }
-        }
 
-        for (_, chats) in self.bioauth_subscription_map.iter_mut() {
-            for (chat_id, state) in chats {
-                if failed_notifications_chat_ids.contains(chat_id) {
-                    state.last_block_number_notified = 0;
-                    state.next_block_number_to_notify = 0;
+            match failure {
+                FailedNotification::BioauthLostNotificationFailed {
+                    chat_id,
+                    bioauth_public_key,
+                    ..
+                } => {
+                    if let Some(state) = self
+                        .bioauth_subscription_map
+                        .get_state_mut(bioauth_public_key, *chat_id)
+                    {
+                        state.last_block_number_notified = 0;
+                        state.next_block_number_to_notify = 0;
+                    }
                 }
-
-                if failed_alerts_chat_ids.contains(chat_id) {
-                    state.alerted_at = None;
+                FailedNotification::BioauthSoonExpiredAlertFailed {
+                    chat_id,
+                    bioauth_public_key,
+                    ..
+                } => {
+                    if let Some(state) = self
+                        .bioauth_subscription_map
+                        .get_state_mut(bioauth_public_key, *chat_id)
+                    {
+                        state.alerted_expires_at = None;
+                        state.alerted_thresholds_in_mins.clear();
+                    }
                 }
+                FailedNotification::OtherNotificationFailed { .. } => {}
             }
         }
     }
@@ -122,65 +229,192 @@ where
     ) -> Vec<Notification<BioauthPublicKey>> {
         let NewBlockParams {
             block_number,
+            block_timestamp,
             active_authentications_map,
+            bioauth_events,
             bioauth_settings_map,
         } = params;
 
+        // Both the timestamp and `expires_at` are in milliseconds.
+        let timestamp = block_timestamp.unwrap_or_else(|| self.clock.now().as_millis() as u64);
+        self.last_block_timestamp = Some(timestamp);
+
         let mut notifications = vec![];
+        let mut lost_by_event = HashSet::new();
+
+        for bioauth_event in bioauth_events {
+            let bioauth_public_key = match bioauth_event {
+                BioauthEvent::NewAuthentication { bioauth_public_key } => bioauth_public_key,
+                BioauthEvent::AuthenticationExpired { bioauth_public_key }
+                | BioauthEvent::AuthenticationRemoved { bioauth_public_key } => {
+                    lost_by_event.insert(*bioauth_public_key);
+                    bioauth_public_key
+                }
+            };
+
+            let Some(chats) = self.bioauth_subscription_map.get(bioauth_public_key) else {
+                continue;
+            };
+
+            for (chat_id, state) in chats {
+                let chat_id = *chat_id;
+                let bioauth_public_key = *bioauth_public_key;
+
+                notifications.push(match bioauth_event {
+                    BioauthEvent::NewAuthentication { .. }
+                        if state.is_lost
+                            && active_authentications_map.contains_key(&bioauth_public_key) =>
+                    {
+                        // The restored notification is sent instead.
+                        continue;
+                    }
+                    BioauthEvent::NewAuthentication { .. } => {
+                        Notification::BioauthAuthenticatedNotification {
+                            chat_id,
+                            bioauth_public_key,
+                            block_number,
+                        }
+                    }
+                    BioauthEvent::AuthenticationExpired { .. } => {
+                        Notification::BioauthExpiredNotification {
+                            chat_id,
+                            bioauth_public_key,
+                            block_number,
+                        }
+                    }
+                    BioauthEvent::AuthenticationRemoved { .. } => {
+                        Notification::BioauthRemovedNotification {
+                            chat_id,
+                            bioauth_public_key,
+                            block_number,
+                        }
+                    }
+                });
+            }
+        }
 
         for (bioauth_public_key, chats) in self.bioauth_subscription_map.iter_mut() {
             let expires_at_opt = active_authentications_map.get(bioauth_public_key).copied();
 
             for (chat_id, state) in chats.iter_mut() {
                 let settings = bioauth_settings_map.get(&(*chat_id, *bioauth_public_key));
+                let is_quiet = bioauth_settings_map
+                    .get_chat(*chat_id)
+                    .is_quiet_at(timestamp);
                 match expires_at_opt {
                     None => {
+                        state.is_lost = true;
+                        let lost_at = *state.lost_at.get_or_insert(timestamp);
+
+                        // The unacknowledged loss is escalated level by level, regardless of
+                        // the quiet hours and the snooze of the chat.
+                        while let Some(level) =
+                            settings.escalation_levels.get(state.escalated_levels)
+                        {
+                            if state.acknowledged
+                                || lost_at.saturating_add(level.after_in_mins.saturating_mul(60000))
+                                    > timestamp
+                            {
+                                break;
+                            }
+
+                            notifications.push(Notification::BioauthLossEscalated {
+                                chat_id: level.chat_id,
+                                bioauth_public_key: *bioauth_public_key,
+                                from_chat_id: *chat_id,
+                            });
+                            state.escalated_levels += 1;
+                        }
+
                         if block_number < state.next_block_number_to_notify
                             && state.next_block_number_to_notify != 0
                         {
                             continue;
                         }
 
-                        notifications.push(Notification::BioauthLostNotification {
-                            chat_id: *chat_id,
-                            bioauth_public_key: *bioauth_public_key,
-                        });
+                        // Only the first loss notification is sent in the quiet hours,
+                        // the repeats are deferred until they end.
+                        if is_quiet && state.last_block_number_notified != 0 {
+                            continue;
+                        }
+
+                        if state.acknowledged
+                            || state
+                                .snoozed_until
+                                .is_some_and(|snoozed_until| timestamp < snoozed_until)
+                        {
+                            continue;
+                        }
+
+                        // The expiration or removal notification has already told about the loss.
+                        if !lost_by_event.contains(bioauth_public_key) {
+                            notifications.push(Notification::BioauthLostNotification {
+                                chat_id: *chat_id,
+                                bioauth_public_key: *bioauth_public_key,
+                                block_number,
+                            });
+                        }
 
                         state.last_block_number_notified = block_number;
                         state.next_block_number_to_notify =
                             block_number + settings.max_message_frequency_in_blocks;
                     }
                     Some(expires_at) => {
-                        let alert_at =
-                            expires_at - settings.alert_before_expiration_in_mins * 60000;
-
-                        let start = SystemTime::now();
-                        let since_the_epoch = start
-                            .duration_since(UNIX_EPOCH)
-                            .expect("Time went backwards");
-                        let timestamp = since_the_epoch.as_secs();
-
-                        let alert_at = match state.alerted_at {
-                            None => alert_at,
-                            Some(alerted_at) => {
-                                let mut res = alerted_at;
-                                if alerted_at < alert_at {
-                                    if alert_at <= timestamp {
-                                        state.alerted_at = None;
-                                    }
-                                    res = alert_at
-                                }
-                                res
-                            }
-                        };
+                        if state.is_lost {
+                            notifications.push(Notification::BioauthRestored {
+                                chat_id: *chat_id,
+                                bioauth_public_key: *bioauth_public_key,
+                                expires_at,
+                            });
+
+                            state.is_lost = false;
+                            state.last_block_number_notified = 0;
+                            state.next_block_number_to_notify = 0;
+                            state.acknowledged = false;
+                            state.snoozed_until = None;
+                            state.lost_at = None;
+                            state.escalated_levels = 0;
+                        }
+
+                        // The authentication was renewed, re-arm the alerts.
+                        if state.alerted_expires_at != Some(expires_at) {
+                            state.alerted_expires_at = Some(expires_at);
+                            state.alerted_thresholds_in_mins.clear();
+                        }
+
+                        let due_thresholds_in_mins: Vec<u64> = settings
+                            .alert_before_expiration_in_mins
+                            .iter()
+                            .copied()
+                            .filter(|in_mins| {
+                                expires_at.saturating_sub(in_mins.saturating_mul(60000))
+                                    <= timestamp
+                                    && !state.alerted_thresholds_in_mins.contains(in_mins)
+                            })
+                            .collect();
+
+                        // Only the final alert is sent in the quiet hours, the earlier ones
+                        // are deferred until they end.
+                        let is_final_due = settings
+                            .alert_before_expiration_in_mins
+                            .last()
+                            .is_some_and(|in_mins| due_thresholds_in_mins.contains(in_mins));
+                        if is_quiet && !is_final_due {
+                            continue;
+                        }
 
-                        if alert_at <= timestamp {
+                        // Several thresholds can become due at once, e.g. after a restart,
+                        // only a single alert is sent for them.
+                        if !due_thresholds_in_mins.is_empty() {
                             notifications.push(Notification::BioauthSoonExpiredAlert {
                                 chat_id: *chat_id,
                                 bioauth_public_key: *bioauth_public_key,
+                                expires_at,
                             });
 
-                            state.alerted_at = Some(timestamp);
+                            state
+                                .alerted_thresholds_in_mins
+                                .extend(due_thresholds_in_mins);
                         }
                     }
                 }
@@ -190,6 +424,69 @@ where
         notifications
     }
 
+    /// Stop repeating the lost notification until the validator is restored.
+    ///
+    /// Returns `false` if the validator is not lost for the chat.
+    pub fn acknowledge(
+        &mut self,
+        t_chat_id: ChatId,
+        bioauth_public_key: &BioauthPublicKey,
+    ) -> bool {
+        match self
+            .bioauth_subscription_map
+            .get_state_mut(bioauth_public_key, t_chat_id)
+        {
+            Some(state) if state.is_lost => {
+                state.acknowledged = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Defer the repeated lost notification for the number of minutes.
+    ///
+    /// Returns `false` if the validator is not lost for the chat or the duration is out of range.
+    pub fn snooze(
+        &mut self,
+        t_chat_id: ChatId,
+        bioauth_public_key: &BioauthPublicKey,
+        in_mins: u64,
+    ) -> bool {
+        // Compared against the block timestamps, so counted from the last processed block.
+        let now = self
+            .last_block_timestamp
+            .unwrap_or_else(|| self.clock.now().as_millis() as u64);
+        let Some(snoozed_until) = in_mins
+            .checked_mul(60_000)
+            .and_then(|in_millis| now.checked_add(in_millis))
+        else {
+            return false;
+        };
+        match self
+            .bioauth_subscription_map
+            .get_state_mut(bioauth_public_key, t_chat_id)
+        {
+            Some(state) if state.is_lost => {
+                state.snoozed_until = Some(snoozed_until);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Snapshot the notification states of all the subscriptions.
+    pub fn notification_states(&self) -> Vec<(ChatId, BioauthPublicKey, BioauthNotificationState)> {
+        self.bioauth_subscription_map
+            .iter()
+            .flat_map(|(bioauth_public_key, chats)| {
+                chats
+                    .iter()
+                    .map(|(chat_id, state)| (*chat_id, *bioauth_public_key, state.clone()))
+            })
+            .collect()
+    }
+
     pub fn update_subscription(&mut self, params: UpdateSubscriptionParams<BioauthPublicKey>) {
         let UpdateSubscriptionParams {
             t_chat_id,
@@ -216,6 +513,14 @@ where
     pub fn remove_all_subscription(&mut self, t_chat_id: ChatId) {
         self.bioauth_subscription_map.unsubscribe_all(t_chat_id);
     }
+
+    /// Move the subscriptions of the migrated chat to its new id, keeping the notification state.
+    ///
+    /// The subscriptions the new chat already has are kept as is.
+    pub fn migrate_chat(&mut self, from_chat_id: ChatId, to_chat_id: ChatId) {
+        self.bioauth_subscription_map
+            .migrate_chat(from_chat_id, to_chat_id);
+    }
 }
 
 #[cfg(test)]
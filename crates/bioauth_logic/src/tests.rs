@@ -1,27 +1,38 @@
-use crate::{BioauthLogic, InitParams, NewBlockParams, Notification, UpdateSubscriptionParams};
-use bioauth_settings::BioauthSettingsMap;
-use std::{
-    collections::HashMap,
-    time::{SystemTime, UNIX_EPOCH},
+use crate::{
+    BioauthEvent, BioauthLogic, Clock, DeliveryError, FailedNotification, InitParamBioauth,
+    InitParams, ManualClock, NewBlockParams, Notification, UpdateSubscriptionParams,
 };
+use bioauth_settings::{
+    BioauthSettings, BioauthSettingsMap, ChatSettings, EscalationLevel, QuietHours,
+};
+use std::{collections::HashMap, time::Duration};
 use tracing_test::traced_test;
 
+const NOW: Duration = Duration::from_secs(1_700_000_000);
+
+fn init_logic() -> (BioauthLogic<usize, ManualClock>, ManualClock) {
+    let clock = ManualClock::new(NOW);
+    let logic = BioauthLogic::init(InitParams {
+        bioauths: vec![],
+        clock: clock.clone(),
+    });
+    (logic, clock)
+}
+
 #[test]
 #[traced_test]
 fn process_block() {
-    let mut logic = BioauthLogic::<usize>::init(InitParams { bioauths: vec![] });
+    let (mut logic, clock) = init_logic();
     let bioauth_settings_map = BioauthSettingsMap::new();
     let mut active_authentications_map = HashMap::new();
 
-    let start = SystemTime::now();
-    let since_the_epoch = start
-        .duration_since(UNIX_EPOCH)
-        .expect("Time went backwards");
-    let timestamp = since_the_epoch.as_secs();
+    let timestamp = clock.now().as_millis() as u64;
 
     let notifications = logic.new_block(NewBlockParams {
         block_number: 1,
+        block_timestamp: None,
         active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
         bioauth_settings_map: &bioauth_settings_map,
     });
 
@@ -37,7 +48,9 @@ fn process_block() {
 
     let notifications = logic.new_block(NewBlockParams {
         block_number: 2,
+        block_timestamp: None,
         active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
         bioauth_settings_map: &bioauth_settings_map,
     });
 
@@ -48,9 +61,11 @@ fn process_block() {
             Notification::BioauthLostNotification {
                 chat_id,
                 bioauth_public_key,
+                block_number,
             } => {
                 assert_eq!(chat_id, t_chat_id_0);
                 assert_eq!(bioauth_public_key, bioauth_public_key_0);
+                assert_eq!(block_number, 2);
             }
             _ => panic!(),
         }
@@ -60,17 +75,25 @@ fn process_block() {
 
     let notifications = logic.new_block(NewBlockParams {
         block_number: 3,
+        block_timestamp: None,
         active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
         bioauth_settings_map: &bioauth_settings_map,
     });
 
-    assert_eq!(notifications.len(), 0);
+    assert_eq!(notifications.len(), 1);
+    assert!(matches!(
+        notifications[0],
+        Notification::BioauthRestored { .. }
+    ));
 
     active_authentications_map.insert(0, timestamp);
 
     let notifications = logic.new_block(NewBlockParams {
         block_number: 3,
+        block_timestamp: None,
         active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
         bioauth_settings_map: &bioauth_settings_map,
     });
 
@@ -81,6 +104,7 @@ fn process_block() {
             Notification::BioauthSoonExpiredAlert {
                 chat_id,
                 bioauth_public_key,
+                ..
             } => {
                 assert_eq!(chat_id, t_chat_id_0);
                 assert_eq!(bioauth_public_key, bioauth_public_key_0);
@@ -89,3 +113,1119 @@ fn process_block() {
         }
     }
 }
+
+#[test]
+#[traced_test]
+fn process_bioauth_events() {
+    let (mut logic, clock) = init_logic();
+    let bioauth_settings_map = BioauthSettingsMap::new();
+    let mut active_authentications_map = HashMap::new();
+
+    let timestamp = clock.now().as_millis() as u64;
+
+    let bioauth_public_key_0 = 0;
+    let t_chat_id_0 = 0;
+
+    logic.update_subscription(UpdateSubscriptionParams {
+        bioauth_public_key: bioauth_public_key_0,
+        t_chat_id: t_chat_id_0,
+    });
+
+    active_authentications_map.insert(bioauth_public_key_0, timestamp + 1000 * 60000);
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 1,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[
+            BioauthEvent::NewAuthentication {
+                bioauth_public_key: bioauth_public_key_0,
+            },
+            BioauthEvent::NewAuthentication {
+                bioauth_public_key: 1,
+            },
+        ],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 1);
+
+    match notifications[0] {
+        Notification::BioauthAuthenticatedNotification {
+            chat_id,
+            bioauth_public_key,
+            block_number,
+        } => {
+            assert_eq!(chat_id, t_chat_id_0);
+            assert_eq!(bioauth_public_key, bioauth_public_key_0);
+            assert_eq!(block_number, 1);
+        }
+        _ => panic!(),
+    }
+
+    active_authentications_map.remove(&bioauth_public_key_0);
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 2,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[BioauthEvent::AuthenticationExpired {
+            bioauth_public_key: bioauth_public_key_0,
+        }],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    // The expiration notification replaces the lost one.
+    assert_eq!(notifications.len(), 1);
+
+    match notifications[0] {
+        Notification::BioauthExpiredNotification {
+            chat_id,
+            bioauth_public_key,
+            block_number,
+        } => {
+            assert_eq!(chat_id, t_chat_id_0);
+            assert_eq!(bioauth_public_key, bioauth_public_key_0);
+            assert_eq!(block_number, 2);
+        }
+        _ => panic!(),
+    }
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 3,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 0);
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 12,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 1);
+    assert!(matches!(
+        notifications[0],
+        Notification::BioauthLostNotification { .. }
+    ));
+}
+
+#[test]
+#[traced_test]
+fn restore_bioauth() {
+    let (mut logic, clock) = init_logic();
+    let bioauth_settings_map = BioauthSettingsMap::new();
+    let mut active_authentications_map = HashMap::new();
+
+    let timestamp = clock.now().as_millis() as u64;
+    let expires_at_0 = timestamp + 1000 * 60000;
+
+    let bioauth_public_key_0 = 0;
+    let t_chat_id_0 = 0;
+
+    logic.update_subscription(UpdateSubscriptionParams {
+        bioauth_public_key: bioauth_public_key_0,
+        t_chat_id: t_chat_id_0,
+    });
+
+    active_authentications_map.insert(bioauth_public_key_0, expires_at_0);
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 1,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    // Active from the start, nothing to restore.
+    assert_eq!(notifications.len(), 0);
+
+    active_authentications_map.remove(&bioauth_public_key_0);
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 2,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 1);
+    assert!(matches!(
+        notifications[0],
+        Notification::BioauthLostNotification { .. }
+    ));
+
+    active_authentications_map.insert(bioauth_public_key_0, expires_at_0);
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 3,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[BioauthEvent::NewAuthentication {
+            bioauth_public_key: bioauth_public_key_0,
+        }],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    // The restored notification replaces the authenticated one.
+    assert_eq!(notifications.len(), 1);
+
+    match notifications[0] {
+        Notification::BioauthRestored {
+            chat_id,
+            bioauth_public_key,
+            expires_at,
+        } => {
+            assert_eq!(chat_id, t_chat_id_0);
+            assert_eq!(bioauth_public_key, bioauth_public_key_0);
+            assert_eq!(expires_at, expires_at_0);
+        }
+        _ => panic!(),
+    }
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 4,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 0);
+
+    active_authentications_map.remove(&bioauth_public_key_0);
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 5,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    // The throttling is reset on restore, so the new loss is reported immediately.
+    assert_eq!(notifications.len(), 1);
+    assert!(matches!(
+        notifications[0],
+        Notification::BioauthLostNotification { .. }
+    ));
+}
+
+#[test]
+#[traced_test]
+fn alert_fires_once_and_rearms() {
+    let (mut logic, clock) = init_logic();
+    let bioauth_settings_map = BioauthSettingsMap::new();
+    let mut active_authentications_map = HashMap::new();
+
+    let bioauth_public_key_0 = 0;
+    let t_chat_id_0 = 0;
+
+    logic.update_subscription(UpdateSubscriptionParams {
+        bioauth_public_key: bioauth_public_key_0,
+        t_chat_id: t_chat_id_0,
+    });
+
+    let alert_window = bioauth_settings_map
+        .get(&(t_chat_id_0, bioauth_public_key_0))
+        .alert_before_expiration_in_mins[0]
+        * 60000;
+    let alert_at = clock.now().as_millis() as u64 + 10_000;
+    active_authentications_map.insert(bioauth_public_key_0, alert_at + alert_window);
+
+    clock.advance(Duration::from_secs(9));
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 1,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 0);
+
+    clock.advance(Duration::from_secs(1));
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 2,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 1);
+    assert!(matches!(
+        notifications[0],
+        Notification::BioauthSoonExpiredAlert { .. }
+    ));
+
+    clock.advance(Duration::from_secs(60));
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 3,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    // The alert is fired once per authentication period.
+    assert_eq!(notifications.len(), 0);
+
+    let next_alert_at = clock.now().as_millis() as u64 + 100_000;
+    active_authentications_map.insert(bioauth_public_key_0, next_alert_at + alert_window);
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 4,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 0);
+
+    clock.set(Duration::from_millis(next_alert_at));
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 5,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    // The renewed authentication re-arms the alert.
+    assert_eq!(notifications.len(), 1);
+    assert!(matches!(
+        notifications[0],
+        Notification::BioauthSoonExpiredAlert { .. }
+    ));
+}
+
+#[test]
+#[traced_test]
+fn alert_failure_rollback() {
+    let (mut logic, clock) = init_logic();
+    let bioauth_settings_map = BioauthSettingsMap::new();
+    let mut active_authentications_map = HashMap::new();
+
+    let bioauth_public_key_0 = 0;
+    let t_chat_id_0 = 0;
+    let t_chat_id_1 = 1;
+
+    for t_chat_id in [t_chat_id_0, t_chat_id_1] {
+        logic.update_subscription(UpdateSubscriptionParams {
+            bioauth_public_key: bioauth_public_key_0,
+            t_chat_id,
+        });
+    }
+
+    active_authentications_map.insert(bioauth_public_key_0, clock.now().as_millis() as u64);
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 1,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 2);
+
+    logic.communicate_notification_failures(&[FailedNotification::BioauthSoonExpiredAlertFailed {
+        chat_id: t_chat_id_0,
+        bioauth_public_key: bioauth_public_key_0,
+        error: DeliveryError::Retryable,
+    }]);
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 2,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    // Only the failed alert is repeated.
+    assert_eq!(notifications.len(), 1);
+
+    match notifications[0] {
+        Notification::BioauthSoonExpiredAlert { chat_id, .. } => {
+            assert_eq!(chat_id, t_chat_id_0);
+        }
+        _ => panic!(),
+    }
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 3,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 0);
+}
+
+#[test]
+#[traced_test]
+fn lost_notification_failure_rollback() {
+    let (mut logic, _clock) = init_logic();
+    let bioauth_settings_map = BioauthSettingsMap::new();
+    let active_authentications_map = HashMap::new();
+
+    let bioauth_public_key_0 = 0;
+    let t_chat_id_0 = 0;
+
+    logic.update_subscription(UpdateSubscriptionParams {
+        bioauth_public_key: bioauth_public_key_0,
+        t_chat_id: t_chat_id_0,
+    });
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 1,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 1);
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 2,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 0);
+
+    logic.communicate_notification_failures(&[FailedNotification::BioauthLostNotificationFailed {
+        chat_id: t_chat_id_0,
+        bioauth_public_key: bioauth_public_key_0,
+        error: DeliveryError::Retryable,
+    }]);
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 3,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 1);
+    assert!(matches!(
+        notifications[0],
+        Notification::BioauthLostNotification { .. }
+    ));
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 4,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 0);
+}
+
+#[test]
+#[traced_test]
+fn lost_notification_throttling_boundaries() {
+    let (mut logic, _clock) = init_logic();
+    let mut bioauth_settings_map = BioauthSettingsMap::new();
+    let active_authentications_map = HashMap::new();
+
+    let bioauth_public_key_0 = 0;
+    let t_chat_id_0 = 0;
+    let max_message_frequency_in_blocks = 5;
+
+    logic.update_subscription(UpdateSubscriptionParams {
+        bioauth_public_key: bioauth_public_key_0,
+        t_chat_id: t_chat_id_0,
+    });
+    bioauth_settings_map.update(
+        (t_chat_id_0, bioauth_public_key_0),
+        BioauthSettings {
+            max_message_frequency_in_blocks,
+            ..BioauthSettings::default()
+        },
+    );
+
+    let notified_blocks: Vec<u32> = (10..=20)
+        .filter(|block_number| {
+            let notifications = logic.new_block(NewBlockParams {
+                block_number: *block_number,
+                block_timestamp: None,
+                active_authentications_map: &active_authentications_map,
+                bioauth_events: &[],
+                bioauth_settings_map: &bioauth_settings_map,
+            });
+            !notifications.is_empty()
+        })
+        .collect();
+
+    assert_eq!(notified_blocks, vec![10, 15, 20]);
+}
+
+#[test]
+#[traced_test]
+fn alert_uses_block_timestamp() {
+    let (mut logic, clock) = init_logic();
+    let bioauth_settings_map = BioauthSettingsMap::new();
+    let mut active_authentications_map = HashMap::new();
+
+    let bioauth_public_key_0 = 0;
+    let t_chat_id_0 = 0;
+
+    logic.update_subscription(UpdateSubscriptionParams {
+        bioauth_public_key: bioauth_public_key_0,
+        t_chat_id: t_chat_id_0,
+    });
+
+    let alert_window = bioauth_settings_map
+        .get(&(t_chat_id_0, bioauth_public_key_0))
+        .alert_before_expiration_in_mins[0]
+        * 60000;
+    let block_timestamp = clock.now().as_millis() as u64;
+    active_authentications_map.insert(bioauth_public_key_0, block_timestamp + alert_window);
+
+    // The local clock is ahead of the chain, it must not trigger the alert.
+    clock.advance(Duration::from_secs(3600));
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 1,
+        block_timestamp: Some(block_timestamp - 1),
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 0);
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 2,
+        block_timestamp: Some(block_timestamp),
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 1);
+    assert!(matches!(
+        notifications[0],
+        Notification::BioauthSoonExpiredAlert { .. }
+    ));
+}
+
+#[test]
+#[traced_test]
+fn staged_alerts() {
+    let (mut logic, clock) = init_logic();
+    let mut bioauth_settings_map = BioauthSettingsMap::new();
+    let mut active_authentications_map = HashMap::new();
+
+    let bioauth_public_key_0 = 0;
+    let t_chat_id_0 = 0;
+
+    logic.update_subscription(UpdateSubscriptionParams {
+        bioauth_public_key: bioauth_public_key_0,
+        t_chat_id: t_chat_id_0,
+    });
+    bioauth_settings_map.update_alert_before_expiration_in_mins(
+        (t_chat_id_0, bioauth_public_key_0),
+        vec![10, 1440, 60, 360],
+    );
+
+    let now = clock.now().as_millis() as u64;
+    let expires_at_0 = now + 2000 * 60000;
+    active_authentications_map.insert(bioauth_public_key_0, expires_at_0);
+
+    let mut alerted_at_mins_before_expiration = vec![];
+
+    for mins_before_expiration in (1..2000).rev() {
+        let notifications = logic.new_block(NewBlockParams {
+            block_number: 2000 - mins_before_expiration,
+            block_timestamp: Some(expires_at_0 - u64::from(mins_before_expiration) * 60000),
+            active_authentications_map: &active_authentications_map,
+            bioauth_events: &[],
+            bioauth_settings_map: &bioauth_settings_map,
+        });
+
+        for notification in notifications {
+            match notification {
+                Notification::BioauthSoonExpiredAlert { expires_at, .. } => {
+                    assert_eq!(expires_at, expires_at_0);
+                    alerted_at_mins_before_expiration.push(mins_before_expiration);
+                }
+                _ => panic!(),
+            }
+        }
+    }
+
+    // Each threshold is alerted exactly once per authentication period.
+    assert_eq!(alerted_at_mins_before_expiration, vec![1440, 360, 60, 10]);
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 2000,
+        block_timestamp: Some(expires_at_0 - 3 * 60000),
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 0);
+
+    // After a gap the overdue thresholds are collapsed into a single alert.
+    let expires_at_1 = expires_at_0 + 30 * 60000;
+    active_authentications_map.insert(bioauth_public_key_0, expires_at_1);
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 2001,
+        block_timestamp: Some(expires_at_1 - 30 * 60000),
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 1);
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 2002,
+        block_timestamp: Some(expires_at_1 - 10 * 60000),
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 1);
+}
+
+#[test]
+#[traced_test]
+fn restore_notification_states() {
+    let (mut logic, clock) = init_logic();
+    let bioauth_settings_map = BioauthSettingsMap::new();
+    let mut active_authentications_map = HashMap::new();
+
+    let bioauth_public_key_0 = 0;
+    let bioauth_public_key_1 = 1;
+    let t_chat_id_0 = 0;
+
+    for bioauth_public_key in [bioauth_public_key_0, bioauth_public_key_1] {
+        logic.update_subscription(UpdateSubscriptionParams {
+            bioauth_public_key,
+            t_chat_id: t_chat_id_0,
+        });
+    }
+
+    let expires_at = clock.now().as_millis() as u64 + 60_000;
+    active_authentications_map.insert(bioauth_public_key_0, expires_at);
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 1,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    // The alert for the first key and the lost notification for the second one.
+    assert_eq!(notifications.len(), 2);
+
+    let bioauths = logic
+        .notification_states()
+        .into_iter()
+        .map(|(t_chat_id, bioauth_public_key, state)| InitParamBioauth {
+            bioauth_public_key,
+            t_chat_id,
+            state,
+        })
+        .collect();
+
+    let mut logic = BioauthLogic::init(InitParams {
+        bioauths,
+        clock: clock.clone(),
+    });
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 2,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    // Neither the alert nor the lost notification is sent again after the restart.
+    assert_eq!(notifications.len(), 0);
+}
+
+#[test]
+#[traced_test]
+fn failure_rollback_is_per_subscription() {
+    let (mut logic, clock) = init_logic();
+    let bioauth_settings_map = BioauthSettingsMap::new();
+    let mut active_authentications_map = HashMap::new();
+
+    let bioauth_public_key_0 = 0;
+    let bioauth_public_key_1 = 1;
+    let t_chat_id_0 = 0;
+
+    for bioauth_public_key in [bioauth_public_key_0, bioauth_public_key_1] {
+        logic.update_subscription(UpdateSubscriptionParams {
+            bioauth_public_key,
+            t_chat_id: t_chat_id_0,
+        });
+        active_authentications_map.insert(bioauth_public_key, clock.now().as_millis() as u64);
+    }
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 1,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 2);
+
+    logic.communicate_notification_failures(&[
+        FailedNotification::BioauthSoonExpiredAlertFailed {
+            chat_id: t_chat_id_0,
+            bioauth_public_key: bioauth_public_key_0,
+            error: DeliveryError::Retryable,
+        },
+        FailedNotification::BioauthSoonExpiredAlertFailed {
+            chat_id: t_chat_id_0,
+            bioauth_public_key: bioauth_public_key_1,
+            error: DeliveryError::Permanent,
+        },
+    ]);
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 2,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    // Only the retryable failure is repeated, the permanent one is dropped.
+    assert_eq!(notifications.len(), 1);
+
+    match notifications[0] {
+        Notification::BioauthSoonExpiredAlert {
+            bioauth_public_key, ..
+        } => {
+            assert_eq!(bioauth_public_key, bioauth_public_key_0);
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+#[traced_test]
+fn migrate_chat() {
+    let (mut logic, _clock) = init_logic();
+    let bioauth_settings_map = BioauthSettingsMap::new();
+    let active_authentications_map = HashMap::new();
+
+    let bioauth_public_key_0 = 0;
+    let t_chat_id_0 = 0;
+    let t_chat_id_1 = 1;
+
+    logic.update_subscription(UpdateSubscriptionParams {
+        bioauth_public_key: bioauth_public_key_0,
+        t_chat_id: t_chat_id_0,
+    });
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 1,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 1);
+
+    logic.migrate_chat(t_chat_id_0, t_chat_id_1);
+
+    let notification_states = logic.notification_states();
+    assert_eq!(notification_states.len(), 1);
+    assert_eq!(notification_states[0].0, t_chat_id_1);
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 2,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    // The throttling state is kept for the new chat id.
+    assert_eq!(notifications.len(), 0);
+}
+
+#[test]
+#[traced_test]
+fn migrate_chat_keeps_existing_subscriptions() {
+    let (mut logic, _clock) = init_logic();
+    let bioauth_settings_map = BioauthSettingsMap::new();
+    let active_authentications_map = HashMap::new();
+
+    let bioauth_public_key_0 = 0;
+    let t_chat_id_0 = 0;
+    let t_chat_id_1 = 1;
+
+    for t_chat_id in [t_chat_id_0, t_chat_id_1] {
+        logic.update_subscription(UpdateSubscriptionParams {
+            bioauth_public_key: bioauth_public_key_0,
+            t_chat_id,
+        });
+    }
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 1,
+        block_timestamp: None,
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 2);
+    assert!(logic.acknowledge(t_chat_id_1, &bioauth_public_key_0));
+
+    logic.migrate_chat(t_chat_id_0, t_chat_id_1);
+
+    // The state of the subscription the new chat already has is not overwritten.
+    let notification_states = logic.notification_states();
+    assert_eq!(notification_states.len(), 1);
+    assert_eq!(notification_states[0].0, t_chat_id_1);
+    assert!(notification_states[0].2.acknowledged);
+}
+
+#[test]
+#[traced_test]
+fn quiet_hours_defer_repeats() {
+    // The local midnight of the chat in UTC+1.
+    let midnight = 1_699_920_000_000 - 60 * 60000;
+    let at = |hours: u64, mins: u64| midnight + (hours * 60 + mins) * 60000;
+    let chat_settings = ChatSettings {
+        timezone_offset_in_mins: 60,
+        quiet_hours: Some(QuietHours {
+            start_in_mins: 22 * 60,
+            end_in_mins: 7 * 60,
+        }),
+        ..ChatSettings::default()
+    };
+
+    let bioauth_public_key_0 = 0;
+    let t_chat_id_0 = 0;
+
+    // The loss is notified once in the quiet hours, the repeats wait until they end.
+    let (mut logic, _clock) = init_logic();
+    let mut bioauth_settings_map = BioauthSettingsMap::new();
+    let mut active_authentications_map = HashMap::new();
+
+    logic.update_subscription(UpdateSubscriptionParams {
+        bioauth_public_key: bioauth_public_key_0,
+        t_chat_id: t_chat_id_0,
+    });
+    bioauth_settings_map.update(
+        (t_chat_id_0, bioauth_public_key_0),
+        BioauthSettings {
+            max_message_frequency_in_blocks: 5,
+            ..BioauthSettings::default()
+        },
+    );
+    bioauth_settings_map.update_chat(t_chat_id_0, chat_settings.clone());
+
+    let notified_blocks: Vec<u32> = (10..=20)
+        .map(|block_number| (block_number, at(23, block_number.into())))
+        .chain([(21, at(24 + 7, 0))])
+        .filter(|(block_number, block_timestamp)| {
+            let notifications = logic.new_block(NewBlockParams {
+                block_number: *block_number,
+                block_timestamp: Some(*block_timestamp),
+                active_authentications_map: &active_authentications_map,
+                bioauth_events: &[],
+                bioauth_settings_map: &bioauth_settings_map,
+            });
+            !notifications.is_empty()
+        })
+        .map(|(block_number, _)| block_number)
+        .collect();
+
+    assert_eq!(notified_blocks, vec![10, 21]);
+
+    // Only the final alert is sent in the quiet hours.
+    let (mut logic, _clock) = init_logic();
+
+    logic.update_subscription(UpdateSubscriptionParams {
+        bioauth_public_key: bioauth_public_key_0,
+        t_chat_id: t_chat_id_0,
+    });
+    bioauth_settings_map
+        .update_alert_before_expiration_in_mins((t_chat_id_0, bioauth_public_key_0), vec![60, 10]);
+
+    let expires_at_0 = at(2, 0);
+    active_authentications_map.insert(bioauth_public_key_0, expires_at_0);
+
+    let alerted_at_mins_before_expiration: Vec<u64> = [60, 30, 10, 5]
+        .into_iter()
+        .enumerate()
+        .filter(|(block_number, mins_before_expiration)| {
+            let notifications = logic.new_block(NewBlockParams {
+                block_number: *block_number as u32 + 1,
+                block_timestamp: Some(expires_at_0 - mins_before_expiration * 60000),
+                active_authentications_map: &active_authentications_map,
+                bioauth_events: &[],
+                bioauth_settings_map: &bioauth_settings_map,
+            });
+            !notifications.is_empty()
+        })
+        .map(|(_, mins_before_expiration)| mins_before_expiration)
+        .collect();
+
+    assert_eq!(alerted_at_mins_before_expiration, vec![10]);
+
+    // Without the quiet hours the earlier alert is sent as usual.
+    bioauth_settings_map.update_chat(t_chat_id_0, ChatSettings::default());
+    let expires_at_1 = expires_at_0 + 24 * 60 * 60000;
+    active_authentications_map.insert(bioauth_public_key_0, expires_at_1);
+
+    let notifications = logic.new_block(NewBlockParams {
+        block_number: 10,
+        block_timestamp: Some(expires_at_1 - 60 * 60000),
+        active_authentications_map: &active_authentications_map,
+        bioauth_events: &[],
+        bioauth_settings_map: &bioauth_settings_map,
+    });
+
+    assert_eq!(notifications.len(), 1);
+}
+
+#[test]
+#[traced_test]
+fn acknowledge_and_snooze_stop_repeats() {
+    let (mut logic, clock) = init_logic();
+    let mut bioauth_settings_map = BioauthSettingsMap::new();
+    let mut active_authentications_map = HashMap::new();
+
+    let bioauth_public_key_0 = 0;
+    let t_chat_id_0 = 0;
+
+    logic.update_subscription(UpdateSubscriptionParams {
+        bioauth_public_key: bioauth_public_key_0,
+        t_chat_id: t_chat_id_0,
+    });
+    bioauth_settings_map.update(
+        (t_chat_id_0, bioauth_public_key_0),
+        BioauthSettings {
+            max_message_frequency_in_blocks: 5,
+            ..BioauthSettings::default()
+        },
+    );
+
+    // Nothing to acknowledge or snooze until the validator is lost.
+    assert!(!logic.acknowledge(t_chat_id_0, &bioauth_public_key_0));
+    assert!(!logic.snooze(t_chat_id_0, &bioauth_public_key_0, 60));
+
+    let notified_blocks = |logic: &mut BioauthLogic<usize, ManualClock>,
+                           active_authentications_map: &HashMap<usize, u64>,
+                           block_numbers: std::ops::RangeInclusive<u32>| {
+        block_numbers
+            .filter(|block_number| {
+                !logic
+                    .new_block(NewBlockParams {
+                        block_number: *block_number,
+                        block_timestamp: None,
+                        active_authentications_map,
+                        bioauth_events: &[],
+                        bioauth_settings_map: &bioauth_settings_map,
+                    })
+                    .is_empty()
+            })
+            .collect::<Vec<u32>>()
+    };
+
+    // The acknowledged loss is not repeated.
+    assert_eq!(
+        notified_blocks(&mut logic, &active_authentications_map, 10..=10),
+        vec![10]
+    );
+    assert!(logic.acknowledge(t_chat_id_0, &bioauth_public_key_0));
+    assert_eq!(
+        notified_blocks(&mut logic, &active_authentications_map, 11..=20),
+        Vec::<u32>::new()
+    );
+
+    // The restoration resets the acknowledgement.
+    active_authentications_map.insert(bioauth_public_key_0, NOW.as_millis() as u64 + 86_400_000);
+    assert_eq!(
+        notified_blocks(&mut logic, &active_authentications_map, 21..=21),
+        vec![21]
+    );
+    active_authentications_map.clear();
+    assert_eq!(
+        notified_blocks(&mut logic, &active_authentications_map, 22..=22),
+        vec![22]
+    );
+
+    // The snoozed loss is repeated once the snooze is over.
+    assert!(logic.snooze(t_chat_id_0, &bioauth_public_key_0, 60));
+    assert_eq!(
+        notified_blocks(&mut logic, &active_authentications_map, 23..=30),
+        Vec::<u32>::new()
+    );
+    clock.advance(Duration::from_secs(3600));
+    assert_eq!(
+        notified_blocks(&mut logic, &active_authentications_map, 31..=31),
+        vec![31]
+    );
+}
+
+#[test]
+#[traced_test]
+fn snooze_counts_from_block_timestamp() {
+    let (mut logic, _clock) = init_logic();
+    let mut bioauth_settings_map = BioauthSettingsMap::new();
+    let active_authentications_map = HashMap::new();
+
+    let bioauth_public_key_0 = 0;
+    let t_chat_id_0 = 0;
+
+    logic.update_subscription(UpdateSubscriptionParams {
+        bioauth_public_key: bioauth_public_key_0,
+        t_chat_id: t_chat_id_0,
+    });
+    bioauth_settings_map.update(
+        (t_chat_id_0, bioauth_public_key_0),
+        BioauthSettings {
+            max_message_frequency_in_blocks: 1,
+            ..BioauthSettings::default()
+        },
+    );
+
+    // The chain lags a day behind the clock.
+    let block_timestamp = NOW.as_millis() as u64 - 86_400_000;
+    let new_block =
+        |logic: &mut BioauthLogic<usize, ManualClock>, block_number: u32, block_timestamp: u64| {
+            !logic
+                .new_block(NewBlockParams {
+                    block_number,
+                    block_timestamp: Some(block_timestamp),
+                    active_authentications_map: &active_authentications_map,
+                    bioauth_events: &[],
+                    bioauth_settings_map: &bioauth_settings_map,
+                })
+                .is_empty()
+        };
+
+    assert!(new_block(&mut logic, 10, block_timestamp));
+    assert!(logic.snooze(t_chat_id_0, &bioauth_public_key_0, 60));
+
+    // The snooze is over an hour of the chain time later, not the clock time.
+    assert!(!new_block(&mut logic, 11, block_timestamp + 3_599_999));
+    assert!(new_block(&mut logic, 12, block_timestamp + 3_600_000));
+}
+
+#[test]
+#[traced_test]
+fn unacknowledged_loss_escalation() {
+    let bioauth_public_key_0 = 0;
+    let t_chat_id_0 = 0;
+    let backup_chat_id = 100;
+    let third_level_chat_id = 200;
+
+    let mut bioauth_settings_map = BioauthSettingsMap::new();
+    let active_authentications_map = HashMap::new();
+    bioauth_settings_map.update(
+        (t_chat_id_0, bioauth_public_key_0),
+        BioauthSettings {
+            max_message_frequency_in_blocks: 1000,
+            escalation_levels: vec![
+                EscalationLevel {
+                    chat_id: backup_chat_id,
+                    after_in_mins: 10,
+                },
+                EscalationLevel {
+                    chat_id: third_level_chat_id,
+                    after_in_mins: 30,
+                },
+            ],
+            ..BioauthSettings::default()
+        },
+    );
+
+    let escalated_chats = |logic: &mut BioauthLogic<usize, ManualClock>, block_number: u32| {
+        logic
+            .new_block(NewBlockParams {
+                block_number,
+                block_timestamp: None,
+                active_authentications_map: &active_authentications_map,
+                bioauth_events: &[],
+                bioauth_settings_map: &bioauth_settings_map,
+            })
+            .into_iter()
+            .filter_map(|notification| match notification {
+                Notification::BioauthLossEscalated {
+                    chat_id,
+                    from_chat_id,
+                    ..
+                } => {
+                    assert_eq!(from_chat_id, t_chat_id_0);
+                    Some(chat_id)
+                }
+                _ => None,
+            })
+            .collect::<Vec<i64>>()
+    };
+
+    // The loss is escalated to the backup chat and then to the third level one.
+    let (mut logic, clock) = init_logic();
+    logic.update_subscription(UpdateSubscriptionParams {
+        bioauth_public_key: bioauth_public_key_0,
+        t_chat_id: t_chat_id_0,
+    });
+
+    assert_eq!(escalated_chats(&mut logic, 1), Vec::<i64>::new());
+    clock.advance(Duration::from_secs(10 * 60));
+    assert_eq!(escalated_chats(&mut logic, 2), vec![backup_chat_id]);
+    clock.advance(Duration::from_secs(10 * 60));
+    assert_eq!(escalated_chats(&mut logic, 3), Vec::<i64>::new());
+    clock.advance(Duration::from_secs(10 * 60));
+    assert_eq!(escalated_chats(&mut logic, 4), vec![third_level_chat_id]);
+    clock.advance(Duration::from_secs(60 * 60));
+    assert_eq!(escalated_chats(&mut logic, 5), Vec::<i64>::new());
+
+    // The acknowledged loss is not escalated any further.
+    let (mut logic, clock) = init_logic();
+    logic.update_subscription(UpdateSubscriptionParams {
+        bioauth_public_key: bioauth_public_key_0,
+        t_chat_id: t_chat_id_0,
+    });
+
+    assert_eq!(escalated_chats(&mut logic, 1), Vec::<i64>::new());
+    clock.advance(Duration::from_secs(10 * 60));
+    assert_eq!(escalated_chats(&mut logic, 2), vec![backup_chat_id]);
+    assert!(logic.acknowledge(t_chat_id_0, &bioauth_public_key_0));
+    clock.advance(Duration::from_secs(60 * 60));
+    assert_eq!(escalated_chats(&mut logic, 3), Vec::<i64>::new());
+}
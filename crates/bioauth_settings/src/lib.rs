@@ -3,34 +3,164 @@
 use std::{
     collections::{HashMap, HashSet},
     hash::Hash,
+    sync::OnceLock,
 };
 
 #[derive(Debug, Clone)]
 pub struct BioauthSettings {
     pub max_message_frequency_in_blocks: u32,
-    pub alert_before_expiration_in_mins: u64,
+    /// The staged alert thresholds, sorted from the earliest to the latest one.
+    pub alert_before_expiration_in_mins: Vec<u64>,
+    /// The human-friendly name of the validator.
+    pub label: Option<String>,
+    /// The backup chats the unacknowledged loss is escalated to, sorted by the escalation delay.
+    pub escalation_levels: Vec<EscalationLevel>,
 }
 
 impl Default for BioauthSettings {
     fn default() -> Self {
         BioauthSettings {
-            alert_before_expiration_in_mins: 60,
+            alert_before_expiration_in_mins: vec![60],
             max_message_frequency_in_blocks: 10,
+            label: None,
+            escalation_levels: vec![],
         }
     }
 }
 
-const DEFAULT_SETTINGS: BioauthSettings = BioauthSettings {
-    alert_before_expiration_in_mins: 60,
-    max_message_frequency_in_blocks: 10,
-};
+/// The backup chat the loss is escalated to if it is not acknowledged in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EscalationLevel {
+    pub chat_id: i64,
+    /// The delay since the loss.
+    pub after_in_mins: u64,
+}
+
+impl EscalationLevel {
+    /// The backup chat and then the third level one.
+    pub const MAX_LEVELS: usize = 2;
+
+    /// The latest escalation, a week after the loss.
+    pub const MAX_AFTER_IN_MINS: u64 = 7 * 24 * 60;
+}
+
+static DEFAULT_SETTINGS: OnceLock<BioauthSettings> = OnceLock::new();
+
+/// Sort the alert thresholds from the earliest to the latest one and remove the duplicates.
+pub fn normalize_alert_thresholds(mut alert_before_expiration_in_mins: Vec<u64>) -> Vec<u64> {
+    alert_before_expiration_in_mins.sort_unstable_by(|a, b| b.cmp(a));
+    alert_before_expiration_in_mins.dedup();
+    alert_before_expiration_in_mins
+}
+
+/// The local time window the non-critical repeated notifications are deferred in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    /// The start of the window in minutes since the local midnight.
+    pub start_in_mins: u16,
+    /// The end of the window in minutes since the local midnight, it can be past the midnight.
+    pub end_in_mins: u16,
+}
+
+impl QuietHours {
+    pub const MINS_PER_DAY: u16 = 24 * 60;
+
+    pub fn contains(&self, local_time_in_mins: u16) -> bool {
+        if self.start_in_mins <= self.end_in_mins {
+            (self.start_in_mins..self.end_in_mins).contains(&local_time_in_mins)
+        } else {
+            local_time_in_mins >= self.start_in_mins || local_time_in_mins < self.end_in_mins
+        }
+    }
+}
+
+/// The settings shared by all the subscriptions of the chat.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChatSettings {
+    /// The offset of the chat local time from UTC.
+    pub timezone_offset_in_mins: i32,
+    pub quiet_hours: Option<QuietHours>,
+    /// The language of the bot texts, detected from the user if it is not set.
+    pub language: Option<String>,
+}
+
+impl ChatSettings {
+    /// Whether the moment, in milliseconds since the Unix epoch, is within the quiet hours.
+    pub fn is_quiet_at(&self, timestamp: u64) -> bool {
+        let Some(quiet_hours) = self.quiet_hours else {
+            return false;
+        };
+
+        let mins_per_day = i64::from(QuietHours::MINS_PER_DAY);
+        let utc_in_mins = (timestamp / 60000) as i64;
+        let local_time_in_mins =
+            (utc_in_mins + i64::from(self.timezone_offset_in_mins)).rem_euclid(mins_per_day);
+
+        quiet_hours.contains(local_time_in_mins as u16)
+    }
+}
+
+static DEFAULT_CHAT_SETTINGS: OnceLock<ChatSettings> = OnceLock::new();
+
+/// The kind of the validator notification, the chat can customize the text of each one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum NotificationKind {
+    Lost,
+    SoonExpired,
+    Restored,
+    Authenticated,
+    Expired,
+    Removed,
+    /// The unacknowledged loss sent to the backup chat.
+    Escalated,
+}
+
+impl NotificationKind {
+    pub const ALL: [NotificationKind; 7] = [
+        NotificationKind::Lost,
+        NotificationKind::SoonExpired,
+        NotificationKind::Restored,
+        NotificationKind::Authenticated,
+        NotificationKind::Expired,
+        NotificationKind::Removed,
+        NotificationKind::Escalated,
+    ];
+
+    /// The name the kind is stored and referred to by the users with.
+    pub fn name(self) -> &'static str {
+        match self {
+            NotificationKind::Lost => "lost",
+            NotificationKind::SoonExpired => "soon_expired",
+            NotificationKind::Restored => "restored",
+            NotificationKind::Authenticated => "authenticated",
+            NotificationKind::Expired => "expired",
+            NotificationKind::Removed => "removed",
+            NotificationKind::Escalated => "escalated",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        NotificationKind::ALL
+            .into_iter()
+            .find(|kind| kind.name() == name)
+    }
+}
 
 #[derive(Debug, Clone)]
-pub struct BioauthSettingsMap<Key>(HashMap<(i64, Key), BioauthSettings>);
+pub struct BioauthSettingsMap<Key> {
+    subscriptions: HashMap<(i64, Key), BioauthSettings>,
+    chats: HashMap<i64, ChatSettings>,
+    /// The custom notification texts of the chats.
+    templates: HashMap<(i64, NotificationKind), String>,
+}
 
 impl<Key> Default for BioauthSettingsMap<Key> {
     fn default() -> Self {
-        Self(HashMap::new())
+        Self {
+            subscriptions: HashMap::new(),
+            chats: HashMap::new(),
+            templates: HashMap::new(),
+        }
     }
 }
 
@@ -45,16 +175,21 @@ where
     Key: Clone + Hash + Eq,
 {
     pub fn get(&self, key: &(i64, Key)) -> &BioauthSettings {
-        let opt_value = self.0.get(key);
+        let opt_value = self.subscriptions.get(key);
         match opt_value {
-            None => &DEFAULT_SETTINGS,
+            None => DEFAULT_SETTINGS.get_or_init(BioauthSettings::default),
             Some(val) => val,
         }
     }
 
+    /// The settings of the existing subscription only, unlike `get` there is no default.
+    pub fn get_mut(&mut self, key: &(i64, Key)) -> Option<&mut BioauthSettings> {
+        self.subscriptions.get_mut(key)
+    }
+
     pub fn get_all_subscriptions_by_id(&self, by_id: i64) -> HashSet<Key> {
         let mut subscriptions = HashSet::new();
-        self.0.keys().for_each(|(id, key)| {
+        self.subscriptions.keys().for_each(|(id, key)| {
             if by_id != *id {
                 return;
             }
@@ -65,36 +200,118 @@ where
     }
 
     pub fn update(&mut self, key: (i64, Key), settings: BioauthSettings) {
-        self.0.insert(key, settings);
+        self.subscriptions.insert(key, settings);
     }
 
     pub fn remove(&mut self, key: &(i64, Key)) {
-        self.0.remove(key);
+        self.subscriptions.remove(key);
     }
 
     pub fn remove_all_by_id(&mut self, by_id: i64) {
         let keys_to_remove: Vec<(i64, Key)> = self
-            .0
+            .subscriptions
             .keys()
             .filter(|(id, _)| id == &by_id)
             .cloned()
             .collect();
 
         for key in keys_to_remove {
-            self.0.remove(&key);
+            self.subscriptions.remove(&key);
+        }
+    }
+
+    pub fn get_chat(&self, chat_id: i64) -> &ChatSettings {
+        self.chats
+            .get(&chat_id)
+            .unwrap_or_else(|| DEFAULT_CHAT_SETTINGS.get_or_init(ChatSettings::default))
+    }
+
+    pub fn update_chat(&mut self, chat_id: i64, chat_settings: ChatSettings) {
+        self.chats.insert(chat_id, chat_settings);
+    }
+
+    pub fn get_template(&self, chat_id: i64, kind: NotificationKind) -> Option<&str> {
+        self.templates.get(&(chat_id, kind)).map(String::as_str)
+    }
+
+    /// Set the custom notification text, `None` restores the default one.
+    pub fn update_template(
+        &mut self,
+        chat_id: i64,
+        kind: NotificationKind,
+        template: Option<String>,
+    ) {
+        match template {
+            Some(template) => {
+                self.templates.insert((chat_id, kind), template);
+            }
+            None => {
+                self.templates.remove(&(chat_id, kind));
+            }
+        }
+    }
+
+    /// The chats escalating the loss of the validator to the backup chat.
+    pub fn get_escalating_chats(&self, key: &Key, backup_chat_id: i64) -> Vec<i64> {
+        self.subscriptions
+            .iter()
+            .filter(|((_, subscription_key), settings)| {
+                subscription_key == key
+                    && settings
+                        .escalation_levels
+                        .iter()
+                        .any(|level| level.chat_id == backup_chat_id)
+            })
+            .map(|((chat_id, _), _)| *chat_id)
+            .collect()
+    }
+
+    pub fn migrate_by_id(&mut self, from_id: i64, to_id: i64) {
+        for settings in self.subscriptions.values_mut() {
+            for level in &mut settings.escalation_levels {
+                if level.chat_id == from_id {
+                    level.chat_id = to_id;
+                }
+            }
+        }
+
+        // The settings the new chat already has take precedence over the migrated ones.
+        if let Some(chat_settings) = self.chats.remove(&from_id) {
+            self.chats.entry(to_id).or_insert(chat_settings);
+        }
+
+        for kind in NotificationKind::ALL {
+            if let Some(template) = self.templates.remove(&(from_id, kind)) {
+                self.templates.entry((to_id, kind)).or_insert(template);
+            }
+        }
+
+        let keys_to_migrate: Vec<(i64, Key)> = self
+            .subscriptions
+            .keys()
+            .filter(|(id, _)| id == &from_id)
+            .cloned()
+            .collect();
+
+        for key in keys_to_migrate {
+            if let Some(settings) = self.subscriptions.remove(&key) {
+                self.subscriptions.entry((to_id, key.1)).or_insert(settings);
+            }
         }
     }
 
     pub fn update_alert_before_expiration_in_mins(
         &mut self,
         key: (i64, Key),
-        alert_before_expiration_in_mins: u64,
+        alert_before_expiration_in_mins: Vec<u64>,
     ) {
-        let value = self.0.get_mut(&key);
+        let alert_before_expiration_in_mins =
+            normalize_alert_thresholds(alert_before_expiration_in_mins);
+        let value = self.subscriptions.get_mut(&key);
 
         match value {
             None => {
-                self.0.insert(
+                self.subscriptions.insert(
                     key.clone(),
                     BioauthSettings {
                         alert_before_expiration_in_mins,
@@ -113,11 +330,11 @@ where
         key: (i64, Key),
         max_message_frequency_in_blocks: u32,
     ) {
-        let value = self.0.get_mut(&key);
+        let value = self.subscriptions.get_mut(&key);
 
         match value {
             None => {
-                self.0.insert(
+                self.subscriptions.insert(
                     key,
                     BioauthSettings {
                         max_message_frequency_in_blocks,
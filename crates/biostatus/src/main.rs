@@ -15,15 +15,20 @@ use tokio::sync::RwLock;
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
     tracing_subscriber::fmt::init();
-    let rpc_url: String = envfury::must("RPC_URL")?;
+    let rpc_urls_str: String = envfury::must("RPC_URL")?;
     let redis_url: String = envfury::must("REDIS_URL")?;
     let telegram_token: String = envfury::must("TELOXIDE_TOKEN")?;
     let database_url: String = envfury::must("DATABASE_URL")?;
+    let rpc_urls = rpc_urls_str.split(',').map(str::to_owned).collect();
     let admin_chat_ids_str: String = envfury::must("ADMIN_CHAT_IDS")?;
     let admin_chat_ids = admin_chat_ids_str
         .split(',')
         .map(|id| id.parse::<i64>().unwrap())
         .collect();
+    let explorer_url: String = envfury::or(
+        "EXPLORER_URL",
+        "https://humanode.subscan.io/account/{address}".to_owned(),
+    )?;
 
     let reqwest = teloxide::net::default_reqwest_settings().build()?;
     let storage = RedisStorage::open(redis_url, Bincode)
@@ -44,18 +49,26 @@ async fn main() -> Result<(), anyhow::Error> {
             .unwrap()
     };
     let db = database::db::Db { pool: db_pool };
-    let api = block_subscription::BlockSubscription::construct_api(rpc_url).await?;
-    let block_subscription = block_subscription::BlockSubscription::subscribe(api).await?;
+    let block_subscription = block_subscription::BlockSubscription::subscribe(
+        rpc_urls,
+        block_subscription::ReconnectParams::default(),
+        block_subscription::HealthCheckParams::default(),
+    )
+    .await?;
     let bioauth_settings_map = bioauth_settings::BioauthSettingsMap::new();
     let rw_bioauth_settings_map = Arc::new(RwLock::new(bioauth_settings_map));
     let dev_subscriptions_map = dev_subscriptions::DevSubscriptionMap::new();
     let rw_dev_subscriptions_map = Arc::new(RwLock::new(dev_subscriptions_map));
+    let rw_active_authentications =
+        Arc::new(RwLock::new(telegram::ActiveAuthentications::default()));
     let telegram = telegram::Telegram {
         bot,
         storage,
         rw_bioauth_settings_map: Arc::clone(&rw_bioauth_settings_map),
         rw_dev_subscriptions_map: Arc::clone(&rw_dev_subscriptions_map),
+        rw_active_authentications: Arc::clone(&rw_active_authentications),
         admin_chat_ids,
+        explorer_url,
     };
 
     telegram.set_commands().await?;
@@ -65,13 +78,14 @@ async fn main() -> Result<(), anyhow::Error> {
 
     tracing::info!("Telegram commands successfully setup");
 
-    let mut loops = main_loop::run(main_loop::Params {
+    let loops = main_loop::run(main_loop::Params {
         block_subscription,
         db,
         subscription_update_handle,
         telegram_notification_handle,
         rw_bioauth_settings_map: Arc::clone(&rw_bioauth_settings_map),
         rw_dev_subscriptions_map: Arc::clone(&rw_dev_subscriptions_map),
+        rw_active_authentications: Arc::clone(&rw_active_authentications),
     })
     .await?;
 
@@ -6,9 +6,17 @@
     clippy::multiple_crate_versions
 )]
 
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    ops::RangeInclusive,
+    time::{Duration, Instant},
+};
 
-use subxt::{backend::StreamOfResults, blocks::Block, OnlineClient, PolkadotConfig};
+use subxt::{
+    backend::{legacy::LegacyRpcMethods, rpc::RpcClient, StreamOfResults},
+    blocks::Block,
+    OnlineClient, PolkadotConfig,
+};
 
 /// The generated runtime data.
 mod r#gen {
@@ -18,48 +26,653 @@ mod r#gen {
 }
 pub use r#gen::humanode;
 
+type FinalizedBlock = Block<PolkadotConfig, OnlineClient<PolkadotConfig>>;
+type FinalizedBlocks = StreamOfResults<FinalizedBlock>;
+
+/// The default limit of missed blocks to be backfilled at once, roughly a day of blocks.
+pub const DEFAULT_MAX_BACKFILL_IN_BLOCKS: u32 = 14_400;
+
+/// The default time limit for fetching a single missed block.
+pub const DEFAULT_BACKFILL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Reconnection settings.
+#[derive(Debug, Clone)]
+pub struct ReconnectParams {
+    /// The delay before the first reconnection attempt.
+    pub initial_delay: Duration,
+    /// The upper bound for the exponentially growing delay.
+    pub max_delay: Duration,
+    /// The stream is considered dead if no finalized block is received within this time.
+    pub stall_timeout: Duration,
+}
+
+impl Default for ReconnectParams {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            stall_timeout: Duration::from_secs(120),
+        }
+    }
+}
+
+/// The counter of the failed reconnection attempts with the exponentially growing delay between them.
+#[derive(Debug, Default)]
+struct Backoff {
+    attempt: u32,
+}
+
+impl Backoff {
+    /// Count the new attempt, returns its number and the delay to wait before it.
+    ///
+    /// The first attempt is made immediately.
+    fn start_attempt(&mut self, params: &ReconnectParams) -> (u32, Duration) {
+        let delay = match self.attempt {
+            0 => Duration::ZERO,
+            _ => self.next_delay(params),
+        };
+        self.attempt = self.attempt.saturating_add(1);
+        (self.attempt, delay)
+    }
+
+    /// The delay before the next attempt once the current one has failed.
+    fn next_delay(&self, params: &ReconnectParams) -> Duration {
+        params
+            .initial_delay
+            .saturating_mul(2u32.saturating_pow(self.attempt.saturating_sub(1)))
+            .min(params.max_delay)
+    }
+
+    fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+/// Endpoints health check settings.
+#[derive(Debug, Clone)]
+pub struct HealthCheckParams {
+    /// How often the endpoints are probed.
+    pub interval: Duration,
+    /// The time limit for a single endpoint probe.
+    pub probe_timeout: Duration,
+    /// An endpoint is unhealthy if its finalized head is behind the best known one by more blocks.
+    pub max_lag_in_blocks: u32,
+}
+
+impl Default for HealthCheckParams {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(60),
+            probe_timeout: Duration::from_secs(10),
+            max_lag_in_blocks: 5,
+        }
+    }
+}
+
+/// The connection to an endpoint.
+#[derive(Debug, Clone)]
+struct Client {
+    api: OnlineClient<PolkadotConfig>,
+    /// The legacy RPC methods over the same connection, used to look up the blocks by number.
+    rpc: LegacyRpcMethods<PolkadotConfig>,
+}
+
+impl Client {
+    async fn connect(url: &str) -> Result<Self, subxt::Error> {
+        let rpc_client = RpcClient::from_insecure_url(url).await?;
+        let api = OnlineClient::<PolkadotConfig>::from_rpc_client(rpc_client.clone()).await?;
+        let rpc = LegacyRpcMethods::new(rpc_client);
+        Ok(Self { api, rpc })
+    }
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    url: String,
+    client: Option<Client>,
+}
+
+/// The finalized blocks subscription over a prioritized list of RPC endpoints.
+///
+/// The first endpoint has the highest priority.
 #[derive(Debug)]
 pub struct BlockSubscription {
     pub api: OnlineClient<PolkadotConfig>,
-    pub subscription: StreamOfResults<Block<PolkadotConfig, OnlineClient<PolkadotConfig>>>,
+    pub subscription: Option<FinalizedBlocks>,
+    pub reconnect_params: ReconnectParams,
+    pub health_check_params: HealthCheckParams,
+    /// Older missed blocks are not backfilled.
+    pub max_backfill_in_blocks: u32,
+    /// The time limit for fetching a single missed block.
+    pub backfill_timeout: Duration,
+    rpc: LegacyRpcMethods<PolkadotConfig>,
+    endpoints: Vec<Endpoint>,
+    active_endpoint: usize,
+    reconnect_backoff: Backoff,
+    last_health_check: Instant,
+    last_block_number: Option<u32>,
+    /// The received block the missed blocks are being backfilled up to.
+    backfill_to: Option<u32>,
 }
 
 #[derive(Debug)]
 pub struct BlockInfo {
     pub active_authentications_map: HashMap<ValidatorPublicKey, u64>,
     pub block_number: u32,
+    /// The `Timestamp::Now` of the block, the chain moment in milliseconds.
+    pub timestamp: Option<u64>,
+    /// The bioauth pallet events emitted in the block.
+    pub bioauth_events: Vec<BioauthEvent>,
+    /// The url of the endpoint the block was received from.
+    pub endpoint: String,
+}
+
+/// The decoded bioauth pallet event concerning a single validator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BioauthEvent {
+    NewAuthentication {
+        validator_public_key: ValidatorPublicKey,
+    },
+    AuthenticationExpired {
+        validator_public_key: ValidatorPublicKey,
+    },
+    AuthenticationRemoved {
+        validator_public_key: ValidatorPublicKey,
+        reason: DeauthenticationReason,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeauthenticationReason {
+    Offence,
+}
+
+/// Connection state transitions of the block subscription.
+#[derive(Debug)]
+pub enum ConnectionEvent {
+    /// The finalized blocks stream has ended, failed or stalled.
+    Disconnected { url: String, reason: String },
+    /// The reconnection attempt to every endpoint has failed, the next one is scheduled after the delay.
+    ReconnectFailed {
+        attempt: u32,
+        next_delay: Duration,
+        error: subxt::Error,
+    },
+    /// The connection and the finalized blocks subscription are re-established.
+    Reconnected { url: String, attempts: u32 },
+    /// The subscription was moved to another endpoint by the health check.
+    FailedOver {
+        from: String,
+        to: String,
+        reason: FailoverReason,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FailoverReason {
+    /// The endpoint with a higher priority is healthy again.
+    PreferredEndpointRecovered,
+    /// The finalized head of the active endpoint is lagging behind.
+    ActiveEndpointLagging { head: Option<u32>, best_head: u32 },
+}
+
+#[derive(Debug)]
+pub enum Event {
+    NewBlock(BlockInfo),
+    /// The received block was already processed, e.g. after failing over to a lagging endpoint.
+    AlreadyProcessed {
+        block_number: u32,
+    },
+    Connection(ConnectionEvent),
 }
 
 #[derive(Debug)]
 pub enum NewBlockError {
-    BlockNotReceived,
-    SubscriptionBlocksError(subxt::Error),
     ActiveAuthenticationNotReceived(subxt::Error),
+    TimestampNotReceived(subxt::Error),
+    MissedBlockNotReceived(subxt::Error),
+    MissedBlockTimedOut { block_number: u32 },
+    EventsNotReceived(subxt::Error),
+    EventNotDecoded(subxt::Error),
 }
 
 type ValidatorPublicKey = [u8; 32];
 
+/// What to do with a received block given the last processed one.
+#[derive(Debug, PartialEq, Eq)]
+enum NextBlock {
+    /// The received block was already processed.
+    AlreadyProcessed,
+    /// The block to be processed next: either the received one or the first missed before it.
+    Process {
+        block_number: u32,
+        /// The missed blocks beyond the backfill limit.
+        skipped: Option<RangeInclusive<u32>>,
+    },
+}
+
+/// Select the most preferred healthy endpoint by the probed finalized heads.
+///
+/// Returns `None` if the active endpoint should be kept.
+fn select_endpoint(
+    heads: &[Option<u32>],
+    active_endpoint: usize,
+    max_lag_in_blocks: u32,
+) -> Option<(usize, FailoverReason)> {
+    let best_head = heads.iter().flatten().copied().max()?;
+    let is_healthy = |head: &Option<u32>| {
+        head.is_some_and(|head| head.saturating_add(max_lag_in_blocks) >= best_head)
+    };
+
+    let candidate = heads.iter().position(is_healthy)?;
+    if candidate == active_endpoint {
+        return None;
+    }
+
+    let active_head = heads.get(active_endpoint).copied().flatten();
+    let reason = if is_healthy(&active_head) {
+        FailoverReason::PreferredEndpointRecovered
+    } else {
+        FailoverReason::ActiveEndpointLagging {
+            head: active_head,
+            best_head,
+        }
+    };
+
+    Some((candidate, reason))
+}
+
+impl NextBlock {
+    fn new(last_block_number: Option<u32>, block_number: u32, max_backfill_in_blocks: u32) -> Self {
+        let Some(last_block_number) = last_block_number else {
+            return Self::Process {
+                block_number,
+                skipped: None,
+            };
+        };
+
+        if block_number <= last_block_number {
+            return Self::AlreadyProcessed;
+        }
+
+        let first_missed = last_block_number + 1;
+        let backfill_from = first_missed.max(block_number.saturating_sub(max_backfill_in_blocks));
+
+        Self::Process {
+            block_number: backfill_from,
+            skipped: (backfill_from > first_missed).then(|| first_missed..=backfill_from - 1),
+        }
+    }
+}
+
 impl BlockSubscription {
     pub async fn construct_api(url: String) -> Result<OnlineClient<PolkadotConfig>, subxt::Error> {
         let api = OnlineClient::<PolkadotConfig>::from_insecure_url(url).await?;
         Ok(api)
     }
-    pub async fn subscribe(api: OnlineClient<PolkadotConfig>) -> Result<Self, subxt::Error> {
-        let subscription = api.blocks().subscribe_finalized().await?;
 
-        Ok(Self { api, subscription })
+    /// Subscribe to the finalized blocks using the first available endpoint.
+    ///
+    /// The urls are trimmed and the empty ones are skipped, at least one is required.
+    pub async fn subscribe(
+        urls: Vec<String>,
+        reconnect_params: ReconnectParams,
+        health_check_params: HealthCheckParams,
+    ) -> Result<Self, subxt::Error> {
+        let mut endpoints: Vec<Endpoint> = urls
+            .iter()
+            .map(|url| url.trim())
+            .filter(|url| !url.is_empty())
+            .map(|url| Endpoint {
+                url: url.to_owned(),
+                client: None,
+            })
+            .collect();
+
+        if endpoints.is_empty() {
+            return Err(subxt::Error::Other(
+                "no RPC endpoint urls configured".to_owned(),
+            ));
+        }
+
+        let (active_endpoint, client, subscription) = Self::connect_any(&mut endpoints).await?;
+
+        tracing::info!(
+            message = "block subscription connected",
+            url = %endpoints[active_endpoint].url
+        );
+
+        Ok(Self {
+            api: client.api,
+            subscription: Some(subscription),
+            reconnect_params,
+            health_check_params,
+            max_backfill_in_blocks: DEFAULT_MAX_BACKFILL_IN_BLOCKS,
+            backfill_timeout: DEFAULT_BACKFILL_TIMEOUT,
+            rpc: client.rpc,
+            endpoints,
+            active_endpoint,
+            reconnect_backoff: Backoff::default(),
+            last_health_check: Instant::now(),
+            last_block_number: None,
+            backfill_to: None,
+        })
+    }
+
+    /// Set the last block that was processed, the missed blocks after it will be backfilled.
+    pub fn resume_from(&mut self, last_processed_block_number: u32) {
+        self.last_block_number = Some(last_processed_block_number);
     }
 
-    pub async fn next_block(&mut self) -> Result<BlockInfo, NewBlockError> {
-        let res_opt = self.subscription.next().await;
-        let mut active_authentications_map = HashMap::new();
+    /// The url of the endpoint currently in use.
+    pub fn active_url(&self) -> &str {
+        &self.endpoints[self.active_endpoint].url
+    }
+
+    /// Wait for the next event: either a new finalized block or a connection state transition.
+    ///
+    /// A dead subscription is reconnected with exponential backoff, so the caller
+    /// can keep polling this method forever. The blocks missed since the last received
+    /// one are backfilled one by one, so the new blocks always come in a continuous sequence.
+    pub async fn next_event(&mut self) -> Result<Event, NewBlockError> {
+        if self.subscription.is_none() {
+            return Ok(Event::Connection(self.reconnect().await));
+        }
+
+        if self.last_health_check.elapsed() >= self.health_check_params.interval {
+            self.last_health_check = Instant::now();
+            if let Some(event) = self.health_check().await {
+                return Ok(Event::Connection(event));
+            }
+        }
+
+        if let Some(block_number) = self.backfill_to.take() {
+            return self.process_block(block_number, None).await;
+        }
+
+        let Some(subscription) = self.subscription.as_mut() else {
+            return Ok(Event::Connection(self.reconnect().await));
+        };
+
+        let res_opt =
+            tokio::time::timeout(self.reconnect_params.stall_timeout, subscription.next()).await;
+
+        let reason = match res_opt {
+            Ok(Some(Ok(block))) => return self.process_block(block.number(), Some(block)).await,
+            Ok(Some(Err(error))) => format!("subscription error: {error}"),
+            Ok(None) => "subscription stream ended".to_owned(),
+            Err(_) => format!(
+                "no finalized blocks received within {:?}",
+                self.reconnect_params.stall_timeout
+            ),
+        };
+
+        self.subscription = None;
+        self.endpoints[self.active_endpoint].client = None;
+        self.reconnect_backoff.reset();
+
+        let url = self.active_url().to_owned();
+
+        tracing::warn!(message = "block subscription disconnected", %url, %reason);
+
+        Ok(Event::Connection(ConnectionEvent::Disconnected {
+            url,
+            reason,
+        }))
+    }
+
+    /// Try to subscribe to the endpoints in their priority order.
+    ///
+    /// Returns the last error if every endpoint has failed.
+    async fn connect_any(
+        endpoints: &mut [Endpoint],
+    ) -> Result<(usize, Client, FinalizedBlocks), subxt::Error> {
+        let mut last_error = subxt::Error::Other("no RPC endpoints configured".to_owned());
+
+        for (index, endpoint) in endpoints.iter_mut().enumerate() {
+            let res = async {
+                let client = match endpoint.client.take() {
+                    Some(client) => client,
+                    None => Client::connect(&endpoint.url).await?,
+                };
+                let subscription = client.api.blocks().subscribe_finalized().await?;
+                Ok::<_, subxt::Error>((client, subscription))
+            }
+            .await;
+
+            match res {
+                Ok((client, subscription)) => {
+                    endpoint.client = Some(client.clone());
+                    return Ok((index, client, subscription));
+                }
+                Err(error) => {
+                    tracing::warn!(message = "endpoint connection failed", url = %endpoint.url, ?error);
+                    last_error = error;
+                }
+            }
+        }
+
+        Err(last_error)
+    }
+
+    async fn reconnect(&mut self) -> ConnectionEvent {
+        let (attempt, delay) = self.reconnect_backoff.start_attempt(&self.reconnect_params);
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+
+        tracing::info!(message = "block subscription reconnecting", ?attempt);
+
+        match Self::connect_any(&mut self.endpoints).await {
+            Ok((active_endpoint, client, subscription)) => {
+                self.active_endpoint = active_endpoint;
+                self.api = client.api;
+                self.rpc = client.rpc;
+                self.subscription = Some(subscription);
+                self.reconnect_backoff.reset();
+                self.last_health_check = Instant::now();
+
+                let url = self.active_url().to_owned();
 
-        let res = match res_opt {
-            None => return Err(NewBlockError::BlockNotReceived),
-            Some(res) => res,
+                tracing::info!(message = "block subscription reconnected", %url, ?attempt);
+
+                ConnectionEvent::Reconnected {
+                    url,
+                    attempts: attempt,
+                }
+            }
+            Err(error) => {
+                let next_delay = self.reconnect_backoff.next_delay(&self.reconnect_params);
+
+                tracing::warn!(
+                    message = "block subscription reconnect failed",
+                    ?attempt,
+                    ?next_delay,
+                    ?error
+                );
+
+                ConnectionEvent::ReconnectFailed {
+                    attempt,
+                    next_delay,
+                    error,
+                }
+            }
+        }
+    }
+
+    /// Fetch the finalized head of the endpoint, connecting to it if needed.
+    async fn probe(endpoint: &mut Endpoint, timeout: Duration) -> Option<u32> {
+        let res = tokio::time::timeout(timeout, async {
+            let client = match &endpoint.client {
+                Some(client) => client.clone(),
+                None => Client::connect(&endpoint.url).await?,
+            };
+            let head = client.api.blocks().at_latest().await?.number();
+            Ok::<_, subxt::Error>((client, head))
+        })
+        .await;
+
+        match res {
+            Ok(Ok((client, head))) => {
+                endpoint.client = Some(client);
+                Some(head)
+            }
+            Ok(Err(error)) => {
+                tracing::warn!(message = "endpoint probe failed", url = %endpoint.url, ?error);
+                endpoint.client = None;
+                None
+            }
+            Err(_) => {
+                tracing::warn!(message = "endpoint probe timed out", url = %endpoint.url);
+                endpoint.client = None;
+                None
+            }
+        }
+    }
+
+    /// Probe all the endpoints and switch to the most preferred healthy one.
+    async fn health_check(&mut self) -> Option<ConnectionEvent> {
+        let HealthCheckParams {
+            probe_timeout,
+            max_lag_in_blocks,
+            ..
+        } = self.health_check_params;
+
+        let mut heads = Vec::with_capacity(self.endpoints.len());
+        for endpoint in self.endpoints.iter_mut() {
+            heads.push(Self::probe(endpoint, probe_timeout).await);
+        }
+
+        tracing::debug!(message = "endpoints health check", ?heads);
+
+        let (candidate, reason) = select_endpoint(&heads, self.active_endpoint, max_lag_in_blocks)?;
+
+        let client = self.endpoints[candidate].client.clone()?;
+        let subscription = match client.api.blocks().subscribe_finalized().await {
+            Ok(subscription) => subscription,
+            Err(error) => {
+                tracing::warn!(
+                    message = "failover subscription failed",
+                    url = %self.endpoints[candidate].url,
+                    ?error
+                );
+                self.endpoints[candidate].client = None;
+                return None;
+            }
+        };
+
+        let from = self.active_url().to_owned();
+        self.active_endpoint = candidate;
+        self.api = client.api;
+        self.rpc = client.rpc;
+        self.subscription = Some(subscription);
+        let to = self.active_url().to_owned();
+
+        tracing::info!(message = "block subscription failed over", %from, %to, ?reason);
+
+        Some(ConnectionEvent::FailedOver { from, to, reason })
+    }
+
+    /// Return the received block, or the first missed block before it and continue
+    /// the backfill up to the received one on the next call.
+    ///
+    /// The block is fetched by number if it is not given.
+    async fn process_block(
+        &mut self,
+        block_number: u32,
+        block: Option<FinalizedBlock>,
+    ) -> Result<Event, NewBlockError> {
+        let next_block_number = match NextBlock::new(
+            self.last_block_number,
+            block_number,
+            self.max_backfill_in_blocks,
+        ) {
+            NextBlock::AlreadyProcessed => {
+                tracing::info!(
+                    message = "skip already processed block",
+                    ?block_number,
+                    last_block_number = ?self.last_block_number
+                );
+                return Ok(Event::AlreadyProcessed { block_number });
+            }
+            NextBlock::Process {
+                block_number: next_block_number,
+                skipped,
+            } => {
+                if let Some(skipped) = skipped {
+                    tracing::warn!(
+                        message = "too many missed blocks, the oldest are not backfilled",
+                        from = ?skipped.start(),
+                        to = ?skipped.end()
+                    );
+                    self.last_block_number = Some(*skipped.end());
+                }
+                next_block_number
+            }
         };
 
-        let block = res.map_err(NewBlockError::SubscriptionBlocksError)?;
+        let endpoint = self.active_url().to_owned();
+
+        if let Some(block) = block.filter(|_| next_block_number == block_number) {
+            let block_info = Self::block_info(block, endpoint).await?;
+            self.last_block_number = Some(block_number);
+            return Ok(Event::NewBlock(block_info));
+        }
+
+        tracing::info!(
+            message = "backfilling block",
+            block_number = ?next_block_number,
+            to = ?block_number
+        );
+
+        // On failure the backfill is dropped, the next received block resumes it.
+        let block_info = tokio::time::timeout(
+            self.backfill_timeout,
+            Self::missed_block_info(&self.api, &self.rpc, next_block_number, endpoint),
+        )
+        .await
+        .map_err(|_| NewBlockError::MissedBlockTimedOut {
+            block_number: next_block_number,
+        })??;
+
+        self.last_block_number = Some(next_block_number);
+        if next_block_number < block_number {
+            self.backfill_to = Some(block_number);
+        }
+
+        Ok(Event::NewBlock(block_info))
+    }
+
+    async fn missed_block_info(
+        api: &OnlineClient<PolkadotConfig>,
+        rpc: &LegacyRpcMethods<PolkadotConfig>,
+        block_number: u32,
+        endpoint: String,
+    ) -> Result<BlockInfo, NewBlockError> {
+        let block_hash = rpc
+            .chain_get_block_hash(Some(block_number.into()))
+            .await
+            .map_err(NewBlockError::MissedBlockNotReceived)?
+            .ok_or_else(|| {
+                NewBlockError::MissedBlockNotReceived(subxt::Error::Other(format!(
+                    "block {block_number} not found"
+                )))
+            })?;
+
+        let block = api
+            .blocks()
+            .at(block_hash)
+            .await
+            .map_err(NewBlockError::MissedBlockNotReceived)?;
+
+        Self::block_info(block, endpoint).await
+    }
+
+    async fn block_info(
+        block: FinalizedBlock,
+        endpoint: String,
+    ) -> Result<BlockInfo, NewBlockError> {
+        let mut active_authentications_map = HashMap::new();
         let block_number = block.number();
 
         let query = &r#gen::humanode::storage()
@@ -83,11 +696,257 @@ impl BlockSubscription {
             }
         }
 
-        tracing::info!(message = "new block", ?block_number);
+        let timestamp = block
+            .storage()
+            .fetch(&r#gen::humanode::storage().timestamp().now())
+            .await
+            .map_err(NewBlockError::TimestampNotReceived)?;
+
+        let bioauth_events = Self::bioauth_events(&block).await?;
+
+        tracing::info!(
+            message = "new block",
+            ?block_number,
+            ?timestamp,
+            %endpoint,
+            ?bioauth_events
+        );
 
         Ok(BlockInfo {
             block_number,
+            timestamp,
             active_authentications_map,
+            bioauth_events,
+            endpoint,
         })
     }
+
+    async fn bioauth_events(block: &FinalizedBlock) -> Result<Vec<BioauthEvent>, NewBlockError> {
+        use r#gen::humanode::{
+            bioauth::events::{AuthenticationsExpired, AuthenticationsRemoved, NewAuthentication},
+            runtime_types::humanode_runtime::deauthentication_reason::DeauthenticationReason as RuntimeDeauthenticationReason,
+        };
+
+        let events = block
+            .events()
+            .await
+            .map_err(NewBlockError::EventsNotReceived)?;
+
+        let mut bioauth_events = vec![];
+
+        for event in events.iter() {
+            let event = event.map_err(|error| NewBlockError::EventNotDecoded(error.into()))?;
+
+            if let Some(NewAuthentication {
+                validator_public_key,
+            }) = event
+                .as_event::<NewAuthentication>()
+                .map_err(|error| NewBlockError::EventNotDecoded(error.into()))?
+            {
+                bioauth_events.push(BioauthEvent::NewAuthentication {
+                    validator_public_key: validator_public_key.0,
+                });
+            } else if let Some(AuthenticationsExpired { expired }) = event
+                .as_event::<AuthenticationsExpired>()
+                .map_err(|error| NewBlockError::EventNotDecoded(error.into()))?
+            {
+                bioauth_events.extend(expired.into_iter().map(|validator_public_key| {
+                    BioauthEvent::AuthenticationExpired {
+                        validator_public_key: validator_public_key.0,
+                    }
+                }));
+            } else if let Some(AuthenticationsRemoved { removed, reason }) = event
+                .as_event::<AuthenticationsRemoved>()
+                .map_err(|error| NewBlockError::EventNotDecoded(error.into()))?
+            {
+                let reason = match reason {
+                    RuntimeDeauthenticationReason::Offence => DeauthenticationReason::Offence,
+                };
+                bioauth_events.extend(removed.into_iter().map(|validator_public_key| {
+                    BioauthEvent::AuthenticationRemoved {
+                        validator_public_key: validator_public_key.0,
+                        reason,
+                    }
+                }));
+            }
+        }
+
+        Ok(bioauth_events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_block_without_last_processed() {
+        assert_eq!(
+            NextBlock::new(None, 100, 10),
+            NextBlock::Process {
+                block_number: 100,
+                skipped: None
+            }
+        );
+    }
+
+    #[test]
+    fn next_block_already_processed() {
+        assert_eq!(
+            NextBlock::new(Some(100), 100, 10),
+            NextBlock::AlreadyProcessed
+        );
+        assert_eq!(
+            NextBlock::new(Some(100), 90, 10),
+            NextBlock::AlreadyProcessed
+        );
+    }
+
+    #[test]
+    fn next_block_continuous() {
+        assert_eq!(
+            NextBlock::new(Some(100), 101, 10),
+            NextBlock::Process {
+                block_number: 101,
+                skipped: None
+            }
+        );
+    }
+
+    #[test]
+    fn next_block_backfill_range() {
+        // The missed blocks are processed from the oldest one.
+        assert_eq!(
+            NextBlock::new(Some(100), 105, 10),
+            NextBlock::Process {
+                block_number: 101,
+                skipped: None
+            }
+        );
+        assert_eq!(
+            NextBlock::new(Some(100), 111, 10),
+            NextBlock::Process {
+                block_number: 101,
+                skipped: None
+            }
+        );
+
+        // The blocks beyond the limit are skipped.
+        assert_eq!(
+            NextBlock::new(Some(100), 120, 10),
+            NextBlock::Process {
+                block_number: 110,
+                skipped: Some(101..=109)
+            }
+        );
+        assert_eq!(
+            NextBlock::new(Some(100), 120, 0),
+            NextBlock::Process {
+                block_number: 120,
+                skipped: Some(101..=119)
+            }
+        );
+    }
+
+    #[test]
+    fn select_endpoint_keeps_healthy_preferred() {
+        assert_eq!(select_endpoint(&[Some(100), Some(100)], 0, 5), None);
+        // The lag within the limit is tolerated.
+        assert_eq!(select_endpoint(&[Some(95), Some(100)], 0, 5), None);
+    }
+
+    #[test]
+    fn select_endpoint_fails_over_from_lagging() {
+        assert_eq!(
+            select_endpoint(&[Some(94), Some(100), Some(100)], 0, 5),
+            Some((
+                1,
+                FailoverReason::ActiveEndpointLagging {
+                    head: Some(94),
+                    best_head: 100
+                }
+            ))
+        );
+        assert_eq!(
+            select_endpoint(&[None, Some(100)], 0, 5),
+            Some((
+                1,
+                FailoverReason::ActiveEndpointLagging {
+                    head: None,
+                    best_head: 100
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn select_endpoint_fails_back_to_recovered() {
+        assert_eq!(
+            select_endpoint(&[Some(100), Some(100), Some(100)], 2, 5),
+            Some((0, FailoverReason::PreferredEndpointRecovered))
+        );
+        // The recovered endpoint that is still lagging is not preferred.
+        assert_eq!(
+            select_endpoint(&[Some(90), Some(100), Some(100)], 2, 5),
+            Some((1, FailoverReason::PreferredEndpointRecovered))
+        );
+        assert_eq!(select_endpoint(&[Some(90), Some(100)], 1, 5), None);
+    }
+
+    #[test]
+    fn select_endpoint_keeps_active_if_all_down() {
+        assert_eq!(select_endpoint(&[None, None], 1, 5), None);
+        assert_eq!(select_endpoint(&[], 0, 5), None);
+    }
+
+    #[test]
+    fn reconnect_delay_grows_exponentially() {
+        let params = ReconnectParams::default();
+        let mut backoff = Backoff::default();
+
+        let delays: Vec<(u32, Duration)> = (0..4).map(|_| backoff.start_attempt(&params)).collect();
+        assert_eq!(
+            delays,
+            vec![
+                (1, Duration::ZERO),
+                (2, Duration::from_secs(1)),
+                (3, Duration::from_secs(2)),
+                (4, Duration::from_secs(4)),
+            ]
+        );
+        assert_eq!(backoff.next_delay(&params), Duration::from_secs(8));
+    }
+
+    #[test]
+    fn reconnect_delay_is_capped() {
+        let params = ReconnectParams::default();
+        let mut backoff = Backoff::default();
+
+        for _ in 0..7 {
+            backoff.start_attempt(&params);
+        }
+        assert_eq!(backoff.next_delay(&params), Duration::from_secs(60));
+
+        // The delay is not overflowed by the long outage.
+        backoff.attempt = u32::MAX;
+        assert_eq!(backoff.next_delay(&params), Duration::from_secs(60));
+        assert_eq!(
+            backoff.start_attempt(&params),
+            (u32::MAX, Duration::from_secs(60))
+        );
+    }
+
+    #[test]
+    fn reconnect_delay_resets_after_success() {
+        let params = ReconnectParams::default();
+        let mut backoff = Backoff::default();
+
+        for _ in 0..5 {
+            backoff.start_attempt(&params);
+        }
+        backoff.reset();
+
+        assert_eq!(backoff.start_attempt(&params), (1, Duration::ZERO));
+        assert_eq!(backoff.start_attempt(&params), (2, Duration::from_secs(1)));
+    }
 }
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+CREATE TABLE last_processed_block (
+  id BOOLEAN NOT NULL PRIMARY KEY DEFAULT 't' CHECK (id),
+  block_number BIGINT NOT NULL
+);
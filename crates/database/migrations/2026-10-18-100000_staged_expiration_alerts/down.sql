@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE bioauth_subscriptions
+  ALTER COLUMN alert_before_expiration_in_mins DROP DEFAULT,
+  ALTER COLUMN alert_before_expiration_in_mins TYPE BIGINT
+    USING COALESCE(alert_before_expiration_in_mins[array_upper(alert_before_expiration_in_mins, 1)], 60),
+  ALTER COLUMN alert_before_expiration_in_mins SET DEFAULT 60;
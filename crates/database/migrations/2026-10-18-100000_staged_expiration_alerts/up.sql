@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE bioauth_subscriptions
+  ALTER COLUMN alert_before_expiration_in_mins DROP DEFAULT,
+  ALTER COLUMN alert_before_expiration_in_mins TYPE BIGINT[]
+    USING ARRAY[alert_before_expiration_in_mins],
+  ALTER COLUMN alert_before_expiration_in_mins SET DEFAULT '{60}';
@@ -0,0 +1,14 @@
+-- Your SQL goes here
+CREATE TABLE bioauth_notification_states (
+    t_chat_id BIGINT NOT NULL,
+    validator_public_key BYTEA NOT NULL,
+    last_block_number_notified BIGINT NOT NULL,
+    next_block_number_to_notify BIGINT NOT NULL,
+    alerted_expires_at BIGINT,
+    alerted_thresholds_in_mins BIGINT[] NOT NULL DEFAULT '{}',
+    is_lost BOOLEAN NOT NULL DEFAULT 'f',
+    PRIMARY KEY (t_chat_id, validator_public_key),
+    FOREIGN KEY (t_chat_id, validator_public_key)
+      REFERENCES bioauth_subscriptions (t_chat_id, validator_public_key)
+      ON UPDATE CASCADE ON DELETE CASCADE
+);
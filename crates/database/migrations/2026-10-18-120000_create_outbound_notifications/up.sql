@@ -0,0 +1,13 @@
+-- Your SQL goes here
+CREATE TABLE outbound_notifications (
+    id BIGSERIAL PRIMARY KEY,
+    kind TEXT NOT NULL,
+    t_chat_id BIGINT NOT NULL,
+    validator_public_key BYTEA NOT NULL,
+    block_number BIGINT NOT NULL,
+    expires_at BIGINT,
+    is_delivered BOOLEAN NOT NULL DEFAULT 'f',
+    UNIQUE (kind, t_chat_id, validator_public_key, block_number)
+);
+
+CREATE INDEX outbound_notifications_is_delivered ON outbound_notifications (is_delivered);
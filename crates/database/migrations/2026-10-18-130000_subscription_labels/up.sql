@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE bioauth_subscriptions ADD COLUMN label TEXT;
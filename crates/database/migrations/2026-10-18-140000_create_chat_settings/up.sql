@@ -0,0 +1,8 @@
+-- Your SQL goes here
+CREATE TABLE chat_settings (
+  t_chat_id BIGINT NOT NULL PRIMARY KEY,
+  timezone_offset_in_mins INT NOT NULL DEFAULT 0,
+  quiet_hours_start_in_mins INT,
+  quiet_hours_end_in_mins INT,
+  CHECK ((quiet_hours_start_in_mins IS NULL) = (quiet_hours_end_in_mins IS NULL))
+);
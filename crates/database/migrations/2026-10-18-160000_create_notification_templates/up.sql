@@ -0,0 +1,7 @@
+-- Your SQL goes here
+CREATE TABLE notification_templates (
+  t_chat_id BIGINT NOT NULL,
+  kind TEXT NOT NULL,
+  template TEXT NOT NULL,
+  PRIMARY KEY (t_chat_id, kind)
+);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE bioauth_notification_states DROP COLUMN snoozed_until;
+ALTER TABLE bioauth_notification_states DROP COLUMN acknowledged;
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE bioauth_notification_states ADD COLUMN acknowledged BOOLEAN NOT NULL DEFAULT 'f';
+ALTER TABLE bioauth_notification_states ADD COLUMN snoozed_until BIGINT;
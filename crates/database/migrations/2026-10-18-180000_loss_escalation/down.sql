@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE outbound_notifications DROP COLUMN from_chat_id;
+
+ALTER TABLE bioauth_notification_states DROP COLUMN escalated_levels;
+ALTER TABLE bioauth_notification_states DROP COLUMN lost_at;
+
+DROP TABLE escalation_levels;
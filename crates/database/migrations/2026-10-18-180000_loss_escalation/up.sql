@@ -0,0 +1,17 @@
+-- Your SQL goes here
+CREATE TABLE escalation_levels (
+    t_chat_id BIGINT NOT NULL,
+    validator_public_key BYTEA NOT NULL,
+    level INT NOT NULL,
+    backup_chat_id BIGINT NOT NULL,
+    after_in_mins BIGINT NOT NULL,
+    PRIMARY KEY (t_chat_id, validator_public_key, level),
+    FOREIGN KEY (t_chat_id, validator_public_key)
+      REFERENCES bioauth_subscriptions (t_chat_id, validator_public_key)
+      ON UPDATE CASCADE ON DELETE CASCADE
+);
+
+ALTER TABLE bioauth_notification_states ADD COLUMN lost_at BIGINT;
+ALTER TABLE bioauth_notification_states ADD COLUMN escalated_levels INT NOT NULL DEFAULT 0;
+
+ALTER TABLE outbound_notifications ADD COLUMN from_chat_id BIGINT;
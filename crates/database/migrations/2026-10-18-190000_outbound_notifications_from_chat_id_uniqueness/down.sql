@@ -0,0 +1,13 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX outbound_notifications_deduplication;
+
+DELETE FROM outbound_notifications AS duplicate
+    USING outbound_notifications AS original
+    WHERE duplicate.id > original.id
+        AND duplicate.kind = original.kind
+        AND duplicate.t_chat_id = original.t_chat_id
+        AND duplicate.validator_public_key = original.validator_public_key
+        AND duplicate.block_number = original.block_number;
+
+ALTER TABLE outbound_notifications
+    ADD UNIQUE (kind, t_chat_id, validator_public_key, block_number);
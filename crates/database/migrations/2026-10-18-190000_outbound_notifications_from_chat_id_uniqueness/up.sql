@@ -0,0 +1,12 @@
+-- Your SQL goes here
+ALTER TABLE outbound_notifications
+    DROP CONSTRAINT outbound_notifications_kind_t_chat_id_validator_public_key__key;
+
+-- The loss is escalated to the backup chat from several chats within the same block.
+CREATE UNIQUE INDEX outbound_notifications_deduplication ON outbound_notifications (
+    kind,
+    t_chat_id,
+    validator_public_key,
+    block_number,
+    COALESCE(from_chat_id, 0)
+);
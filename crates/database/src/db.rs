@@ -1,12 +1,20 @@
 //! Manager implementation.
 #![allow(missing_docs, clippy::missing_docs_in_private_items)]
 
-use crate::models::{AllDevSubscriptions, LoadForInitialization};
+use crate::models::{
+    AllDevSubscriptions, BioauthNotificationState, ChatSettings, EscalationLevel,
+    LoadForInitialization, NewOutboundNotification, NotificationTemplate, OutboundNotification,
+};
 
-use diesel::prelude::*;
-use diesel_async::{pooled_connection::bb8::Pool, AsyncPgConnection, RunQueryDsl};
+use std::collections::HashSet;
 
-#[derive(Debug)]
+use diesel::{prelude::*, upsert::excluded};
+use diesel_async::{
+    pooled_connection::bb8::Pool, scoped_futures::ScopedFutureExt, AsyncConnection,
+    AsyncPgConnection, RunQueryDsl,
+};
+
+#[derive(Debug, Clone)]
 /// The bioauth_subscriptions manager.
 pub struct Db {
     /// Pool.
@@ -43,6 +51,160 @@ impl Db {
         Ok(values)
     }
 
+    pub async fn load_all_chat_settings(&self) -> Result<Vec<ChatSettings>, anyhow::Error> {
+        let mut conn = self.pool.get().await?;
+        use crate::schema::chat_settings::dsl::*;
+
+        let values = chat_settings
+            .select(ChatSettings::as_select())
+            .get_results(&mut conn)
+            .await?;
+
+        Ok(values)
+    }
+
+    pub async fn update_chat_settings(
+        &self,
+        chat_id: i64,
+        timezone_offset_in_mins_value: i32,
+        quiet_hours_value: Option<(i32, i32)>,
+        language_value: Option<&str>,
+    ) -> Result<(), anyhow::Error> {
+        let mut conn = self.pool.get().await?;
+        use crate::schema::chat_settings::dsl::*;
+        let values = (
+            timezone_offset_in_mins.eq(timezone_offset_in_mins_value),
+            quiet_hours_start_in_mins.eq(quiet_hours_value.map(|(start, _)| start)),
+            quiet_hours_end_in_mins.eq(quiet_hours_value.map(|(_, end)| end)),
+            language.eq(language_value),
+        );
+
+        diesel::insert_into(chat_settings)
+            .values((t_chat_id.eq(chat_id), values))
+            .on_conflict(t_chat_id)
+            .do_update()
+            .set(values)
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn load_all_notification_templates(
+        &self,
+    ) -> Result<Vec<NotificationTemplate>, anyhow::Error> {
+        let mut conn = self.pool.get().await?;
+        use crate::schema::notification_templates::dsl::*;
+
+        let values = notification_templates
+            .select(NotificationTemplate::as_select())
+            .get_results(&mut conn)
+            .await?;
+
+        Ok(values)
+    }
+
+    /// Set the custom notification text, `None` removes it.
+    pub async fn update_notification_template(
+        &self,
+        chat_id: i64,
+        kind_value: &str,
+        template_value: Option<&str>,
+    ) -> Result<(), anyhow::Error> {
+        let mut conn = self.pool.get().await?;
+        use crate::schema::notification_templates::dsl::*;
+
+        match template_value {
+            Some(template_value) => {
+                diesel::insert_into(notification_templates)
+                    .values((
+                        t_chat_id.eq(chat_id),
+                        kind.eq(kind_value),
+                        template.eq(template_value),
+                    ))
+                    .on_conflict((t_chat_id, kind))
+                    .do_update()
+                    .set(template.eq(template_value))
+                    .execute(&mut conn)
+                    .await?;
+            }
+            None => {
+                diesel::delete(notification_templates)
+                    .filter(t_chat_id.eq(chat_id))
+                    .filter(kind.eq(kind_value))
+                    .execute(&mut conn)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn load_all_escalation_levels(&self) -> Result<Vec<EscalationLevel>, anyhow::Error> {
+        let mut conn = self.pool.get().await?;
+        use crate::schema::escalation_levels::dsl::*;
+
+        let values = escalation_levels
+            .order(level)
+            .select(EscalationLevel::as_select())
+            .get_results(&mut conn)
+            .await?;
+
+        Ok(values)
+    }
+
+    /// Replace the escalation levels of the subscription with the backup chat ids and the delays.
+    pub async fn update_escalation_levels(
+        &self,
+        chat_id: i64,
+        public_key: &[u8; 32],
+        levels: &[(i64, i64)],
+    ) -> Result<(), anyhow::Error> {
+        let mut conn = self.pool.get().await?;
+        use crate::schema::escalation_levels::dsl::*;
+        let public_key = public_key.to_vec();
+
+        let values: Vec<_> = levels
+            .iter()
+            .enumerate()
+            .map(|(index, (backup_chat_id_value, after_in_mins_value))| {
+                (
+                    t_chat_id.eq(chat_id),
+                    validator_public_key.eq(public_key.clone()),
+                    level.eq(index as i32),
+                    backup_chat_id.eq(*backup_chat_id_value),
+                    after_in_mins.eq(*after_in_mins_value),
+                )
+            })
+            .collect();
+
+        conn.transaction::<_, diesel::result::Error, _>(move |conn| {
+            async move {
+                diesel::delete(escalation_levels)
+                    .filter(
+                        t_chat_id
+                            .eq(chat_id)
+                            .and(validator_public_key.eq(public_key)),
+                    )
+                    .execute(conn)
+                    .await?;
+
+                if !values.is_empty() {
+                    diesel::insert_into(escalation_levels)
+                        .values(values)
+                        .execute(conn)
+                        .await?;
+                }
+
+                Ok(())
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn update_affected_validator_subscription(
         &self,
         chat_id: i64,
@@ -130,7 +292,7 @@ impl Db {
         &self,
         chat_id: i64,
         public_key: &[u8; 32],
-        alert_before_expiration_in_mins_value: i64,
+        alert_before_expiration_in_mins_value: &[i64],
     ) -> Result<(), anyhow::Error> {
         let mut conn = self.pool.get().await?;
         use crate::schema::bioauth_subscriptions::dsl::*;
@@ -149,6 +311,29 @@ impl Db {
         Ok(())
     }
 
+    pub async fn update_bioauth_label(
+        &self,
+        chat_id: i64,
+        public_key: &[u8; 32],
+        label_value: Option<&str>,
+    ) -> Result<(), anyhow::Error> {
+        let mut conn = self.pool.get().await?;
+        use crate::schema::bioauth_subscriptions::dsl::*;
+        let public_key: &[u8] = &public_key[..];
+
+        diesel::update(bioauth_subscriptions)
+            .filter(
+                t_chat_id
+                    .eq(chat_id)
+                    .and(validator_public_key.eq(public_key)),
+            )
+            .set(label.eq(label_value))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn bioauth_unsubscribe_all(&self, chat_id: i64) -> Result<(), anyhow::Error> {
         let mut conn = self.pool.get().await?;
         use crate::schema::bioauth_subscriptions::dsl::*;
@@ -160,4 +345,357 @@ impl Db {
 
         Ok(())
     }
+
+    pub async fn remove_dev_subscriptions(&self, chat_id: i64) -> Result<(), anyhow::Error> {
+        let mut conn = self.pool.get().await?;
+        use crate::schema::dev_subscriptions::dsl::*;
+
+        diesel::delete(dev_subscriptions)
+            .filter(t_chat_id.eq(chat_id))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Re-key all the chat rows to the new chat id in a single transaction.
+    ///
+    /// The notification states and the escalation levels follow the subscriptions by the
+    /// cascading foreign key, the escalations to the chat are re-targeted.
+    pub async fn migrate_chat(
+        &self,
+        from_chat_id: i64,
+        to_chat_id: i64,
+    ) -> Result<(), anyhow::Error> {
+        let mut conn = self.pool.get().await?;
+        use crate::schema::{
+            bioauth_subscriptions, chat_settings, dev_subscriptions, escalation_levels,
+            notification_templates,
+        };
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                // The rows the new chat already has are kept, the conflicting migrated ones are
+                // dropped along with their notification states and escalation levels.
+                let to_public_keys = bioauth_subscriptions::table
+                    .filter(bioauth_subscriptions::t_chat_id.eq(to_chat_id))
+                    .select(bioauth_subscriptions::validator_public_key)
+                    .load::<Vec<u8>>(conn)
+                    .await?;
+                diesel::delete(
+                    bioauth_subscriptions::table
+                        .filter(bioauth_subscriptions::t_chat_id.eq(from_chat_id))
+                        .filter(bioauth_subscriptions::validator_public_key.eq_any(to_public_keys)),
+                )
+                .execute(conn)
+                .await?;
+
+                let to_kinds = notification_templates::table
+                    .filter(notification_templates::t_chat_id.eq(to_chat_id))
+                    .select(notification_templates::kind)
+                    .load::<String>(conn)
+                    .await?;
+                diesel::delete(
+                    notification_templates::table
+                        .filter(notification_templates::t_chat_id.eq(from_chat_id))
+                        .filter(notification_templates::kind.eq_any(to_kinds)),
+                )
+                .execute(conn)
+                .await?;
+
+                let has_dev_subscriptions = diesel::select(diesel::dsl::exists(
+                    dev_subscriptions::table.filter(dev_subscriptions::t_chat_id.eq(to_chat_id)),
+                ))
+                .get_result::<bool>(conn)
+                .await?;
+                if has_dev_subscriptions {
+                    diesel::delete(
+                        dev_subscriptions::table
+                            .filter(dev_subscriptions::t_chat_id.eq(from_chat_id)),
+                    )
+                    .execute(conn)
+                    .await?;
+                }
+
+                let has_chat_settings = diesel::select(diesel::dsl::exists(
+                    chat_settings::table.filter(chat_settings::t_chat_id.eq(to_chat_id)),
+                ))
+                .get_result::<bool>(conn)
+                .await?;
+                if has_chat_settings {
+                    diesel::delete(
+                        chat_settings::table.filter(chat_settings::t_chat_id.eq(from_chat_id)),
+                    )
+                    .execute(conn)
+                    .await?;
+                }
+
+                diesel::update(bioauth_subscriptions::table)
+                    .filter(bioauth_subscriptions::t_chat_id.eq(from_chat_id))
+                    .set(bioauth_subscriptions::t_chat_id.eq(to_chat_id))
+                    .execute(conn)
+                    .await?;
+
+                diesel::update(dev_subscriptions::table)
+                    .filter(dev_subscriptions::t_chat_id.eq(from_chat_id))
+                    .set(dev_subscriptions::t_chat_id.eq(to_chat_id))
+                    .execute(conn)
+                    .await?;
+
+                diesel::update(chat_settings::table)
+                    .filter(chat_settings::t_chat_id.eq(from_chat_id))
+                    .set(chat_settings::t_chat_id.eq(to_chat_id))
+                    .execute(conn)
+                    .await?;
+
+                diesel::update(notification_templates::table)
+                    .filter(notification_templates::t_chat_id.eq(from_chat_id))
+                    .set(notification_templates::t_chat_id.eq(to_chat_id))
+                    .execute(conn)
+                    .await?;
+
+                diesel::update(escalation_levels::table)
+                    .filter(escalation_levels::backup_chat_id.eq(from_chat_id))
+                    .set(escalation_levels::backup_chat_id.eq(to_chat_id))
+                    .execute(conn)
+                    .await?;
+
+                Ok(())
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn load_last_processed_block(&self) -> Result<Option<u32>, anyhow::Error> {
+        let mut conn = self.pool.get().await?;
+        use crate::schema::last_processed_block::dsl::*;
+
+        let value = last_processed_block
+            .select(block_number)
+            .first::<i64>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(value.map(|value| value.try_into()).transpose()?)
+    }
+
+    pub async fn update_last_processed_block(
+        &self,
+        block_number_value: u32,
+    ) -> Result<(), anyhow::Error> {
+        let mut conn = self.pool.get().await?;
+        use crate::schema::last_processed_block::dsl::*;
+        let block_number_value = i64::from(block_number_value);
+
+        diesel::insert_into(last_processed_block)
+            .values((id.eq(true), block_number.eq(block_number_value)))
+            .on_conflict(id)
+            .do_update()
+            .set(block_number.eq(block_number_value))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn load_bioauth_notification_states(
+        &self,
+    ) -> Result<Vec<BioauthNotificationState>, anyhow::Error> {
+        let mut conn = self.pool.get().await?;
+        use crate::schema::bioauth_notification_states::dsl::*;
+
+        let values = bioauth_notification_states
+            .select(BioauthNotificationState::as_select())
+            .get_results(&mut conn)
+            .await?;
+
+        Ok(values)
+    }
+
+    /// Upsert the notification states of the subscriptions.
+    ///
+    /// The states of the subscriptions not persisted yet or already removed are skipped,
+    /// the remaining subscriptions are locked against removal until the states are saved.
+    pub async fn save_bioauth_notification_states(
+        &self,
+        states: &[BioauthNotificationState],
+    ) -> Result<(), anyhow::Error> {
+        /// Keeps the number of the bind parameters of a single statement within the limit.
+        const CHUNK_SIZE: usize = 1000;
+
+        let mut conn = self.pool.get().await?;
+        use crate::schema::{bioauth_notification_states::dsl::*, bioauth_subscriptions};
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                let subscriptions: HashSet<(i64, Vec<u8>)> = bioauth_subscriptions::table
+                    .select((
+                        bioauth_subscriptions::t_chat_id,
+                        bioauth_subscriptions::validator_public_key,
+                    ))
+                    .for_key_share()
+                    .load::<(i64, Vec<u8>)>(conn)
+                    .await?
+                    .into_iter()
+                    .collect();
+
+                let states: Vec<&BioauthNotificationState> = states
+                    .iter()
+                    .filter(|state| {
+                        subscriptions
+                            .contains(&(state.t_chat_id, state.validator_public_key.to_vec()))
+                    })
+                    .collect();
+
+                for chunk in states.chunks(CHUNK_SIZE) {
+                    let values: Vec<_> = chunk
+                        .iter()
+                        .map(|state| {
+                            (
+                                t_chat_id.eq(state.t_chat_id),
+                                validator_public_key.eq(&state.validator_public_key[..]),
+                                last_block_number_notified
+                                    .eq(i64::from(state.last_block_number_notified)),
+                                next_block_number_to_notify
+                                    .eq(i64::from(state.next_block_number_to_notify)),
+                                alerted_expires_at
+                                    .eq(state.alerted_expires_at.map(|value| value as i64)),
+                                alerted_thresholds_in_mins.eq(state
+                                    .alerted_thresholds_in_mins
+                                    .iter()
+                                    .map(|value| *value as i64)
+                                    .collect::<Vec<i64>>()),
+                                is_lost.eq(state.is_lost),
+                                acknowledged.eq(state.acknowledged),
+                                snoozed_until.eq(state.snoozed_until.map(|value| value as i64)),
+                                lost_at.eq(state.lost_at.map(|value| value as i64)),
+                                escalated_levels.eq(state.escalated_levels as i32),
+                            )
+                        })
+                        .collect();
+
+                    diesel::insert_into(bioauth_notification_states)
+                        .values(values)
+                        .on_conflict((t_chat_id, validator_public_key))
+                        .do_update()
+                        .set((
+                            last_block_number_notified.eq(excluded(last_block_number_notified)),
+                            next_block_number_to_notify.eq(excluded(next_block_number_to_notify)),
+                            alerted_expires_at.eq(excluded(alerted_expires_at)),
+                            alerted_thresholds_in_mins.eq(excluded(alerted_thresholds_in_mins)),
+                            is_lost.eq(excluded(is_lost)),
+                            acknowledged.eq(excluded(acknowledged)),
+                            snoozed_until.eq(excluded(snoozed_until)),
+                            lost_at.eq(excluded(lost_at)),
+                            escalated_levels.eq(excluded(escalated_levels)),
+                        ))
+                        .execute(conn)
+                        .await?;
+                }
+
+                Ok(())
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Queue the notifications, skipping the ones already queued before.
+    ///
+    /// Returns the newly queued notifications only, so a block processed twice is not notified twice.
+    pub async fn enqueue_outbound_notifications(
+        &self,
+        notifications: &[NewOutboundNotification],
+    ) -> Result<Vec<OutboundNotification>, anyhow::Error> {
+        /// Keeps the number of the bind parameters of a single statement within the limit.
+        const CHUNK_SIZE: usize = 1000;
+
+        let mut conn = self.pool.get().await?;
+        use crate::schema::outbound_notifications::dsl::*;
+
+        let mut queued = Vec::with_capacity(notifications.len());
+        for chunk in notifications.chunks(CHUNK_SIZE) {
+            let values: Vec<_> = chunk
+                .iter()
+                .map(|notification| {
+                    (
+                        kind.eq(&notification.kind),
+                        t_chat_id.eq(notification.t_chat_id),
+                        validator_public_key.eq(&notification.validator_public_key[..]),
+                        block_number.eq(i64::from(notification.block_number)),
+                        expires_at.eq(notification.expires_at.map(|value| value as i64)),
+                        from_chat_id.eq(notification.from_chat_id),
+                    )
+                })
+                .collect();
+
+            let values = diesel::insert_into(outbound_notifications)
+                .values(values)
+                // The deduplication index is an expression one, so it is not named here.
+                .on_conflict_do_nothing()
+                .returning(OutboundNotification::as_returning())
+                .get_results(&mut conn)
+                .await?;
+            queued.extend(values);
+        }
+
+        Ok(queued)
+    }
+
+    pub async fn load_pending_outbound_notifications(
+        &self,
+    ) -> Result<Vec<OutboundNotification>, anyhow::Error> {
+        let mut conn = self.pool.get().await?;
+        use crate::schema::outbound_notifications::dsl::*;
+
+        let values = outbound_notifications
+            .filter(is_delivered.eq(false))
+            .order(id)
+            .select(OutboundNotification::as_select())
+            .get_results(&mut conn)
+            .await?;
+
+        Ok(values)
+    }
+
+    pub async fn mark_outbound_notification_delivered(
+        &self,
+        id_value: i64,
+    ) -> Result<(), anyhow::Error> {
+        let mut conn = self.pool.get().await?;
+        use crate::schema::outbound_notifications::dsl::*;
+
+        diesel::update(outbound_notifications)
+            .filter(id.eq(id_value))
+            .set(is_delivered.eq(true))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Remove the delivered notifications produced before the block, they can't be produced again.
+    pub async fn purge_delivered_outbound_notifications(
+        &self,
+        before_block_number: u32,
+    ) -> Result<(), anyhow::Error> {
+        let mut conn = self.pool.get().await?;
+        use crate::schema::outbound_notifications::dsl::*;
+
+        diesel::delete(outbound_notifications)
+            .filter(
+                is_delivered
+                    .eq(true)
+                    .and(block_number.lt(i64::from(before_block_number))),
+            )
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
 }
@@ -2,12 +2,15 @@
 
 #![allow(missing_docs, clippy::missing_docs_in_private_items)]
 
-use crate::schema::{bioauth_subscriptions, dev_subscriptions};
+use crate::schema::{
+    bioauth_notification_states, bioauth_subscriptions, chat_settings, dev_subscriptions,
+    escalation_levels, notification_templates, outbound_notifications,
+};
 use diesel::{
     backend::Backend,
     deserialize::{self, FromSql},
     prelude::*,
-    sql_types::Bytea,
+    sql_types::{Array, Bytea, Int8, Nullable},
 };
 
 #[derive(Debug)]
@@ -38,6 +41,53 @@ impl<const N: usize> From<ByteArray<N>> for [u8; N] {
     }
 }
 
+#[derive(Debug)]
+pub struct U64Array(Vec<u64>);
+
+impl<DB> Queryable<Array<Int8>, DB> for U64Array
+where
+    DB: Backend,
+    Vec<i64>: FromSql<Array<Int8>, DB>,
+{
+    type Row = Vec<i64>;
+
+    fn build(data: Vec<i64>) -> deserialize::Result<Self> {
+        let values = data
+            .into_iter()
+            .map(u64::try_from)
+            .collect::<Result<_, _>>()?;
+        Ok(U64Array(values))
+    }
+}
+
+impl From<U64Array> for Vec<u64> {
+    fn from(value: U64Array) -> Self {
+        value.0
+    }
+}
+
+#[derive(Debug)]
+pub struct OptionalU64(Option<u64>);
+
+impl<DB> Queryable<Nullable<Int8>, DB> for OptionalU64
+where
+    DB: Backend,
+    Option<i64>: FromSql<Nullable<Int8>, DB>,
+{
+    type Row = Option<i64>;
+
+    fn build(data: Option<i64>) -> deserialize::Result<Self> {
+        let value = data.map(u64::try_from).transpose()?;
+        Ok(OptionalU64(value))
+    }
+}
+
+impl From<OptionalU64> for Option<u64> {
+    fn from(value: OptionalU64) -> Self {
+        value.0
+    }
+}
+
 /// Model for load init validator with settings values.
 #[derive(Debug, Queryable, Selectable)]
 #[diesel(table_name = bioauth_subscriptions)]
@@ -53,9 +103,12 @@ pub struct LoadForInitialization {
     #[diesel(deserialize_as = i32)]
     pub max_message_frequency_in_blocks: u32,
 
-    /// Notify a few minutes before expiration.
-    #[diesel(deserialize_as = i64)]
-    pub alert_before_expiration_in_mins: u64,
+    /// Notify a few minutes before expiration, at each of the thresholds.
+    #[diesel(deserialize_as = U64Array)]
+    pub alert_before_expiration_in_mins: Vec<u64>,
+
+    /// The human-friendly name of the validator.
+    pub label: Option<String>,
 }
 
 /// Model for load init validator with settings values.
@@ -67,3 +120,145 @@ pub struct AllDevSubscriptions {
     /// Category of dev subscription.
     pub affected_validator: bool,
 }
+
+/// Model for the settings shared by all the subscriptions of the chat.
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = chat_settings)]
+pub struct ChatSettings {
+    /// The telegram user's chat id.
+    pub t_chat_id: i64,
+
+    /// The offset of the chat local time from UTC.
+    pub timezone_offset_in_mins: i32,
+
+    /// The start of the quiet hours in minutes since the local midnight.
+    pub quiet_hours_start_in_mins: Option<i32>,
+
+    /// The end of the quiet hours in minutes since the local midnight.
+    pub quiet_hours_end_in_mins: Option<i32>,
+
+    /// The language of the bot texts chosen for the chat.
+    pub language: Option<String>,
+}
+
+/// Model for the custom notification text of the chat.
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = notification_templates)]
+pub struct NotificationTemplate {
+    /// The telegram user's chat id.
+    pub t_chat_id: i64,
+
+    /// The notification kind.
+    pub kind: String,
+
+    /// The text with the placeholders.
+    pub template: String,
+}
+
+/// Model for the persisted notification state of the validator subscription.
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = bioauth_notification_states)]
+pub struct BioauthNotificationState {
+    /// The telegram user's chat id.
+    pub t_chat_id: i64,
+
+    /// Validator public key
+    #[diesel(deserialize_as = ByteArray<32>)]
+    pub validator_public_key: [u8; 32],
+
+    /// The block the lost notification was sent at.
+    #[diesel(deserialize_as = i64)]
+    pub last_block_number_notified: u32,
+
+    /// The block the lost notification can be repeated at.
+    #[diesel(deserialize_as = i64)]
+    pub next_block_number_to_notify: u32,
+
+    /// The expiration moment the alerts are tracked for.
+    #[diesel(deserialize_as = OptionalU64)]
+    pub alerted_expires_at: Option<u64>,
+
+    /// The alert thresholds already fired.
+    #[diesel(deserialize_as = U64Array)]
+    pub alerted_thresholds_in_mins: Vec<u64>,
+
+    /// Whether the validator has lost the bio-authentication.
+    pub is_lost: bool,
+
+    /// Whether the loss is acknowledged and the lost notification is not repeated.
+    pub acknowledged: bool,
+
+    /// The moment in milliseconds the lost notification is snoozed until.
+    #[diesel(deserialize_as = OptionalU64)]
+    pub snoozed_until: Option<u64>,
+
+    /// The moment in milliseconds the validator was found lost at.
+    #[diesel(deserialize_as = OptionalU64)]
+    pub lost_at: Option<u64>,
+
+    /// The number of the escalation levels the loss is already escalated to.
+    #[diesel(deserialize_as = i32)]
+    pub escalated_levels: u32,
+}
+
+/// Model for the backup chat the unacknowledged loss of the validator is escalated to.
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = escalation_levels)]
+pub struct EscalationLevel {
+    /// The telegram user's chat id.
+    pub t_chat_id: i64,
+
+    /// Validator public key
+    #[diesel(deserialize_as = ByteArray<32>)]
+    pub validator_public_key: [u8; 32],
+
+    /// The escalation order, starting from zero.
+    pub level: i32,
+
+    /// The chat the loss is escalated to.
+    pub backup_chat_id: i64,
+
+    /// The delay since the loss.
+    #[diesel(deserialize_as = i64)]
+    pub after_in_mins: u64,
+}
+
+/// Model for the notification queued to be sent to the telegram chat.
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = outbound_notifications)]
+pub struct OutboundNotification {
+    /// The queue position.
+    pub id: i64,
+
+    /// The notification kind.
+    pub kind: String,
+
+    /// The telegram user's chat id.
+    pub t_chat_id: i64,
+
+    /// Validator public key
+    #[diesel(deserialize_as = ByteArray<32>)]
+    pub validator_public_key: [u8; 32],
+
+    /// The block the notification is produced at.
+    #[diesel(deserialize_as = i64)]
+    pub block_number: u32,
+
+    /// The authentication expiration moment, if the notification has it.
+    #[diesel(deserialize_as = OptionalU64)]
+    pub expires_at: Option<u64>,
+
+    /// The chat the loss is escalated from, if the notification is an escalation.
+    pub from_chat_id: Option<i64>,
+}
+
+/// The notification to queue, identified by its kind, chat, validator and block.
+#[derive(Debug)]
+pub struct NewOutboundNotification {
+    pub kind: String,
+    pub t_chat_id: i64,
+    pub validator_public_key: [u8; 32],
+    pub block_number: u32,
+    pub expires_at: Option<u64>,
+    pub from_chat_id: Option<i64>,
+}
@@ -1,11 +1,38 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    bioauth_notification_states (t_chat_id, validator_public_key) {
+        t_chat_id -> Int8,
+        validator_public_key -> Bytea,
+        last_block_number_notified -> Int8,
+        next_block_number_to_notify -> Int8,
+        alerted_expires_at -> Nullable<Int8>,
+        alerted_thresholds_in_mins -> Array<Int8>,
+        is_lost -> Bool,
+        acknowledged -> Bool,
+        snoozed_until -> Nullable<Int8>,
+        lost_at -> Nullable<Int8>,
+        escalated_levels -> Int4,
+    }
+}
+
 diesel::table! {
     bioauth_subscriptions (t_chat_id, validator_public_key) {
         t_chat_id -> Int8,
         validator_public_key -> Bytea,
         max_message_frequency_in_blocks -> Int4,
-        alert_before_expiration_in_mins -> Int8,
+        alert_before_expiration_in_mins -> Array<Int8>,
+        label -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    chat_settings (t_chat_id) {
+        t_chat_id -> Int8,
+        timezone_offset_in_mins -> Int4,
+        quiet_hours_start_in_mins -> Nullable<Int4>,
+        quiet_hours_end_in_mins -> Nullable<Int4>,
+        language -> Nullable<Text>,
     }
 }
 
@@ -16,4 +43,51 @@ diesel::table! {
     }
 }
 
-diesel::allow_tables_to_appear_in_same_query!(bioauth_subscriptions, dev_subscriptions,);
+diesel::table! {
+    escalation_levels (t_chat_id, validator_public_key, level) {
+        t_chat_id -> Int8,
+        validator_public_key -> Bytea,
+        level -> Int4,
+        backup_chat_id -> Int8,
+        after_in_mins -> Int8,
+    }
+}
+
+diesel::table! {
+    last_processed_block (id) {
+        id -> Bool,
+        block_number -> Int8,
+    }
+}
+
+diesel::table! {
+    notification_templates (t_chat_id, kind) {
+        t_chat_id -> Int8,
+        kind -> Text,
+        template -> Text,
+    }
+}
+
+diesel::table! {
+    outbound_notifications (id) {
+        id -> Int8,
+        kind -> Text,
+        t_chat_id -> Int8,
+        validator_public_key -> Bytea,
+        block_number -> Int8,
+        expires_at -> Nullable<Int8>,
+        is_delivered -> Bool,
+        from_chat_id -> Nullable<Int8>,
+    }
+}
+
+diesel::allow_tables_to_appear_in_same_query!(
+    bioauth_notification_states,
+    bioauth_subscriptions,
+    chat_settings,
+    dev_subscriptions,
+    escalation_levels,
+    last_processed_block,
+    notification_templates,
+    outbound_notifications,
+);
@@ -48,4 +48,11 @@ impl DevSubscriptionMap {
     pub fn remove(&mut self, key: &i64) {
         self.0.remove(key);
     }
+
+    /// Move the settings to the new key, the ones already present there are kept.
+    pub fn migrate(&mut self, from_key: &i64, to_key: i64) {
+        if let Some(settings) = self.0.remove(from_key) {
+            self.0.entry(to_key).or_insert(settings);
+        }
+    }
 }
@@ -6,14 +6,114 @@
     clippy::multiple_crate_versions
 )]
 
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use bioauth_logic::{BioauthLogic, FailedNotification};
-use bioauth_settings::BioauthSettings;
+use bioauth_settings::{BioauthSettings, ChatSettings};
 use block_subscription::BlockSubscription;
 use database::db::Db;
 use tokio::{sync::Mutex, task::JoinSet};
 
+mod outbound_queue;
+
+/// How often the delivered notifications are purged from the outbound queue.
+const OUTBOUND_QUEUE_PURGE_INTERVAL_IN_BLOCKS: u32 = 600;
+
+/// How often the notification states are persisted.
+const NOTIFICATION_STATES_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(60);
+
+type SharedBioauthLogic = Arc<Mutex<BioauthLogic<[u8; 32]>>>;
+
+/// The running main loop tasks.
+#[derive(Debug)]
+pub struct MainLoop {
+    pub tasks: JoinSet<()>,
+    bioauth_logic: SharedBioauthLogic,
+    db: Db,
+}
+
+impl MainLoop {
+    /// Stop the tasks and persist the final notification states.
+    pub async fn shutdown(mut self) {
+        self.tasks.shutdown().await;
+        save_notification_states(&self.bioauth_logic, &self.db).await;
+    }
+}
+
+async fn save_notification_states(bioauth_logic: &SharedBioauthLogic, db: &Db) {
+    let notification_states: Vec<database::models::BioauthNotificationState> = {
+        let bioauth_logic = bioauth_logic.lock().await;
+        bioauth_logic
+            .notification_states()
+            .into_iter()
+            .map(|(t_chat_id, validator_public_key, state)| {
+                database::models::BioauthNotificationState {
+                    t_chat_id,
+                    validator_public_key,
+                    last_block_number_notified: state.last_block_number_notified,
+                    next_block_number_to_notify: state.next_block_number_to_notify,
+                    alerted_expires_at: state.alerted_expires_at,
+                    alerted_thresholds_in_mins: state
+                        .alerted_thresholds_in_mins
+                        .into_iter()
+                        .collect(),
+                    is_lost: state.is_lost,
+                    acknowledged: state.acknowledged,
+                    snoozed_until: state.snoozed_until,
+                    lost_at: state.lost_at,
+                    escalated_levels: state.escalated_levels as u32,
+                }
+            })
+            .collect()
+    };
+
+    match db
+        .save_bioauth_notification_states(&notification_states)
+        .await
+    {
+        Ok(()) => tracing::debug!(
+            message = "notification states saved",
+            len = notification_states.len()
+        ),
+        Err(error) => tracing::error!(message = "save_bioauth_notification_states error", ?error),
+    }
+}
+
+/// Apply the change to the chat settings under the write lock and persist the merged settings.
+async fn update_chat_settings(
+    rw_bioauth_settings_map: &tokio::sync::RwLock<bioauth_settings::BioauthSettingsMap<[u8; 32]>>,
+    db: &Db,
+    chat_id: i64,
+    update: impl FnOnce(&mut ChatSettings),
+) {
+    let chat_settings = {
+        let mut bioauth_settings_map = rw_bioauth_settings_map.write().await;
+        let mut chat_settings = bioauth_settings_map.get_chat(chat_id).clone();
+        update(&mut chat_settings);
+        bioauth_settings_map.update_chat(chat_id, chat_settings.clone());
+        chat_settings
+    };
+
+    let quiet_hours = chat_settings.quiet_hours.map(|quiet_hours| {
+        (
+            i32::from(quiet_hours.start_in_mins),
+            i32::from(quiet_hours.end_in_mins),
+        )
+    });
+
+    if let Err(error) = db
+        .update_chat_settings(
+            chat_id,
+            chat_settings.timezone_offset_in_mins,
+            quiet_hours,
+            chat_settings.language.as_deref(),
+        )
+        .await
+    {
+        tracing::error!(message = "chat settings update error", ?chat_id, ?error);
+    }
+}
+
 #[derive(Debug)]
 pub struct Params {
     pub db: Db,
@@ -23,9 +123,10 @@ pub struct Params {
     pub rw_bioauth_settings_map:
         Arc<tokio::sync::RwLock<bioauth_settings::BioauthSettingsMap<[u8; 32]>>>,
     pub rw_dev_subscriptions_map: Arc<tokio::sync::RwLock<dev_subscriptions::DevSubscriptionMap>>,
+    pub rw_active_authentications: Arc<tokio::sync::RwLock<telegram::ActiveAuthentications>>,
 }
 
-pub async fn run(params: Params) -> Result<JoinSet<()>, anyhow::Error> {
+pub async fn run(params: Params) -> Result<MainLoop, anyhow::Error> {
     let Params {
         db,
         mut block_subscription,
@@ -33,16 +134,33 @@ pub async fn run(params: Params) -> Result<JoinSet<()>, anyhow::Error> {
         mut subscription_update_handle,
         rw_bioauth_settings_map,
         rw_dev_subscriptions_map,
+        rw_active_authentications,
     } = params;
 
     let all_loaded_data = db.load_for_initialization().await?;
     let all_team_subscriptions = db.load_all_team_subscriptions().await?;
+    let all_chat_settings = db.load_all_chat_settings().await?;
+    let all_notification_templates = db.load_all_notification_templates().await?;
+    let all_escalation_levels = db.load_all_escalation_levels().await?;
+    let last_processed_block = db.load_last_processed_block().await?;
+    let mut notification_states: HashMap<_, _> = db
+        .load_bioauth_notification_states()
+        .await?
+        .into_iter()
+        .map(|state| ((state.t_chat_id, state.validator_public_key), state))
+        .collect();
 
     tracing::info!(
         message = "Got all load",
         ?all_loaded_data,
-        ?all_team_subscriptions
+        ?all_team_subscriptions,
+        ?last_processed_block
     );
+
+    if let Some(last_processed_block) = last_processed_block {
+        block_subscription.resume_from(last_processed_block);
+    }
+
     let mut bioauths = vec![];
 
     {
@@ -51,9 +169,28 @@ pub async fn run(params: Params) -> Result<JoinSet<()>, anyhow::Error> {
         for data in all_loaded_data {
             tracing::info!(message = "main loop initializing data", ?data);
 
+            let state = notification_states
+                .remove(&(data.t_chat_id, data.validator_public_key))
+                .map(|state| bioauth_logic::BioauthNotificationState {
+                    last_block_number_notified: state.last_block_number_notified,
+                    next_block_number_to_notify: state.next_block_number_to_notify,
+                    alerted_expires_at: state.alerted_expires_at,
+                    alerted_thresholds_in_mins: state
+                        .alerted_thresholds_in_mins
+                        .into_iter()
+                        .collect(),
+                    is_lost: state.is_lost,
+                    acknowledged: state.acknowledged,
+                    snoozed_until: state.snoozed_until,
+                    lost_at: state.lost_at,
+                    escalated_levels: state.escalated_levels as usize,
+                })
+                .unwrap_or_default();
+
             bioauths.push(bioauth_logic::InitParamBioauth {
                 t_chat_id: data.t_chat_id,
                 bioauth_public_key: data.validator_public_key,
+                state,
             });
 
             bioauth_settings.update(
@@ -61,9 +198,52 @@ pub async fn run(params: Params) -> Result<JoinSet<()>, anyhow::Error> {
                 BioauthSettings {
                     alert_before_expiration_in_mins: data.alert_before_expiration_in_mins,
                     max_message_frequency_in_blocks: data.max_message_frequency_in_blocks,
+                    label: data.label,
+                    escalation_levels: vec![],
+                },
+            );
+        }
+
+        // The levels are loaded in the escalation order.
+        for data in all_escalation_levels {
+            let key = (data.t_chat_id, data.validator_public_key);
+            let mut settings = bioauth_settings.get(&key).clone();
+            settings
+                .escalation_levels
+                .push(bioauth_settings::EscalationLevel {
+                    chat_id: data.backup_chat_id,
+                    after_in_mins: data.after_in_mins,
+                });
+            bioauth_settings.update(key, settings);
+        }
+
+        for data in all_chat_settings {
+            let quiet_hours = match (data.quiet_hours_start_in_mins, data.quiet_hours_end_in_mins) {
+                (Some(start_in_mins), Some(end_in_mins)) => Some(bioauth_settings::QuietHours {
+                    start_in_mins: start_in_mins.try_into()?,
+                    end_in_mins: end_in_mins.try_into()?,
+                }),
+                _ => None,
+            };
+
+            bioauth_settings.update_chat(
+                data.t_chat_id,
+                bioauth_settings::ChatSettings {
+                    timezone_offset_in_mins: data.timezone_offset_in_mins,
+                    quiet_hours,
+                    language: data.language,
                 },
             );
         }
+
+        for data in all_notification_templates {
+            let Some(kind) = bioauth_settings::NotificationKind::from_name(&data.kind) else {
+                tracing::warn!(message = "unknown notification template kind", ?data);
+                continue;
+            };
+
+            bioauth_settings.update_template(data.t_chat_id, kind, Some(data.template));
+        }
     }
 
     {
@@ -78,28 +258,68 @@ pub async fn run(params: Params) -> Result<JoinSet<()>, anyhow::Error> {
         }
     }
 
-    let bioauth_logic = BioauthLogic::init(bioauth_logic::InitParams { bioauths });
+    let bioauth_logic = BioauthLogic::init(bioauth_logic::InitParams {
+        bioauths,
+        clock: bioauth_logic::SystemClock,
+    });
 
     let (notification_failures_tx, mut notification_failures_rx) =
         tokio::sync::mpsc::channel(10_000);
 
     let bioauth_logic = Arc::new(Mutex::new(bioauth_logic));
 
+    let pending_notifications = outbound_queue::load_pending(&db).await?;
+    tracing::info!(
+        message = "draining pending notifications",
+        len = pending_notifications.len()
+    );
+    for (id, notification) in pending_notifications {
+        outbound_queue::spawn_delivery(
+            &db,
+            &telegram_notification_handle,
+            &notification_failures_tx,
+            id,
+            notification,
+        );
+    }
+
     let mut tasks = tokio::task::JoinSet::new();
+    {
+        let bioauth_logic = Arc::clone(&bioauth_logic);
+        let db = db.clone();
+
+        tasks.spawn(async move {
+            let mut interval = tokio::time::interval(NOTIFICATION_STATES_SNAPSHOT_INTERVAL);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                save_notification_states(&bioauth_logic, &db).await;
+            }
+        });
+    }
     {
         let bioauth_logic = Arc::clone(&bioauth_logic);
         let telegram_notification_handle = telegram_notification_handle.clone();
         let rw_bioauth_settings_map = Arc::clone(&rw_bioauth_settings_map);
+        let db = db.clone();
 
         tasks.spawn(async move {
             let limit = 10_000;
-            let mut notification_failures_buffer: Vec<FailedNotification> =
+            let mut notification_failures_buffer: Vec<FailedNotification<[u8; 32]>> =
                 Vec::with_capacity(limit);
             loop {
-                let new_block_res = block_subscription.next_block().await;
+                let event_res = block_subscription.next_event().await;
 
-                let new_block_info = match new_block_res {
-                    Ok(val) => val,
+                let new_block_info = match event_res {
+                    Ok(block_subscription::Event::NewBlock(val)) => val,
+                    Ok(block_subscription::Event::AlreadyProcessed { block_number }) => {
+                        tracing::debug!(message = "block already processed", ?block_number);
+                        continue;
+                    }
+                    Ok(block_subscription::Event::Connection(event)) => {
+                        tracing::info!(message = "block subscription connection event", ?event);
+                        continue;
+                    }
                     Err(error) => {
                         tracing::error!(message = "new_block_error", ?error);
                         continue;
@@ -108,9 +328,34 @@ pub async fn run(params: Params) -> Result<JoinSet<()>, anyhow::Error> {
 
                 let block_subscription::BlockInfo {
                     block_number,
+                    timestamp,
                     active_authentications_map,
+                    bioauth_events,
+                    ..
                 } = new_block_info;
 
+                let bioauth_events: Vec<bioauth_logic::BioauthEvent<[u8; 32]>> = bioauth_events
+                    .into_iter()
+                    .map(|bioauth_event| match bioauth_event {
+                        block_subscription::BioauthEvent::NewAuthentication {
+                            validator_public_key,
+                        } => bioauth_logic::BioauthEvent::NewAuthentication {
+                            bioauth_public_key: validator_public_key,
+                        },
+                        block_subscription::BioauthEvent::AuthenticationExpired {
+                            validator_public_key,
+                        } => bioauth_logic::BioauthEvent::AuthenticationExpired {
+                            bioauth_public_key: validator_public_key,
+                        },
+                        block_subscription::BioauthEvent::AuthenticationRemoved {
+                            validator_public_key,
+                            ..
+                        } => bioauth_logic::BioauthEvent::AuthenticationRemoved {
+                            bioauth_public_key: validator_public_key,
+                        },
+                    })
+                    .collect();
+
                 let notifications = {
                     let mut logic = bioauth_logic.lock().await;
                     let bioauth_settings_map = rw_bioauth_settings_map.read().await;
@@ -132,7 +377,9 @@ pub async fn run(params: Params) -> Result<JoinSet<()>, anyhow::Error> {
 
                     logic.new_block(bioauth_logic::NewBlockParams {
                         active_authentications_map: &active_authentications_map,
+                        bioauth_events: &bioauth_events,
                         block_number,
+                        block_timestamp: timestamp,
                         bioauth_settings_map: &bioauth_settings_map,
                     })
                 };
@@ -143,34 +390,106 @@ pub async fn run(params: Params) -> Result<JoinSet<()>, anyhow::Error> {
                         bioauth_logic::Notification::BioauthLostNotification {
                             chat_id,
                             bioauth_public_key,
+                            block_number,
                         } => telegram::Notification::BioauthLostNotification {
                             chat_id: *chat_id,
                             bioauth_public_key: *bioauth_public_key,
+                            block_number: *block_number,
                         },
                         bioauth_logic::Notification::BioauthSoonExpiredAlert {
                             chat_id,
                             bioauth_public_key,
+                            expires_at,
                         } => telegram::Notification::BioauthSoonExpiredAlert {
                             chat_id: *chat_id,
                             bioauth_public_key: *bioauth_public_key,
+                            expires_at: *expires_at,
+                        },
+                        bioauth_logic::Notification::BioauthRestored {
+                            chat_id,
+                            bioauth_public_key,
+                            expires_at,
+                        } => telegram::Notification::BioauthRestored {
+                            chat_id: *chat_id,
+                            bioauth_public_key: *bioauth_public_key,
+                            expires_at: *expires_at,
+                        },
+                        bioauth_logic::Notification::BioauthAuthenticatedNotification {
+                            chat_id,
+                            bioauth_public_key,
+                            block_number,
+                        } => telegram::Notification::BioauthAuthenticatedNotification {
+                            chat_id: *chat_id,
+                            bioauth_public_key: *bioauth_public_key,
+                            block_number: *block_number,
+                        },
+                        bioauth_logic::Notification::BioauthExpiredNotification {
+                            chat_id,
+                            bioauth_public_key,
+                            block_number,
+                        } => telegram::Notification::BioauthExpiredNotification {
+                            chat_id: *chat_id,
+                            bioauth_public_key: *bioauth_public_key,
+                            block_number: *block_number,
+                        },
+                        bioauth_logic::Notification::BioauthRemovedNotification {
+                            chat_id,
+                            bioauth_public_key,
+                            block_number,
+                        } => telegram::Notification::BioauthRemovedNotification {
+                            chat_id: *chat_id,
+                            bioauth_public_key: *bioauth_public_key,
+                            block_number: *block_number,
+                        },
+                        bioauth_logic::Notification::BioauthLossEscalated {
+                            chat_id,
+                            bioauth_public_key,
+                            from_chat_id,
+                        } => telegram::Notification::BioauthLossEscalated {
+                            chat_id: *chat_id,
+                            bioauth_public_key: *bioauth_public_key,
+                            from_chat_id: *from_chat_id,
                         },
                     })
                     .collect();
 
-                for notification in telegram_notifications {
-                    let notification_failures_tx = notification_failures_tx.clone();
-                    let telegram_notification_handle = telegram_notification_handle.clone();
+                {
+                    let mut active_authentications = rw_active_authentications.write().await;
+                    active_authentications.block_number = Some(block_number);
+                    active_authentications.active_authentications_map = active_authentications_map;
+                }
 
-                    tokio::spawn(async move {
-                        if let Err(telegram::bioauth_handlers::SendNotificationError {
-                            notification,
-                        }) = telegram_notification_handle
-                            .send_notification(notification)
-                            .await
-                        {
-                            let _ = notification_failures_tx.send(notification).await;
-                        }
-                    });
+                // The notifications are persisted before the block is marked as processed,
+                // so they survive a crash and are not repeated if the block is processed again.
+                let queued =
+                    outbound_queue::enqueue(&db, block_number, telegram_notifications).await;
+
+                if let Err(error) = db.update_last_processed_block(block_number).await {
+                    tracing::error!(message = "update_last_processed_block error", ?error);
+                }
+
+                for (id, notification) in queued {
+                    outbound_queue::spawn_delivery(
+                        &db,
+                        &telegram_notification_handle,
+                        &notification_failures_tx,
+                        id,
+                        notification,
+                    );
+                }
+
+                if block_number % OUTBOUND_QUEUE_PURGE_INTERVAL_IN_BLOCKS == 0 {
+                    let before_block_number =
+                        block_number.saturating_sub(outbound_queue::DEDUPLICATION_WINDOW_IN_BLOCKS);
+                    if let Err(error) = db
+                        .purge_delivered_outbound_notifications(before_block_number)
+                        .await
+                    {
+                        tracing::error!(
+                            message = "purge_delivered_outbound_notifications error",
+                            ?error
+                        );
+                    }
                 }
             }
         });
@@ -179,6 +498,7 @@ pub async fn run(params: Params) -> Result<JoinSet<()>, anyhow::Error> {
     {
         let bioauth_logic = Arc::clone(&bioauth_logic);
         let rw_bioauth_settings_map = Arc::clone(&rw_bioauth_settings_map);
+        let db = db.clone();
         tasks.spawn(async move {
             loop {
                 let subscription_update = subscription_update_handle.next().await.unwrap();
@@ -191,6 +511,21 @@ pub async fn run(params: Params) -> Result<JoinSet<()>, anyhow::Error> {
                         chat_id: t_chat_id,
                         bioauth_public_key,
                     } => {
+                        // The existing subscription keeps its settings and notification state.
+                        if rw_bioauth_settings_map
+                            .read()
+                            .await
+                            .get_all_subscriptions_by_id(t_chat_id)
+                            .contains(&bioauth_public_key)
+                        {
+                            tracing::info!(
+                                message = "SubscribeToValidator skipped, already subscribed",
+                                ?t_chat_id,
+                                ?bioauth_public_key
+                            );
+                            continue;
+                        }
+
                         {
                             let mut bioauth_logic = bioauth_logic.lock().await;
 
@@ -302,42 +637,378 @@ pub async fn run(params: Params) -> Result<JoinSet<()>, anyhow::Error> {
                             .await
                             .unwrap();
                     }
+                    telegram::SubscriptionUpdate::RemoveDevSubscriptions { chat_id } => {
+                        {
+                            let mut dev_subscriptions =
+                                rw_dev_subscriptions_map.write().await;
+
+                            dev_subscriptions.remove(&chat_id);
+                        }
+                        tracing::info!(message = "RemoveDevSubscriptions", ?chat_id);
+
+                        db.remove_dev_subscriptions(chat_id).await.unwrap();
+                    }
+                    telegram::SubscriptionUpdate::MigrateChat {
+                        from_chat_id,
+                        to_chat_id,
+                    } => {
+                        tracing::info!(message = "MigrateChat", ?from_chat_id, ?to_chat_id);
+
+                        // The memory is only updated once the migration is persisted.
+                        if let Err(error) = db.migrate_chat(from_chat_id, to_chat_id).await {
+                            tracing::error!(
+                                message = "chat migration error",
+                                ?from_chat_id,
+                                ?to_chat_id,
+                                ?error
+                            );
+                            continue;
+                        }
+
+                        {
+                            let mut bioauth_logic = bioauth_logic.lock().await;
+
+                            bioauth_logic.migrate_chat(from_chat_id, to_chat_id);
+                        }
+
+                        {
+                            let mut bioauth_settings_map = rw_bioauth_settings_map.write().await;
+                            bioauth_settings_map.migrate_by_id(from_chat_id, to_chat_id);
+                        }
+
+                        {
+                            let mut dev_subscriptions =
+                                rw_dev_subscriptions_map.write().await;
+
+                            dev_subscriptions.migrate(&from_chat_id, to_chat_id);
+                        }
+                    }
+                    telegram::SubscriptionUpdate::UpdateTimezone {
+                        chat_id,
+                        timezone_offset_in_mins,
+                    } => {
+                        tracing::info!(message = "UpdateTimezone", ?chat_id, ?timezone_offset_in_mins);
+
+                        update_chat_settings(&rw_bioauth_settings_map, &db, chat_id, |chat_settings| {
+                            chat_settings.timezone_offset_in_mins = timezone_offset_in_mins;
+                        })
+                        .await;
+                    }
+                    telegram::SubscriptionUpdate::UpdateQuietHours {
+                        chat_id,
+                        quiet_hours,
+                    } => {
+                        tracing::info!(message = "UpdateQuietHours", ?chat_id, ?quiet_hours);
+
+                        update_chat_settings(&rw_bioauth_settings_map, &db, chat_id, |chat_settings| {
+                            chat_settings.quiet_hours = quiet_hours;
+                        })
+                        .await;
+                    }
+                    telegram::SubscriptionUpdate::UpdateLanguage { chat_id, language } => {
+                        tracing::info!(message = "UpdateLanguage", ?chat_id, ?language);
+
+                        update_chat_settings(&rw_bioauth_settings_map, &db, chat_id, |chat_settings| {
+                            chat_settings.language = language;
+                        })
+                        .await;
+                    }
+                    telegram::SubscriptionUpdate::UpdateNotificationTemplate {
+                        chat_id,
+                        kind,
+                        template,
+                    } => {
+                        {
+                            let mut bioauth_settings_map = rw_bioauth_settings_map.write().await;
+                            bioauth_settings_map.update_template(chat_id, kind, template.clone());
+                        }
+                        tracing::info!(message = "UpdateNotificationTemplate", ?chat_id, ?kind);
+
+                        db.update_notification_template(chat_id, kind.name(), template.as_deref())
+                            .await
+                            .unwrap();
+                    }
+                    telegram::SubscriptionUpdate::AcknowledgeLoss {
+                        chat_id,
+                        bioauth_public_key,
+                    } => {
+                        let acknowledged = {
+                            let mut bioauth_logic = bioauth_logic.lock().await;
+                            bioauth_logic.acknowledge(chat_id, &bioauth_public_key)
+                        };
+                        tracing::info!(
+                            message = "AcknowledgeLoss",
+                            ?chat_id,
+                            ?bioauth_public_key,
+                            ?acknowledged
+                        );
+
+                        if acknowledged {
+                            save_notification_states(&bioauth_logic, &db).await;
+                        }
+                    }
+                    telegram::SubscriptionUpdate::SnoozeLoss {
+                        chat_id,
+                        bioauth_public_key,
+                        in_mins,
+                    } => {
+                        let snoozed = {
+                            let mut bioauth_logic = bioauth_logic.lock().await;
+                            bioauth_logic.snooze(chat_id, &bioauth_public_key, in_mins)
+                        };
+                        tracing::info!(
+                            message = "SnoozeLoss",
+                            ?chat_id,
+                            ?bioauth_public_key,
+                            ?in_mins,
+                            ?snoozed
+                        );
+
+                        if snoozed {
+                            save_notification_states(&bioauth_logic, &db).await;
+                        }
+                    }
+                    telegram::SubscriptionUpdate::ApplySettingsCardAction {
+                        chat_id,
+                        bioauth_public_key,
+                        action,
+                        result_tx,
+                    } => {
+                        let res = {
+                            let mut bioauth_settings_map = rw_bioauth_settings_map.write().await;
+                            match bioauth_settings_map.get_mut(&(chat_id, bioauth_public_key)) {
+                                None => Err(telegram::SettingsCardActionError::NotSubscribed),
+                                Some(settings) => action.apply(settings).map(|updated| {
+                                    (std::mem::replace(settings, updated.clone()), updated)
+                                }),
+                            }
+                        };
+                        tracing::info!(
+                            message = "ApplySettingsCardAction",
+                            ?chat_id,
+                            ?bioauth_public_key,
+                            ?action,
+                            ?res
+                        );
+
+                        let (previous, updated) = match res {
+                            Ok(res) => res,
+                            Err(error) => {
+                                // The sender may have stopped waiting for the result.
+                                result_tx.send(Err(error)).ok();
+                                continue;
+                            }
+                        };
+
+                        if updated.max_message_frequency_in_blocks
+                            != previous.max_message_frequency_in_blocks
+                        {
+                            let res = match i32::try_from(updated.max_message_frequency_in_blocks) {
+                                Ok(in_blocks) => db
+                                    .update_bioauth_max_message_frequency_in_blocks(
+                                        chat_id,
+                                        &bioauth_public_key,
+                                        in_blocks,
+                                    )
+                                    .await,
+                                Err(error) => Err(error.into()),
+                            };
+                            if let Err(error) = res {
+                                tracing::error!(
+                                    message = "max message frequency update error",
+                                    ?chat_id,
+                                    ?bioauth_public_key,
+                                    ?error
+                                );
+                            }
+                        }
+                        if updated.alert_before_expiration_in_mins
+                            != previous.alert_before_expiration_in_mins
+                        {
+                            let res = match updated
+                                .alert_before_expiration_in_mins
+                                .iter()
+                                .map(|in_mins| i64::try_from(*in_mins))
+                                .collect::<Result<Vec<i64>, _>>()
+                            {
+                                Ok(in_mins) => db
+                                    .update_bioauth_alert_before_expiration_in_mins(
+                                        chat_id,
+                                        &bioauth_public_key,
+                                        &in_mins,
+                                    )
+                                    .await,
+                                Err(error) => Err(error.into()),
+                            };
+                            if let Err(error) = res {
+                                tracing::error!(
+                                    message = "alert thresholds update error",
+                                    ?chat_id,
+                                    ?bioauth_public_key,
+                                    ?error
+                                );
+                            }
+                        }
+
+                        result_tx.send(Ok(updated)).ok();
+                    }
+                    telegram::SubscriptionUpdate::UpdateEscalationLevels {
+                        chat_id,
+                        bioauth_public_key,
+                        escalation_levels,
+                    } => {
+                        let Ok(levels) = escalation_levels
+                            .iter()
+                            .map(|level| Ok((level.chat_id, i64::try_from(level.after_in_mins)?)))
+                            .collect::<Result<Vec<(i64, i64)>, std::num::TryFromIntError>>()
+                        else {
+                            tracing::error!(
+                                message = "escalation levels out of range",
+                                ?chat_id,
+                                ?bioauth_public_key,
+                                ?escalation_levels
+                            );
+                            continue;
+                        };
+                        {
+                            let mut bioauth_settings_map = rw_bioauth_settings_map.write().await;
+                            let Some(settings) =
+                                bioauth_settings_map.get_mut(&(chat_id, bioauth_public_key))
+                            else {
+                                tracing::warn!(
+                                    message = "UpdateEscalationLevels skipped, not subscribed",
+                                    ?chat_id,
+                                    ?bioauth_public_key
+                                );
+                                continue;
+                            };
+                            settings.escalation_levels = escalation_levels;
+                        }
+                        tracing::info!(
+                            message = "UpdateEscalationLevels",
+                            ?chat_id,
+                            ?bioauth_public_key
+                        );
+
+                        if let Err(error) =
+                            db.update_escalation_levels(chat_id, &bioauth_public_key, &levels).await
+                        {
+                            tracing::error!(
+                                message = "escalation levels update error",
+                                ?chat_id,
+                                ?bioauth_public_key,
+                                ?error
+                            );
+                        }
+                    }
                     telegram::SubscriptionUpdate::UpdateSubscriptionAlertBeforeExpirationInMins { chat_id, bioauth_public_key, in_mins } => {
+                        let Ok(in_mins_values) = in_mins
+                            .iter()
+                            .map(|in_mins| i64::try_from(*in_mins))
+                            .collect::<Result<Vec<i64>, _>>()
+                        else {
+                            tracing::error!(
+                                message = "alert thresholds out of range",
+                                ?chat_id,
+                                ?bioauth_public_key,
+                                ?in_mins
+                            );
+                            continue;
+                        };
                         {
                             let mut bioauth_settings_map =
                                 rw_bioauth_settings_map.write().await;
-                            let key = (chat_id, bioauth_public_key);
-                            let mut settings = bioauth_settings_map.get(&key).clone();
+                            let Some(settings) =
+                                bioauth_settings_map.get_mut(&(chat_id, bioauth_public_key))
+                            else {
+                                tracing::warn!(
+                                    message = "UpdateSubscriptionAlertBeforeExpirationInMins skipped, not subscribed",
+                                    ?chat_id,
+                                    ?bioauth_public_key
+                                );
+                                continue;
+                            };
                             settings.alert_before_expiration_in_mins = in_mins;
-                            bioauth_settings_map.update(
-                                key,
-                                settings
-                            )
                         }
 
-                        db.update_bioauth_alert_before_expiration_in_mins(chat_id, &bioauth_public_key, in_mins as i64)
-                            .await.unwrap();
+                        if let Err(error) = db.update_bioauth_alert_before_expiration_in_mins(chat_id, &bioauth_public_key, &in_mins_values).await {
+                            tracing::error!(
+                                message = "alert thresholds update error",
+                                ?chat_id,
+                                ?bioauth_public_key,
+                                ?error
+                            );
+                        }
                     }
                     telegram::SubscriptionUpdate::UpdateSubscriptionMaxMessageFrequencyInBlocks { chat_id, bioauth_public_key, in_blocks } => {
+                        let Ok(in_blocks_value) = i32::try_from(in_blocks) else {
+                            tracing::error!(
+                                message = "max message frequency out of range",
+                                ?chat_id,
+                                ?bioauth_public_key,
+                                ?in_blocks
+                            );
+                            continue;
+                        };
                         {
                             let mut bioauth_settings_map =
                                 rw_bioauth_settings_map.write().await;
-                            let key = (chat_id, bioauth_public_key);
-                            let mut settings = bioauth_settings_map.get(&key).clone();
+                            let Some(settings) =
+                                bioauth_settings_map.get_mut(&(chat_id, bioauth_public_key))
+                            else {
+                                tracing::warn!(
+                                    message = "UpdateSubscriptionMaxMessageFrequencyInBlocks skipped, not subscribed",
+                                    ?chat_id,
+                                    ?bioauth_public_key
+                                );
+                                continue;
+                            };
                             settings.max_message_frequency_in_blocks = in_blocks;
-                            bioauth_settings_map.update(
-                                key,
-                                settings
-                            )
                         }
 
-                        db.update_bioauth_max_message_frequency_in_blocks(chat_id, &bioauth_public_key, in_blocks as i32)
-                            .await.unwrap();
+                        if let Err(error) = db.update_bioauth_max_message_frequency_in_blocks(chat_id, &bioauth_public_key, in_blocks_value).await {
+                            tracing::error!(
+                                message = "max message frequency update error",
+                                ?chat_id,
+                                ?bioauth_public_key,
+                                ?error
+                            );
+                        }
+                    }
+                    telegram::SubscriptionUpdate::UpdateSubscriptionLabel { chat_id, bioauth_public_key, label } => {
+                        {
+                            let mut bioauth_settings_map =
+                                rw_bioauth_settings_map.write().await;
+                            let Some(settings) =
+                                bioauth_settings_map.get_mut(&(chat_id, bioauth_public_key))
+                            else {
+                                tracing::warn!(
+                                    message = "UpdateSubscriptionLabel skipped, not subscribed",
+                                    ?chat_id,
+                                    ?bioauth_public_key
+                                );
+                                continue;
+                            };
+                            settings.label.clone_from(&label);
+                        }
+
+                        if let Err(error) = db.update_bioauth_label(chat_id, &bioauth_public_key, label.as_deref()).await {
+                            tracing::error!(
+                                message = "label update error",
+                                ?chat_id,
+                                ?bioauth_public_key,
+                                ?error
+                            );
+                        }
                     }
                 }
             }
         });
     }
 
-    Ok(tasks)
+    Ok(MainLoop {
+        tasks,
+        bioauth_logic,
+        db,
+    })
 }
@@ -0,0 +1,271 @@
+//! The durable queue of the notifications on their way to telegram.
+
+use std::time::Duration;
+
+use bioauth_logic::{DeliveryError, FailedNotification};
+use database::{
+    db::Db,
+    models::{NewOutboundNotification, OutboundNotification},
+};
+
+const KIND_LOST: &str = "lost";
+const KIND_SOON_EXPIRED_ALERT: &str = "soon_expired_alert";
+const KIND_RESTORED: &str = "restored";
+const KIND_AUTHENTICATED: &str = "authenticated";
+const KIND_EXPIRED: &str = "expired";
+const KIND_REMOVED: &str = "removed";
+const KIND_ESCALATED: &str = "escalated";
+
+/// The delivered notifications are kept to skip them if their block is processed again.
+pub const DEDUPLICATION_WINDOW_IN_BLOCKS: u32 = block_subscription::DEFAULT_MAX_BACKFILL_IN_BLOCKS;
+
+/// The delay before the first redelivery attempt, doubled with every next one.
+const REDELIVERY_INITIAL_DELAY: Duration = Duration::from_secs(5);
+/// The upper bound for the exponentially growing redelivery delay.
+const REDELIVERY_MAX_DELAY: Duration = Duration::from_secs(300);
+/// The notification is left undelivered after as many attempts and is sent again on restart.
+const MAX_DELIVERY_ATTEMPTS: u32 = 10;
+
+fn to_new_outbound_notification(
+    notification: &telegram::Notification,
+    block_number: u32,
+) -> NewOutboundNotification {
+    let (kind, t_chat_id, validator_public_key, expires_at, from_chat_id) = match *notification {
+        telegram::Notification::BioauthLostNotification {
+            chat_id,
+            bioauth_public_key,
+            ..
+        } => (KIND_LOST, chat_id, bioauth_public_key, None, None),
+        telegram::Notification::BioauthSoonExpiredAlert {
+            chat_id,
+            bioauth_public_key,
+            expires_at,
+        } => (
+            KIND_SOON_EXPIRED_ALERT,
+            chat_id,
+            bioauth_public_key,
+            Some(expires_at),
+            None,
+        ),
+        telegram::Notification::BioauthRestored {
+            chat_id,
+            bioauth_public_key,
+            expires_at,
+        } => (
+            KIND_RESTORED,
+            chat_id,
+            bioauth_public_key,
+            Some(expires_at),
+            None,
+        ),
+        telegram::Notification::BioauthAuthenticatedNotification {
+            chat_id,
+            bioauth_public_key,
+            ..
+        } => (KIND_AUTHENTICATED, chat_id, bioauth_public_key, None, None),
+        telegram::Notification::BioauthExpiredNotification {
+            chat_id,
+            bioauth_public_key,
+            ..
+        } => (KIND_EXPIRED, chat_id, bioauth_public_key, None, None),
+        telegram::Notification::BioauthRemovedNotification {
+            chat_id,
+            bioauth_public_key,
+            ..
+        } => (KIND_REMOVED, chat_id, bioauth_public_key, None, None),
+        telegram::Notification::BioauthLossEscalated {
+            chat_id,
+            bioauth_public_key,
+            from_chat_id,
+        } => (
+            KIND_ESCALATED,
+            chat_id,
+            bioauth_public_key,
+            None,
+            Some(from_chat_id),
+        ),
+    };
+
+    NewOutboundNotification {
+        kind: kind.to_owned(),
+        t_chat_id,
+        validator_public_key,
+        block_number,
+        expires_at,
+        from_chat_id,
+    }
+}
+
+fn from_outbound_notification(
+    notification: &OutboundNotification,
+) -> Option<telegram::Notification> {
+    let chat_id = notification.t_chat_id;
+    let bioauth_public_key = notification.validator_public_key;
+    let block_number = notification.block_number;
+
+    let notification = match (notification.kind.as_str(), notification.expires_at) {
+        (KIND_LOST, _) => telegram::Notification::BioauthLostNotification {
+            chat_id,
+            bioauth_public_key,
+            block_number,
+        },
+        (KIND_SOON_EXPIRED_ALERT, Some(expires_at)) => {
+            telegram::Notification::BioauthSoonExpiredAlert {
+                chat_id,
+                bioauth_public_key,
+                expires_at,
+            }
+        }
+        (KIND_RESTORED, Some(expires_at)) => telegram::Notification::BioauthRestored {
+            chat_id,
+            bioauth_public_key,
+            expires_at,
+        },
+        (KIND_AUTHENTICATED, _) => telegram::Notification::BioauthAuthenticatedNotification {
+            chat_id,
+            bioauth_public_key,
+            block_number,
+        },
+        (KIND_EXPIRED, _) => telegram::Notification::BioauthExpiredNotification {
+            chat_id,
+            bioauth_public_key,
+            block_number,
+        },
+        (KIND_REMOVED, _) => telegram::Notification::BioauthRemovedNotification {
+            chat_id,
+            bioauth_public_key,
+            block_number,
+        },
+        (KIND_ESCALATED, _) => telegram::Notification::BioauthLossEscalated {
+            chat_id,
+            bioauth_public_key,
+            from_chat_id: notification.from_chat_id?,
+        },
+        _ => return None,
+    };
+
+    Some(notification)
+}
+
+/// Persist the notifications of the block, returning the ones to send along with their queue ids.
+///
+/// The notifications already queued for the block are dropped; if the queue is unavailable,
+/// all of them are sent without persisting.
+pub async fn enqueue(
+    db: &Db,
+    block_number: u32,
+    notifications: Vec<telegram::Notification>,
+) -> Vec<(Option<i64>, telegram::Notification)> {
+    if notifications.is_empty() {
+        return vec![];
+    }
+
+    let new_notifications: Vec<NewOutboundNotification> = notifications
+        .iter()
+        .map(|notification| to_new_outbound_notification(notification, block_number))
+        .collect();
+
+    match db.enqueue_outbound_notifications(&new_notifications).await {
+        Ok(queued) => queued
+            .iter()
+            .filter_map(|queued| {
+                let notification = from_outbound_notification(queued)?;
+                Some((Some(queued.id), notification))
+            })
+            .collect(),
+        Err(error) => {
+            tracing::error!(message = "enqueue_outbound_notifications error", ?error);
+            notifications
+                .into_iter()
+                .map(|notification| (None, notification))
+                .collect()
+        }
+    }
+}
+
+/// Load the notifications left undelivered before the restart.
+pub async fn load_pending(
+    db: &Db,
+) -> Result<Vec<(Option<i64>, telegram::Notification)>, anyhow::Error> {
+    let pending = db.load_pending_outbound_notifications().await?;
+
+    Ok(pending
+        .iter()
+        .filter_map(|pending| {
+            let Some(notification) = from_outbound_notification(pending) else {
+                tracing::warn!(message = "unknown outbound notification", ?pending);
+                return None;
+            };
+            Some((Some(pending.id), notification))
+        })
+        .collect())
+}
+
+fn redelivery_delay(attempt: u32) -> Duration {
+    REDELIVERY_INITIAL_DELAY
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(REDELIVERY_MAX_DELAY)
+}
+
+/// Send the notification in the background, reporting the failure and marking it delivered.
+///
+/// The lost and soon expired notifications are produced again by the logic once their retryable
+/// failure is reported, the other ones are sent again here with backoff and stay undelivered
+/// in the queue until then.
+pub fn spawn_delivery(
+    db: &Db,
+    telegram_notification_handle: &telegram::NotificationHandle,
+    notification_failures_tx: &tokio::sync::mpsc::Sender<FailedNotification<[u8; 32]>>,
+    id: Option<i64>,
+    notification: telegram::Notification,
+) {
+    let db = db.clone();
+    let telegram_notification_handle = telegram_notification_handle.clone();
+    let notification_failures_tx = notification_failures_tx.clone();
+
+    tokio::spawn(async move {
+        let mut attempt = 0;
+        while let Err(telegram::bioauth_handlers::SendNotificationError {
+            notification: failed,
+        }) = telegram_notification_handle
+            .send_notification(notification.clone())
+            .await
+        {
+            attempt += 1;
+            let is_redelivered = matches!(
+                failed,
+                FailedNotification::OtherNotificationFailed {
+                    error: DeliveryError::Retryable,
+                    ..
+                }
+            );
+            if is_redelivered && attempt < MAX_DELIVERY_ATTEMPTS {
+                let delay = redelivery_delay(attempt - 1);
+                tracing::warn!(
+                    message = "notification delivery failed, retrying",
+                    ?notification,
+                    ?attempt,
+                    ?delay
+                );
+                tokio::time::sleep(delay).await;
+                continue;
+            }
+
+            tracing::warn!(message = "notification delivery failed", ?failed);
+            let _ = notification_failures_tx.send(failed).await;
+            if is_redelivered {
+                return;
+            }
+            break;
+        }
+
+        if let Some(id) = id {
+            if let Err(error) = db.mark_outbound_notification_delivered(id).await {
+                tracing::error!(
+                    message = "mark_outbound_notification_delivered error",
+                    ?error
+                );
+            }
+        }
+    });
+}
@@ -0,0 +1,304 @@
+## Commands
+
+help-header = These commands are supported:
+
+command-help = display this text
+command-start = Welcome message
+command-managevalidatorsubscriptions = manage validator subscriptions, you can add new ones, configure existing ones individually, update notification frequency, set warning time before validator status loss, or unsubscribe from one
+command-managedevsubscriptions = manage notifications from the developer. Stay informed about network updates that may affect your validator status
+command-status = show the current bio-authentication status of your validators
+command-export = export your validator subscriptions and their settings as a document
+command-import = restore validator subscriptions from an exported document
+command-timezone = show or set the UTC offset of your local time, e.g. /timezone +02:00
+command-quiethours = show or set the local time range the repeated notifications are deferred in, e.g. /quiethours 23:00-07:00
+command-language = show or set the language of the bot, e.g. /language es
+command-template = show or set the custom notification texts, e.g. /template lost {"{name}"} is down, @oncall
+command-escalation = show or set the backup chats the unacknowledged loss is escalated to, e.g. /escalation <address> -1001234567890 15
+command-resetstate = #debug_command restart state.
+command-cancel = cancel the operation
+command-subscribe = add new subscription
+command-settings = show the settings of this subscription with the buttons to change them
+command-updatemaxmessagefrequency = update the maximum message frequency (in block est. ~6sec per block) for this subscription
+command-updatealertbefore = adjust the alert times (in minutes) before losing validator status, e.g. 1440, 60, 10
+command-rename = set the label shown instead of the address for this subscription
+command-unsubscribe = unsubscribe from this subscription
+command-adminnotify = notify subscribers
+
+## Common
+
+start-message =
+    !!! PAY ATTENTION !!!
+    !!! THIS IS EARLY ACCESS VERSION !!!
+    !!! SOME FUNCTIONALITY CAN BE UNSTABLE !!!
+    !!! SOMETIMES BOT CAN BE FULLY RESET, BUT I WILL NOTIFY YOU ABOUT THAT IF YOU ACTIVATE DEVELOPER NOTIFICATION !!!
+
+    Welcome to the biostatus bot!
+
+    By using this bot, you will receive timely notifications regarding the impending loss of your validator status according to your settings.
+
+    If your validator status is lost, or you are no longer a validator, you will be regularly notified based on your settings.
+
+    All bot messages are customizable to your liking, ensuring that you won't need to mute the bot and can stay informed about your status, allowing for immediate action.
+
+    It's also recommended to enable developer notifications to stay updated on any network changes that might affect your validator status, you can always unsubscribe from developer notifications if you find them irrelevant.
+
+    Use /language command to change the language of the bot.
+
+    Use /help command to display bot usage instructions.
+help-hint = Use /help command to display bot usage instructions.
+reset-state = Resetting state
+status-no-subscriptions = You have no validator subscriptions, use /managevalidatorsubscriptions command to add one.
+status-no-block = The bio-authentication status is not received from the chain yet, try again later.
+status-header = Status at block #{ $block_number }:
+status-active = { $name } is active, the authentication expires at { $expires_at }.
+status-not-authenticated = { $name } is not bio-authenticated.
+
+## Notifications
+
+notification-lost = { $name } have lost bio-authentication to be an active validator at block #{ $block_number }.
+notification-soon-expired = { $name } will lost bio-authentication soon, the authentication expires at { $expires_at }
+notification-restored = { $name } has restored bio-authentication and is an active validator again. The authentication expires at { $expires_at }.
+notification-authenticated = { $name } re-authenticated at block #{ $block_number }.
+notification-expired = { $name } authentication expired at block #{ $block_number }.
+notification-removed = { $name } authentication was removed due to an offence at block #{ $block_number }.
+notification-escalated = { $name } have lost bio-authentication and the alert is not acknowledged in time in the chat { $from_chat }.
+expires-at-passed = { $time } (already passed)
+expires-at-in = { $time } (in { $hours }h { $mins }m)
+alert-button-status = Show status
+alert-button-settings = Settings
+alert-button-snooze = Snooze { $hours }h
+alert-button-acknowledge = Acknowledge
+alert-not-lost = { $name } is not lost anymore.
+alert-snoozed = The lost notifications of { $name } are snoozed for { $hours }h { $mins }m.
+alert-acknowledged = The loss of { $name } is acknowledged, the notifications are not repeated until it is restored.
+alert-not-escalated = The loss of { $address } is not escalated to this chat.
+alert-escalation-acknowledged = The loss of { $name } is acknowledged in the backup chat { $chat_id }.
+
+## Validator subscriptions
+
+no-subscriptions-message =
+    You don't have any subscriptions yet,
+
+    use /help command to display bot usage instructions.
+choose-subscription = Choose a subscription to manage it, or use /help command to display bot usage instructions.
+subscriptions-unchanged-message =
+    Your validator subscriptions remain unchanged.
+
+    use /help command to display bot usage instructions.
+action-canceled = You have canceled the action.
+subscription-selected = Subscription { $address } selected
+cancel-message =
+    You have canceled the action.
+
+    use /help command to display bot usage instructions.
+cancel-subscriptions-unchanged-message =
+    You have canceled the action.
+
+    Your validator subscriptions remain unchanged.
+
+    use /help command to display bot usage instructions.
+
+subscribe-message =
+    Enter the validator address (must start with 'hm..'), or use /help command to display bot usage instructions.
+
+    To add many validators at once, enter their addresses separated by newlines, commas or spaces.
+subscribe-activated = Subscription action is activated
+subscribed-message =
+    Validator address successfully added.
+    You will now receive notifications according to your settings.
+    You can manage the settings for this subscription.
+
+    Use /help command to display bot usage instructions
+already-subscribed-message =
+    You are already subscribed to this validator, its settings are kept.
+    You can manage the settings for this subscription.
+enter-address = Enter address
+too-many-addresses = Too many addresses, enter at most { $max } at once.
+invalid-address = invalid address { $error }
+duplicate-address = duplicate
+already-subscribed = already subscribed
+subscribe-report = Subscribed to { $subscribed } of { $total } addresses:
+enter-valid-addresses = Enter valid addresses.
+
+unsubscribe-confirm =
+    Enter the 'agree' to confirm to unsubscribe from { $address },
+
+    use /help command to display bot usage instructions.
+unsubscribed = Successfully unsubscribed.
+unsubscribe-enter-agree = Please enter the word 'agree' to confirm the operation.
+
+max-frequency-current = Current maximum message frequency for { $address }: { $in_blocks } blocks
+max-frequency-message = Enter the new value of the maximum message frequency in blocks (where each block is roughly equivalent to 6 seconds).
+max-frequency-updated = Maximum message frequency updated.
+max-frequency-invalid = Enter a number of blocks from 1 to { $max }.
+
+alert-times-current = Current alert times for { $address }: { $in_mins } mins
+alert-times-message =
+    Enter the new alert times in minutes, separated by commas or spaces (for example: 1440, 360, 60, 10).
+    Each of the alerts is sent once per authentication period.
+alert-times-updated = Alert times updated.
+alert-times-invalid = Enter from 1 to { $max } numbers of minutes, each from 1 to { $max_mins }.
+
+label-current = Current label for { $address }: { $label }
+label-not-set = not set
+rename-message =
+    Enter the new label of the validator, it is shown instead of the address in the subscriptions list and the notifications.
+
+    Enter - to remove the label.
+label-empty = The label can't be empty.
+label-too-long = The label can't be longer than { $max } characters.
+label-updated = Label updated.
+enter-new-label = Enter the new label.
+
+## Settings card
+
+settings-card =
+    Settings of { $name }
+
+    Maximum message frequency: { $in_blocks } blocks (~{ $duration })
+    Alert times: { $alert_times } mins
+
+    Use the buttons to change them.
+settings-card-subtract-blocks = − { $in_blocks } blocks
+settings-card-add-blocks = + { $in_blocks } blocks
+settings-card-not-subscribed = You are not subscribed to { $address }.
+settings-card-alert-required = At least one alert time is required.
+settings-card-too-many-alerts = Too many alert times.
+duration-secs = { $secs } sec
+duration-mins = { $mins } min
+duration-hours = { $hours } h
+
+## Export and import
+
+export-empty = You have no validator subscriptions to export.
+export-caption = Send this document after the /import command to restore the subscriptions.
+import-message =
+    Send the document exported with the /export command.
+
+    The subscriptions from the document are added to this chat, the settings of the existing ones are replaced.
+import-send-document = Send the exported document, or use /cancel command to cancel the import.
+import-too-large = The document is too large.
+import-invalid-document = The document is not valid: { $error }
+import-too-many = Too many subscriptions, import at most { $max } at once.
+import-invalid-alert-times = invalid alert times
+import-invalid-max-frequency = invalid maximum message frequency
+import-settings-restored = already subscribed, the settings are restored
+import-report = Imported { $imported } of { $total } subscriptions:
+import-cancel-message =
+    You have canceled the import.
+
+    Your validator subscriptions remain unchanged.
+
+    use /help command to display bot usage instructions.
+
+## Chat settings
+
+timezone-message =
+    Current timezone: { $timezone }
+
+    Send /timezone with the UTC offset of your local time to change it, for example: /timezone +02:00 or /timezone -5.
+timezone-invalid = Enter the UTC offset from -12:00 to +14:00, for example: /timezone +02:00
+timezone-updated = Timezone updated: { $timezone }
+quiet-hours-message =
+    Current quiet hours: { $hours } ({ $timezone })
+
+    In the quiet hours the repeated lost notifications and the early expiration alerts are deferred until the quiet hours end. The first lost notification and the final expiration alert are always sent.
+
+    Send /quiethours with the local time range to change them, for example: /quiethours 23:00-07:00, or /quiethours off to disable them.
+quiet-hours-invalid = Enter the local time range, for example: /quiethours 23:00-07:00
+quiet-hours-updated = Quiet hours updated: { $hours } ({ $timezone })
+
+language-auto = { $language } (detected from your Telegram settings)
+language-message =
+    Current language: { $current }
+
+    Send /language with the code of the language to change it, or /language auto to detect it from your Telegram settings:
+    { $languages }
+language-invalid =
+    Enter one of the supported languages:
+    { $languages }
+language-updated = Language updated: { $language }
+
+## Notification templates
+
+template-message =
+    Custom notification texts:
+    { $templates }
+
+    Send /template with the notification kind and the text to change it, for example:
+    /template lost {"{name}"} is down, @oncall see https://wiki.example.com/runbook
+
+    Send /template with the kind to show its text, or with the kind and reset to restore the default text, e.g. /template lost reset.
+    Write {"{{"} and {"}}"} for the braces themselves.
+
+    The kinds and their placeholders:
+    { $kinds }
+template-none = none, the default texts are used
+template-default = the default text
+template-invalid-kind =
+    Enter one of the notification kinds:
+    { $kinds }
+template-current =
+    The text of the { $kind } notifications: { $template }
+
+    The placeholders: { $placeholders }
+template-reset = The default text of the { $kind } notifications is restored.
+template-empty = The text can't be empty.
+template-too-long = The text can't be longer than { $max } characters.
+template-unclosed = The placeholder is not closed, write {"{{"} and {"}}"} for the braces themselves.
+template-unknown-placeholder =
+    The {"{"}{ $placeholder }{"}"} placeholder is not available for this notification kind.
+    The placeholders: { $placeholders }
+template-updated =
+    The text of the { $kind } notifications is updated, the preview:
+
+    { $preview }
+
+## Escalation
+
+escalation-message =
+    The backup chats the loss is escalated to if it is not acknowledged in time:
+    { $subscriptions }
+
+    Send /escalation with the address, the backup chat id and the delay in minutes since the loss to change them, a third level chat can follow, for example:
+    /escalation <address> -1001234567890 15 -1009876543210 60
+
+    Send /escalation with the address to show its backup chats, or with the address and off to disable the escalation.
+    The bot has to be added to the backup chats.
+escalation-none = no escalation
+escalation-level = { $level }. { $chat_id } after { $mins } min
+escalation-current =
+    The backup chats of { $name }:
+    { $levels }
+escalation-invalid = Enter the address followed by up to { $max } pairs of the backup chat id and the delay in minutes, e.g. /escalation <address> -1001234567890 15
+escalation-own-chat = The loss can't be escalated to this chat itself.
+escalation-not-ascending = The delays must be from 1 to { $max_mins } minutes and increase level by level.
+escalation-unreachable-chat = The bot can't reach the chat { $chat_id }, add the bot to it first.
+escalation-not-member = Only the members of the chat { $chat_id } can escalate to it.
+escalation-updated =
+    The backup chats of { $name } are updated:
+    { $levels }
+
+## Developer subscriptions
+
+enable-affected-validator = Enable affected validator notifications
+disable-affected-validator = Disable affected validator notifications
+dev-subscription-enabled =
+    Subscription successfully enabled
+
+    use /help command to display bot usage instructions.
+dev-subscription-disabled =
+    Subscription successfully disabled
+
+    use /help command to display bot usage instructions.
+dev-subscriptions-cancel-message =
+    You have canceled the action.
+
+    Your developer notification subscriptions remain unchanged.
+
+    use /help command to display bot usage instructions.
+
+## Admin
+
+admin-command-got = Admin command got
+admin-sent = Sent this text to { $subscribers }
@@ -0,0 +1,304 @@
+## Commands
+
+help-header = Se admiten estos comandos:
+
+command-help = mostrar este texto
+command-start = Mensaje de bienvenida
+command-managevalidatorsubscriptions = gestionar las suscripciones a validadores: añadir nuevas, configurar las existentes, cambiar la frecuencia de las notificaciones, el tiempo de aviso antes de perder el estado de validador o cancelar una suscripción
+command-managedevsubscriptions = gestionar las notificaciones del desarrollador. Manténgase informado de las actualizaciones de la red que pueden afectar al estado de su validador
+command-status = mostrar el estado actual de bioautenticación de sus validadores
+command-export = exportar sus suscripciones a validadores y su configuración como documento
+command-import = restaurar las suscripciones a validadores desde un documento exportado
+command-timezone = mostrar o establecer la diferencia de su hora local con UTC, p. ej. /timezone +02:00
+command-quiethours = mostrar o establecer el intervalo de hora local en el que se aplazan las notificaciones repetidas, p. ej. /quiethours 23:00-07:00
+command-language = mostrar o establecer el idioma del bot, p. ej. /language es
+command-template = mostrar o establecer los textos propios de las notificaciones, p. ej. /template lost {"{name}"} está caído, @oncall
+command-escalation = mostrar o configurar los chats de respaldo a los que se escala la pérdida no reconocida, p. ej. /escalation <dirección> -1001234567890 15
+command-resetstate = #debug_command reiniciar el estado.
+command-cancel = cancelar la operación
+command-subscribe = añadir una nueva suscripción
+command-settings = mostrar la configuración de esta suscripción con botones para cambiarla
+command-updatemaxmessagefrequency = cambiar la frecuencia máxima de mensajes (en bloques, ~6 s por bloque) de esta suscripción
+command-updatealertbefore = ajustar los tiempos de aviso (en minutos) antes de perder el estado de validador, p. ej. 1440, 60, 10
+command-rename = establecer la etiqueta que se muestra en lugar de la dirección de esta suscripción
+command-unsubscribe = cancelar esta suscripción
+command-adminnotify = notificar a los suscriptores
+
+## Common
+
+start-message =
+    !!! ATENCIÓN !!!
+    !!! ESTA ES UNA VERSIÓN DE ACCESO ANTICIPADO !!!
+    !!! ALGUNAS FUNCIONES PUEDEN SER INESTABLES !!!
+    !!! A VECES EL BOT PUEDE REINICIARSE POR COMPLETO, PERO LE AVISARÉ SI ACTIVA LAS NOTIFICACIONES DEL DESARROLLADOR !!!
+
+    ¡Bienvenido al bot biostatus!
+
+    Con este bot recibirá a tiempo notificaciones sobre la pérdida inminente del estado de su validador según su configuración.
+
+    Si pierde el estado de validador o deja de ser validador, se le notificará periódicamente según su configuración.
+
+    Todos los mensajes del bot se pueden ajustar a su gusto, así no tendrá que silenciar el bot y estará al tanto de su estado para actuar de inmediato.
+
+    También se recomienda activar las notificaciones del desarrollador para conocer los cambios de la red que puedan afectar al estado de su validador; siempre puede cancelarlas si no le resultan útiles.
+
+    Use el comando /language para cambiar el idioma del bot.
+
+    Use el comando /help para ver las instrucciones de uso del bot.
+help-hint = Use el comando /help para ver las instrucciones de uso del bot.
+reset-state = Reiniciando el estado
+status-no-subscriptions = No tiene suscripciones a validadores, use el comando /managevalidatorsubscriptions para añadir una.
+status-no-block = El estado de bioautenticación aún no se ha recibido de la red, inténtelo más tarde.
+status-header = Estado en el bloque #{ $block_number }:
+status-active = { $name } está activo, la autenticación caduca el { $expires_at }.
+status-not-authenticated = { $name } no está bioautenticado.
+
+## Notifications
+
+notification-lost = { $name } ha perdido la bioautenticación para ser un validador activo en el bloque #{ $block_number }.
+notification-soon-expired = { $name } perderá pronto la bioautenticación, la autenticación caduca el { $expires_at }
+notification-restored = { $name } ha restablecido la bioautenticación y vuelve a ser un validador activo. La autenticación caduca el { $expires_at }.
+notification-authenticated = { $name } se ha vuelto a autenticar en el bloque #{ $block_number }.
+notification-expired = La autenticación de { $name } caducó en el bloque #{ $block_number }.
+notification-removed = La autenticación de { $name } se eliminó por una infracción en el bloque #{ $block_number }.
+notification-escalated = { $name } perdió la bioautenticación y la alerta no fue reconocida a tiempo en el chat { $from_chat }.
+expires-at-passed = { $time } (ya pasó)
+expires-at-in = { $time } (dentro de { $hours } h { $mins } min)
+alert-button-status = Ver estado
+alert-button-settings = Ajustes
+alert-button-snooze = Posponer { $hours } h
+alert-button-acknowledge = Entendido
+alert-not-lost = { $name } ya no ha perdido la autenticación.
+alert-snoozed = Las notificaciones de pérdida de { $name } se pospusieron { $hours } h { $mins } min.
+alert-acknowledged = La pérdida de { $name } fue reconocida, las notificaciones no se repetirán hasta que se restaure.
+alert-not-escalated = La pérdida de { $address } no se escala a este chat.
+alert-escalation-acknowledged = La pérdida de { $name } fue reconocida en el chat de respaldo { $chat_id }.
+
+## Validator subscriptions
+
+no-subscriptions-message =
+    Todavía no tiene suscripciones,
+
+    use el comando /help para ver las instrucciones de uso del bot.
+choose-subscription = Elija una suscripción para gestionarla o use el comando /help para ver las instrucciones de uso del bot.
+subscriptions-unchanged-message =
+    Sus suscripciones a validadores no han cambiado.
+
+    use el comando /help para ver las instrucciones de uso del bot.
+action-canceled = Ha cancelado la acción.
+subscription-selected = Suscripción { $address } seleccionada
+cancel-message =
+    Ha cancelado la acción.
+
+    use el comando /help para ver las instrucciones de uso del bot.
+cancel-subscriptions-unchanged-message =
+    Ha cancelado la acción.
+
+    Sus suscripciones a validadores no han cambiado.
+
+    use el comando /help para ver las instrucciones de uso del bot.
+
+subscribe-message =
+    Introduzca la dirección del validador (debe empezar por 'hm..') o use el comando /help para ver las instrucciones de uso del bot.
+
+    Para añadir varios validadores a la vez, introduzca sus direcciones separadas por saltos de línea, comas o espacios.
+subscribe-activated = La acción de suscripción está activada
+subscribed-message =
+    Dirección del validador añadida correctamente.
+    A partir de ahora recibirá notificaciones según su configuración.
+    Puede gestionar la configuración de esta suscripción.
+
+    Use el comando /help para ver las instrucciones de uso del bot
+already-subscribed-message =
+    Ya está suscrito a este validador, su configuración se mantiene.
+    Puede gestionar la configuración de esta suscripción.
+enter-address = Introduzca la dirección
+too-many-addresses = Demasiadas direcciones, introduzca como máximo { $max } a la vez.
+invalid-address = dirección no válida { $error }
+duplicate-address = duplicada
+already-subscribed = ya suscrito
+subscribe-report = Suscrito a { $subscribed } de { $total } direcciones:
+enter-valid-addresses = Introduzca direcciones válidas.
+
+unsubscribe-confirm =
+    Introduzca 'agree' para confirmar la cancelación de la suscripción a { $address },
+
+    use el comando /help para ver las instrucciones de uso del bot.
+unsubscribed = Suscripción cancelada correctamente.
+unsubscribe-enter-agree = Introduzca la palabra 'agree' para confirmar la operación.
+
+max-frequency-current = Frecuencia máxima de mensajes actual para { $address }: { $in_blocks } bloques
+max-frequency-message = Introduzca el nuevo valor de la frecuencia máxima de mensajes en bloques (cada bloque equivale aproximadamente a 6 segundos).
+max-frequency-updated = Frecuencia máxima de mensajes actualizada.
+max-frequency-invalid = Introduzca un número de bloques de 1 a { $max }.
+
+alert-times-current = Tiempos de aviso actuales para { $address }: { $in_mins } min
+alert-times-message =
+    Introduzca los nuevos tiempos de aviso en minutos, separados por comas o espacios (por ejemplo: 1440, 360, 60, 10).
+    Cada aviso se envía una vez por período de autenticación.
+alert-times-updated = Tiempos de aviso actualizados.
+alert-times-invalid = Introduzca de 1 a { $max } números de minutos, cada uno de 1 a { $max_mins }.
+
+label-current = Etiqueta actual para { $address }: { $label }
+label-not-set = sin establecer
+rename-message =
+    Introduzca la nueva etiqueta del validador, se muestra en lugar de la dirección en la lista de suscripciones y en las notificaciones.
+
+    Introduzca - para eliminar la etiqueta.
+label-empty = La etiqueta no puede estar vacía.
+label-too-long = La etiqueta no puede tener más de { $max } caracteres.
+label-updated = Etiqueta actualizada.
+enter-new-label = Introduzca la nueva etiqueta.
+
+## Settings card
+
+settings-card =
+    Configuración de { $name }
+
+    Frecuencia máxima de mensajes: { $in_blocks } bloques (~{ $duration })
+    Tiempos de aviso: { $alert_times } min
+
+    Use los botones para cambiarla.
+settings-card-subtract-blocks = − { $in_blocks } bloques
+settings-card-add-blocks = + { $in_blocks } bloques
+settings-card-not-subscribed = No está suscrito a { $address }.
+settings-card-alert-required = Se necesita al menos un tiempo de aviso.
+settings-card-too-many-alerts = Demasiados tiempos de aviso.
+duration-secs = { $secs } s
+duration-mins = { $mins } min
+duration-hours = { $hours } h
+
+## Export and import
+
+export-empty = No tiene suscripciones a validadores para exportar.
+export-caption = Envíe este documento después del comando /import para restaurar las suscripciones.
+import-message =
+    Envíe el documento exportado con el comando /export.
+
+    Las suscripciones del documento se añaden a este chat y la configuración de las existentes se reemplaza.
+import-send-document = Envíe el documento exportado o use el comando /cancel para cancelar la importación.
+import-too-large = El documento es demasiado grande.
+import-invalid-document = El documento no es válido: { $error }
+import-too-many = Demasiadas suscripciones, importe como máximo { $max } a la vez.
+import-invalid-alert-times = tiempos de aviso no válidos
+import-invalid-max-frequency = frecuencia máxima de mensajes no válida
+import-settings-restored = ya suscrito, se restauraron los ajustes
+import-report = Importadas { $imported } de { $total } suscripciones:
+import-cancel-message =
+    Ha cancelado la importación.
+
+    Sus suscripciones a validadores no han cambiado.
+
+    use el comando /help para ver las instrucciones de uso del bot.
+
+## Chat settings
+
+timezone-message =
+    Zona horaria actual: { $timezone }
+
+    Envíe /timezone con la diferencia de su hora local con UTC para cambiarla, por ejemplo: /timezone +02:00 o /timezone -5.
+timezone-invalid = Introduzca la diferencia con UTC de -12:00 a +14:00, por ejemplo: /timezone +02:00
+timezone-updated = Zona horaria actualizada: { $timezone }
+quiet-hours-message =
+    Horas de silencio actuales: { $hours } ({ $timezone })
+
+    Durante las horas de silencio, las notificaciones repetidas de pérdida y los avisos anticipados de caducidad se aplazan hasta que terminen. La primera notificación de pérdida y el último aviso de caducidad se envían siempre.
+
+    Envíe /quiethours con el intervalo de hora local para cambiarlas, por ejemplo: /quiethours 23:00-07:00, o /quiethours off para desactivarlas.
+quiet-hours-invalid = Introduzca el intervalo de hora local, por ejemplo: /quiethours 23:00-07:00
+quiet-hours-updated = Horas de silencio actualizadas: { $hours } ({ $timezone })
+
+language-auto = { $language } (según su configuración de Telegram)
+language-message =
+    Idioma actual: { $current }
+
+    Envíe /language con el código del idioma para cambiarlo, o /language auto para detectarlo según su configuración de Telegram:
+    { $languages }
+language-invalid =
+    Introduzca uno de los idiomas admitidos:
+    { $languages }
+language-updated = Idioma actualizado: { $language }
+
+## Notification templates
+
+template-message =
+    Textos propios de las notificaciones:
+    { $templates }
+
+    Envíe /template con el tipo de notificación y el texto para cambiarlo, por ejemplo:
+    /template lost {"{name}"} está caído, @oncall ver https://wiki.example.com/runbook
+
+    Envíe /template con el tipo para ver su texto, o con el tipo y reset para restaurar el texto predeterminado, p. ej. /template lost reset.
+    Escriba {"{{"} y {"}}"} para las propias llaves.
+
+    Los tipos y sus marcadores:
+    { $kinds }
+template-none = ninguno, se usan los textos predeterminados
+template-default = el texto predeterminado
+template-invalid-kind =
+    Introduzca uno de los tipos de notificación:
+    { $kinds }
+template-current =
+    El texto de las notificaciones { $kind }: { $template }
+
+    Los marcadores: { $placeholders }
+template-reset = Se ha restaurado el texto predeterminado de las notificaciones { $kind }.
+template-empty = El texto no puede estar vacío.
+template-too-long = El texto no puede tener más de { $max } caracteres.
+template-unclosed = El marcador no está cerrado, escriba {"{{"} y {"}}"} para las propias llaves.
+template-unknown-placeholder =
+    El marcador {"{"}{ $placeholder }{"}"} no está disponible para este tipo de notificación.
+    Los marcadores: { $placeholders }
+template-updated =
+    Se ha actualizado el texto de las notificaciones { $kind }, la vista previa:
+
+    { $preview }
+
+## Escalation
+
+escalation-message =
+    Los chats de respaldo a los que se escala la pérdida si no se reconoce a tiempo:
+    { $subscriptions }
+
+    Envía /escalation con la dirección, el id del chat de respaldo y el retraso en minutos desde la pérdida para cambiarlos, puede seguir un chat de tercer nivel, por ejemplo:
+    /escalation <dirección> -1001234567890 15 -1009876543210 60
+
+    Envía /escalation con la dirección para ver sus chats de respaldo, o con la dirección y off para desactivar la escalada.
+    El bot tiene que estar agregado a los chats de respaldo.
+escalation-none = sin escalada
+escalation-level = { $level }. { $chat_id } después de { $mins } min
+escalation-current =
+    Los chats de respaldo de { $name }:
+    { $levels }
+escalation-invalid = Introduce la dirección seguida de hasta { $max } pares del id del chat de respaldo y el retraso en minutos, p. ej. /escalation <dirección> -1001234567890 15
+escalation-own-chat = La pérdida no se puede escalar a este mismo chat.
+escalation-not-ascending = Los retrasos deben ser de 1 a { $max_mins } minutos y aumentar nivel por nivel.
+escalation-unreachable-chat = El bot no puede acceder al chat { $chat_id }, agrega el bot primero.
+escalation-not-member = Solo los miembros del chat { $chat_id } pueden escalar a él.
+escalation-updated =
+    Los chats de respaldo de { $name } se actualizaron:
+    { $levels }
+
+## Developer subscriptions
+
+enable-affected-validator = Activar las notificaciones de validadores afectados
+disable-affected-validator = Desactivar las notificaciones de validadores afectados
+dev-subscription-enabled =
+    Suscripción activada correctamente
+
+    use el comando /help para ver las instrucciones de uso del bot.
+dev-subscription-disabled =
+    Suscripción desactivada correctamente
+
+    use el comando /help para ver las instrucciones de uso del bot.
+dev-subscriptions-cancel-message =
+    Ha cancelado la acción.
+
+    Sus suscripciones a las notificaciones del desarrollador no han cambiado.
+
+    use el comando /help para ver las instrucciones de uso del bot.
+
+## Admin
+
+admin-command-got = Comando de administrador recibido
+admin-sent = Texto enviado a { $subscribers }
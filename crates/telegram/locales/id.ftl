@@ -0,0 +1,304 @@
+## Commands
+
+help-header = Perintah berikut didukung:
+
+command-help = tampilkan teks ini
+command-start = Pesan sambutan
+command-managevalidatorsubscriptions = kelola langganan validator: tambah yang baru, atur yang sudah ada, ubah frekuensi notifikasi, waktu peringatan sebelum kehilangan status validator, atau berhenti berlangganan
+command-managedevsubscriptions = kelola notifikasi dari pengembang. Tetap ikuti pembaruan jaringan yang dapat memengaruhi status validator Anda
+command-status = tampilkan status bio-autentikasi validator Anda saat ini
+command-export = ekspor langganan validator dan pengaturannya sebagai dokumen
+command-import = pulihkan langganan validator dari dokumen yang diekspor
+command-timezone = tampilkan atau atur selisih waktu lokal Anda dari UTC, mis. /timezone +07:00
+command-quiethours = tampilkan atau atur rentang waktu lokal saat notifikasi berulang ditunda, mis. /quiethours 23:00-07:00
+command-language = tampilkan atau atur bahasa bot, mis. /language id
+command-template = tampilkan atau atur teks notifikasi sendiri, mis. /template lost {"{name}"} mati, @oncall
+command-escalation = tampilkan atau atur obrolan cadangan tujuan eskalasi kehilangan yang tidak diakui, mis. /escalation <alamat> -1001234567890 15
+command-resetstate = #debug_command atur ulang status.
+command-cancel = batalkan operasi
+command-subscribe = tambah langganan baru
+command-settings = tampilkan pengaturan langganan ini dengan tombol untuk mengubahnya
+command-updatemaxmessagefrequency = ubah frekuensi pesan maksimum (dalam blok, ~6 detik per blok) untuk langganan ini
+command-updatealertbefore = atur waktu peringatan (dalam menit) sebelum kehilangan status validator, mis. 1440, 60, 10
+command-rename = atur label yang ditampilkan sebagai pengganti alamat langganan ini
+command-unsubscribe = berhenti berlangganan dari langganan ini
+command-adminnotify = beri tahu pelanggan
+
+## Common
+
+start-message =
+    !!! PERHATIAN !!!
+    !!! INI ADALAH VERSI AKSES AWAL !!!
+    !!! BEBERAPA FUNGSI MUNGKIN TIDAK STABIL !!!
+    !!! TERKADANG BOT DAPAT DIATUR ULANG SEPENUHNYA, TETAPI SAYA AKAN MEMBERI TAHU ANDA JIKA ANDA MENGAKTIFKAN NOTIFIKASI PENGEMBANG !!!
+
+    Selamat datang di bot biostatus!
+
+    Dengan bot ini, Anda akan menerima notifikasi tepat waktu tentang akan hilangnya status validator Anda sesuai pengaturan Anda.
+
+    Jika status validator Anda hilang, atau Anda bukan lagi validator, Anda akan diberi tahu secara berkala sesuai pengaturan Anda.
+
+    Semua pesan bot dapat disesuaikan dengan keinginan Anda, sehingga Anda tidak perlu membisukan bot dan tetap mengetahui status Anda untuk segera bertindak.
+
+    Disarankan juga untuk mengaktifkan notifikasi pengembang agar tetap mengetahui perubahan jaringan yang dapat memengaruhi status validator Anda, Anda selalu dapat berhenti berlangganan jika notifikasi tersebut tidak relevan.
+
+    Gunakan perintah /language untuk mengubah bahasa bot.
+
+    Gunakan perintah /help untuk menampilkan petunjuk penggunaan bot.
+help-hint = Gunakan perintah /help untuk menampilkan petunjuk penggunaan bot.
+reset-state = Mengatur ulang status
+status-no-subscriptions = Anda tidak memiliki langganan validator, gunakan perintah /managevalidatorsubscriptions untuk menambahkannya.
+status-no-block = Status bio-autentikasi belum diterima dari jaringan, coba lagi nanti.
+status-header = Status pada blok #{ $block_number }:
+status-active = { $name } aktif, autentikasi berakhir pada { $expires_at }.
+status-not-authenticated = { $name } tidak terautentikasi secara bio.
+
+## Notifications
+
+notification-lost = { $name } telah kehilangan bio-autentikasi untuk menjadi validator aktif pada blok #{ $block_number }.
+notification-soon-expired = { $name } akan segera kehilangan bio-autentikasi, autentikasi berakhir pada { $expires_at }
+notification-restored = { $name } telah memulihkan bio-autentikasi dan kembali menjadi validator aktif. Autentikasi berakhir pada { $expires_at }.
+notification-authenticated = { $name } melakukan autentikasi ulang pada blok #{ $block_number }.
+notification-expired = Autentikasi { $name } berakhir pada blok #{ $block_number }.
+notification-removed = Autentikasi { $name } dihapus karena pelanggaran pada blok #{ $block_number }.
+notification-escalated = { $name } kehilangan bio-autentikasi dan peringatan tidak diakui tepat waktu di obrolan { $from_chat }.
+expires-at-passed = { $time } (sudah lewat)
+expires-at-in = { $time } (dalam { $hours } j { $mins } m)
+alert-button-status = Lihat status
+alert-button-settings = Pengaturan
+alert-button-snooze = Tunda { $hours } j
+alert-button-acknowledge = Dimengerti
+alert-not-lost = { $name } tidak lagi kehilangan autentikasi.
+alert-snoozed = Notifikasi kehilangan { $name } ditunda selama { $hours } j { $mins } m.
+alert-acknowledged = Kehilangan { $name } telah diakui, notifikasi tidak akan diulang sampai dipulihkan.
+alert-not-escalated = Kehilangan { $address } tidak dieskalasi ke obrolan ini.
+alert-escalation-acknowledged = Kehilangan { $name } telah diakui di obrolan cadangan { $chat_id }.
+
+## Validator subscriptions
+
+no-subscriptions-message =
+    Anda belum memiliki langganan,
+
+    gunakan perintah /help untuk menampilkan petunjuk penggunaan bot.
+choose-subscription = Pilih langganan untuk mengelolanya, atau gunakan perintah /help untuk menampilkan petunjuk penggunaan bot.
+subscriptions-unchanged-message =
+    Langganan validator Anda tidak berubah.
+
+    gunakan perintah /help untuk menampilkan petunjuk penggunaan bot.
+action-canceled = Anda telah membatalkan tindakan.
+subscription-selected = Langganan { $address } dipilih
+cancel-message =
+    Anda telah membatalkan tindakan.
+
+    gunakan perintah /help untuk menampilkan petunjuk penggunaan bot.
+cancel-subscriptions-unchanged-message =
+    Anda telah membatalkan tindakan.
+
+    Langganan validator Anda tidak berubah.
+
+    gunakan perintah /help untuk menampilkan petunjuk penggunaan bot.
+
+subscribe-message =
+    Masukkan alamat validator (harus diawali 'hm..'), atau gunakan perintah /help untuk menampilkan petunjuk penggunaan bot.
+
+    Untuk menambahkan banyak validator sekaligus, masukkan alamatnya dipisahkan baris baru, koma, atau spasi.
+subscribe-activated = Tindakan berlangganan diaktifkan
+subscribed-message =
+    Alamat validator berhasil ditambahkan.
+    Anda sekarang akan menerima notifikasi sesuai pengaturan Anda.
+    Anda dapat mengelola pengaturan langganan ini.
+
+    Gunakan perintah /help untuk menampilkan petunjuk penggunaan bot
+already-subscribed-message =
+    Anda sudah berlangganan validator ini, pengaturannya tetap dipertahankan.
+    Anda dapat mengelola pengaturan langganan ini.
+enter-address = Masukkan alamat
+too-many-addresses = Terlalu banyak alamat, masukkan paling banyak { $max } sekaligus.
+invalid-address = alamat tidak valid { $error }
+duplicate-address = duplikat
+already-subscribed = sudah berlangganan
+subscribe-report = Berlangganan { $subscribed } dari { $total } alamat:
+enter-valid-addresses = Masukkan alamat yang valid.
+
+unsubscribe-confirm =
+    Masukkan 'agree' untuk mengonfirmasi berhenti berlangganan dari { $address },
+
+    gunakan perintah /help untuk menampilkan petunjuk penggunaan bot.
+unsubscribed = Berhasil berhenti berlangganan.
+unsubscribe-enter-agree = Silakan masukkan kata 'agree' untuk mengonfirmasi operasi.
+
+max-frequency-current = Frekuensi pesan maksimum saat ini untuk { $address }: { $in_blocks } blok
+max-frequency-message = Masukkan nilai baru frekuensi pesan maksimum dalam blok (setiap blok kira-kira setara dengan 6 detik).
+max-frequency-updated = Frekuensi pesan maksimum diperbarui.
+max-frequency-invalid = Masukkan jumlah blok dari 1 sampai { $max }.
+
+alert-times-current = Waktu peringatan saat ini untuk { $address }: { $in_mins } menit
+alert-times-message =
+    Masukkan waktu peringatan baru dalam menit, dipisahkan koma atau spasi (misalnya: 1440, 360, 60, 10).
+    Setiap peringatan dikirim satu kali per periode autentikasi.
+alert-times-updated = Waktu peringatan diperbarui.
+alert-times-invalid = Masukkan 1 sampai { $max } bilangan menit, masing-masing dari 1 sampai { $max_mins }.
+
+label-current = Label saat ini untuk { $address }: { $label }
+label-not-set = belum diatur
+rename-message =
+    Masukkan label baru validator, label ditampilkan sebagai pengganti alamat di daftar langganan dan notifikasi.
+
+    Masukkan - untuk menghapus label.
+label-empty = Label tidak boleh kosong.
+label-too-long = Label tidak boleh lebih dari { $max } karakter.
+label-updated = Label diperbarui.
+enter-new-label = Masukkan label baru.
+
+## Settings card
+
+settings-card =
+    Pengaturan { $name }
+
+    Frekuensi pesan maksimum: { $in_blocks } blok (~{ $duration })
+    Waktu peringatan: { $alert_times } menit
+
+    Gunakan tombol untuk mengubahnya.
+settings-card-subtract-blocks = − { $in_blocks } blok
+settings-card-add-blocks = + { $in_blocks } blok
+settings-card-not-subscribed = Anda tidak berlangganan { $address }.
+settings-card-alert-required = Diperlukan setidaknya satu waktu peringatan.
+settings-card-too-many-alerts = Terlalu banyak waktu peringatan.
+duration-secs = { $secs } dtk
+duration-mins = { $mins } mnt
+duration-hours = { $hours } j
+
+## Export and import
+
+export-empty = Anda tidak memiliki langganan validator untuk diekspor.
+export-caption = Kirim dokumen ini setelah perintah /import untuk memulihkan langganan.
+import-message =
+    Kirim dokumen yang diekspor dengan perintah /export.
+
+    Langganan dari dokumen ditambahkan ke obrolan ini, pengaturan langganan yang sudah ada diganti.
+import-send-document = Kirim dokumen yang diekspor, atau gunakan perintah /cancel untuk membatalkan impor.
+import-too-large = Dokumen terlalu besar.
+import-invalid-document = Dokumen tidak valid: { $error }
+import-too-many = Terlalu banyak langganan, impor paling banyak { $max } sekaligus.
+import-invalid-alert-times = waktu peringatan tidak valid
+import-invalid-max-frequency = frekuensi pesan maksimum tidak valid
+import-settings-restored = sudah berlangganan, pengaturan dipulihkan
+import-report = Diimpor { $imported } dari { $total } langganan:
+import-cancel-message =
+    Anda telah membatalkan impor.
+
+    Langganan validator Anda tidak berubah.
+
+    gunakan perintah /help untuk menampilkan petunjuk penggunaan bot.
+
+## Chat settings
+
+timezone-message =
+    Zona waktu saat ini: { $timezone }
+
+    Kirim /timezone dengan selisih waktu lokal Anda dari UTC untuk mengubahnya, misalnya: /timezone +07:00 atau /timezone -5.
+timezone-invalid = Masukkan selisih dari UTC antara -12:00 dan +14:00, misalnya: /timezone +07:00
+timezone-updated = Zona waktu diperbarui: { $timezone }
+quiet-hours-message =
+    Jam tenang saat ini: { $hours } ({ $timezone })
+
+    Selama jam tenang, notifikasi kehilangan status yang berulang dan peringatan kedaluwarsa awal ditunda hingga jam tenang berakhir. Notifikasi kehilangan status pertama dan peringatan kedaluwarsa terakhir selalu dikirim.
+
+    Kirim /quiethours dengan rentang waktu lokal untuk mengubahnya, misalnya: /quiethours 23:00-07:00, atau /quiethours off untuk menonaktifkannya.
+quiet-hours-invalid = Masukkan rentang waktu lokal, misalnya: /quiethours 23:00-07:00
+quiet-hours-updated = Jam tenang diperbarui: { $hours } ({ $timezone })
+
+language-auto = { $language } (dari pengaturan Telegram Anda)
+language-message =
+    Bahasa saat ini: { $current }
+
+    Kirim /language dengan kode bahasa untuk mengubahnya, atau /language auto untuk mendeteksinya dari pengaturan Telegram Anda:
+    { $languages }
+language-invalid =
+    Masukkan salah satu bahasa yang didukung:
+    { $languages }
+language-updated = Bahasa diperbarui: { $language }
+
+## Notification templates
+
+template-message =
+    Teks notifikasi sendiri:
+    { $templates }
+
+    Kirim /template dengan jenis notifikasi dan teksnya untuk mengubahnya, misalnya:
+    /template lost {"{name}"} mati, @oncall lihat https://wiki.example.com/runbook
+
+    Kirim /template dengan jenisnya untuk menampilkan teksnya, atau dengan jenis dan reset untuk memulihkan teks bawaan, mis. /template lost reset.
+    Tulis {"{{"} dan {"}}"} untuk kurung kurawal itu sendiri.
+
+    Jenis notifikasi dan placeholder-nya:
+    { $kinds }
+template-none = tidak ada, teks bawaan digunakan
+template-default = teks bawaan
+template-invalid-kind =
+    Masukkan salah satu jenis notifikasi:
+    { $kinds }
+template-current =
+    Teks notifikasi { $kind }: { $template }
+
+    Placeholder: { $placeholders }
+template-reset = Teks bawaan notifikasi { $kind } dipulihkan.
+template-empty = Teks tidak boleh kosong.
+template-too-long = Teks tidak boleh lebih dari { $max } karakter.
+template-unclosed = Placeholder tidak ditutup, tulis {"{{"} dan {"}}"} untuk kurung kurawal itu sendiri.
+template-unknown-placeholder =
+    Placeholder {"{"}{ $placeholder }{"}"} tidak tersedia untuk jenis notifikasi ini.
+    Placeholder: { $placeholders }
+template-updated =
+    Teks notifikasi { $kind } diperbarui, pratinjau:
+
+    { $preview }
+
+## Escalation
+
+escalation-message =
+    Obrolan cadangan tujuan eskalasi kehilangan jika tidak diakui tepat waktu:
+    { $subscriptions }
+
+    Kirim /escalation dengan alamat, id obrolan cadangan, dan jeda dalam menit sejak kehilangan untuk mengubahnya, obrolan tingkat ketiga dapat menyusul, misalnya:
+    /escalation <alamat> -1001234567890 15 -1009876543210 60
+
+    Kirim /escalation dengan alamat untuk menampilkan obrolan cadangannya, atau dengan alamat dan off untuk menonaktifkan eskalasi.
+    Bot harus ditambahkan ke obrolan cadangan.
+escalation-none = tanpa eskalasi
+escalation-level = { $level }. { $chat_id } setelah { $mins } menit
+escalation-current =
+    Obrolan cadangan { $name }:
+    { $levels }
+escalation-invalid = Masukkan alamat diikuti hingga { $max } pasang id obrolan cadangan dan jeda dalam menit, mis. /escalation <alamat> -1001234567890 15
+escalation-own-chat = Kehilangan tidak dapat dieskalasi ke obrolan ini sendiri.
+escalation-not-ascending = Jeda harus dari 1 sampai { $max_mins } menit dan bertambah di setiap tingkat.
+escalation-unreachable-chat = Bot tidak dapat menjangkau obrolan { $chat_id }, tambahkan bot ke obrolan itu terlebih dahulu.
+escalation-not-member = Hanya anggota obrolan { $chat_id } yang dapat mengeskalasi ke obrolan itu.
+escalation-updated =
+    Obrolan cadangan { $name } diperbarui:
+    { $levels }
+
+## Developer subscriptions
+
+enable-affected-validator = Aktifkan notifikasi validator yang terdampak
+disable-affected-validator = Nonaktifkan notifikasi validator yang terdampak
+dev-subscription-enabled =
+    Langganan berhasil diaktifkan
+
+    gunakan perintah /help untuk menampilkan petunjuk penggunaan bot.
+dev-subscription-disabled =
+    Langganan berhasil dinonaktifkan
+
+    gunakan perintah /help untuk menampilkan petunjuk penggunaan bot.
+dev-subscriptions-cancel-message =
+    Anda telah membatalkan tindakan.
+
+    Langganan notifikasi pengembang Anda tidak berubah.
+
+    gunakan perintah /help untuk menampilkan petunjuk penggunaan bot.
+
+## Admin
+
+admin-command-got = Perintah admin diterima
+admin-sent = Teks ini dikirim ke { $subscribers }
@@ -0,0 +1,304 @@
+## Commands
+
+help-header = Поддерживаются следующие команды:
+
+command-help = показать этот текст
+command-start = Приветственное сообщение
+command-managevalidatorsubscriptions = управление подписками на валидаторов: добавление новых, настройка существующих, частота уведомлений, время предупреждения до потери статуса валидатора или отписка
+command-managedevsubscriptions = управление уведомлениями от разработчика. Будьте в курсе обновлений сети, которые могут повлиять на статус вашего валидатора
+command-status = показать текущий статус биоаутентификации ваших валидаторов
+command-export = экспортировать подписки на валидаторов и их настройки в документ
+command-import = восстановить подписки на валидаторов из экспортированного документа
+command-timezone = показать или задать смещение вашего местного времени от UTC, например /timezone +03:00
+command-quiethours = показать или задать диапазон местного времени, в который повторные уведомления откладываются, например /quiethours 23:00-07:00
+command-language = показать или задать язык бота, например /language ru
+command-template = показать или задать свои тексты уведомлений, например /template lost {"{name}"} недоступен, @oncall
+command-escalation = показать или задать резервные чаты, куда передаётся непринятая потеря, например /escalation <адрес> -1001234567890 15
+command-resetstate = #debug_command сбросить состояние.
+command-cancel = отменить операцию
+command-subscribe = добавить новую подписку
+command-settings = показать настройки этой подписки с кнопками для их изменения
+command-updatemaxmessagefrequency = изменить максимальную частоту сообщений (в блоках, ~6 секунд на блок) для этой подписки
+command-updatealertbefore = изменить время предупреждений (в минутах) до потери статуса валидатора, например 1440, 60, 10
+command-rename = задать метку, которая показывается вместо адреса этой подписки
+command-unsubscribe = отписаться от этой подписки
+command-adminnotify = уведомить подписчиков
+
+## Common
+
+start-message =
+    !!! ОБРАТИТЕ ВНИМАНИЕ !!!
+    !!! ЭТО ВЕРСИЯ РАННЕГО ДОСТУПА !!!
+    !!! НЕКОТОРЫЕ ФУНКЦИИ МОГУТ РАБОТАТЬ НЕСТАБИЛЬНО !!!
+    !!! ИНОГДА БОТ МОЖЕТ БЫТЬ ПОЛНОСТЬЮ СБРОШЕН, НО Я СООБЩУ ВАМ ОБ ЭТОМ, ЕСЛИ ВЫ ВКЛЮЧИТЕ УВЕДОМЛЕНИЯ ОТ РАЗРАБОТЧИКА !!!
+
+    Добро пожаловать в бот biostatus!
+
+    С этим ботом вы будете своевременно получать уведомления о приближающейся потере статуса валидатора в соответствии с вашими настройками.
+
+    Если статус валидатора потерян или вы больше не валидатор, вы будете регулярно получать уведомления в соответствии с вашими настройками.
+
+    Все сообщения бота настраиваются по вашему вкусу, поэтому вам не придётся отключать звук бота, и вы всегда будете знать свой статус и сможете сразу принять меры.
+
+    Также рекомендуется включить уведомления от разработчика, чтобы узнавать об изменениях сети, которые могут повлиять на статус вашего валидатора. Вы всегда можете отписаться от них, если они окажутся неактуальными.
+
+    Используйте команду /language, чтобы изменить язык бота.
+
+    Используйте команду /help, чтобы получить инструкцию по использованию бота.
+help-hint = Используйте команду /help, чтобы получить инструкцию по использованию бота.
+reset-state = Сброс состояния
+status-no-subscriptions = У вас нет подписок на валидаторов, используйте команду /managevalidatorsubscriptions, чтобы добавить подписку.
+status-no-block = Статус биоаутентификации ещё не получен из сети, попробуйте позже.
+status-header = Статус на блоке #{ $block_number }:
+status-active = { $name } активен, аутентификация истекает { $expires_at }.
+status-not-authenticated = { $name } не прошёл биоаутентификацию.
+
+## Notifications
+
+notification-lost = { $name } потерял биоаутентификацию и больше не является активным валидатором с блока #{ $block_number }.
+notification-soon-expired = { $name } скоро потеряет биоаутентификацию, аутентификация истекает { $expires_at }
+notification-restored = { $name } восстановил биоаутентификацию и снова является активным валидатором. Аутентификация истекает { $expires_at }.
+notification-authenticated = { $name } повторно прошёл аутентификацию на блоке #{ $block_number }.
+notification-expired = Аутентификация { $name } истекла на блоке #{ $block_number }.
+notification-removed = Аутентификация { $name } удалена из-за нарушения на блоке #{ $block_number }.
+notification-escalated = { $name } потерял биоаутентификацию, и оповещение не было принято вовремя в чате { $from_chat }.
+expires-at-passed = { $time } (уже прошло)
+expires-at-in = { $time } (через { $hours } ч { $mins } мин)
+alert-button-status = Показать статус
+alert-button-settings = Настройки
+alert-button-snooze = Отложить на { $hours } ч
+alert-button-acknowledge = Принято
+alert-not-lost = { $name } больше не потерял статус.
+alert-snoozed = Уведомления о потере { $name } отложены на { $hours } ч { $mins } мин.
+alert-acknowledged = Потеря { $name } принята к сведению, уведомления не будут повторяться до восстановления.
+alert-not-escalated = Потеря { $address } не передаётся в этот чат.
+alert-escalation-acknowledged = Потеря { $name } принята к сведению в резервном чате { $chat_id }.
+
+## Validator subscriptions
+
+no-subscriptions-message =
+    У вас ещё нет подписок,
+
+    используйте команду /help, чтобы получить инструкцию по использованию бота.
+choose-subscription = Выберите подписку для управления или используйте команду /help, чтобы получить инструкцию по использованию бота.
+subscriptions-unchanged-message =
+    Ваши подписки на валидаторов не изменились.
+
+    используйте команду /help, чтобы получить инструкцию по использованию бота.
+action-canceled = Вы отменили действие.
+subscription-selected = Выбрана подписка { $address }
+cancel-message =
+    Вы отменили действие.
+
+    используйте команду /help, чтобы получить инструкцию по использованию бота.
+cancel-subscriptions-unchanged-message =
+    Вы отменили действие.
+
+    Ваши подписки на валидаторов не изменились.
+
+    используйте команду /help, чтобы получить инструкцию по использованию бота.
+
+subscribe-message =
+    Введите адрес валидатора (должен начинаться с 'hm..') или используйте команду /help, чтобы получить инструкцию по использованию бота.
+
+    Чтобы добавить сразу несколько валидаторов, введите их адреса через перевод строки, запятую или пробел.
+subscribe-activated = Добавление подписки активировано
+subscribed-message =
+    Адрес валидатора успешно добавлен.
+    Теперь вы будете получать уведомления в соответствии с вашими настройками.
+    Вы можете изменить настройки этой подписки.
+
+    Используйте команду /help, чтобы получить инструкцию по использованию бота
+already-subscribed-message =
+    Вы уже подписаны на этого валидатора, его настройки сохранены.
+    Вы можете изменить настройки этой подписки.
+enter-address = Введите адрес
+too-many-addresses = Слишком много адресов, вводите не больше { $max } за раз.
+invalid-address = неверный адрес { $error }
+duplicate-address = уже добавлен
+already-subscribed = подписка уже есть
+subscribe-report = Добавлено подписок: { $subscribed } из { $total }:
+enter-valid-addresses = Введите правильные адреса.
+
+unsubscribe-confirm =
+    Введите 'agree', чтобы подтвердить отписку от { $address },
+
+    используйте команду /help, чтобы получить инструкцию по использованию бота.
+unsubscribed = Вы успешно отписались.
+unsubscribe-enter-agree = Пожалуйста, введите слово 'agree', чтобы подтвердить операцию.
+
+max-frequency-current = Текущая максимальная частота сообщений для { $address }: { $in_blocks } блоков
+max-frequency-message = Введите новое значение максимальной частоты сообщений в блоках (каждый блок примерно равен 6 секундам).
+max-frequency-updated = Максимальная частота сообщений изменена.
+max-frequency-invalid = Введите число блоков от 1 до { $max }.
+
+alert-times-current = Текущее время предупреждений для { $address }: { $in_mins } мин
+alert-times-message =
+    Введите новое время предупреждений в минутах через запятую или пробел (например: 1440, 360, 60, 10).
+    Каждое предупреждение отправляется один раз за период аутентификации.
+alert-times-updated = Время предупреждений изменено.
+alert-times-invalid = Введите от 1 до { $max } чисел минут, каждое от 1 до { $max_mins }.
+
+label-current = Текущая метка для { $address }: { $label }
+label-not-set = не задана
+rename-message =
+    Введите новую метку валидатора, она показывается вместо адреса в списке подписок и в уведомлениях.
+
+    Введите - чтобы удалить метку.
+label-empty = Метка не может быть пустой.
+label-too-long = Метка не может быть длиннее { $max } символов.
+label-updated = Метка изменена.
+enter-new-label = Введите новую метку.
+
+## Settings card
+
+settings-card =
+    Настройки { $name }
+
+    Максимальная частота сообщений: { $in_blocks } блоков (~{ $duration })
+    Время предупреждений: { $alert_times } мин
+
+    Используйте кнопки, чтобы изменить их.
+settings-card-subtract-blocks = − { $in_blocks } блоков
+settings-card-add-blocks = + { $in_blocks } блоков
+settings-card-not-subscribed = Вы не подписаны на { $address }.
+settings-card-alert-required = Нужно хотя бы одно время предупреждения.
+settings-card-too-many-alerts = Слишком много предупреждений.
+duration-secs = { $secs } сек
+duration-mins = { $mins } мин
+duration-hours = { $hours } ч
+
+## Export and import
+
+export-empty = У вас нет подписок на валидаторов для экспорта.
+export-caption = Отправьте этот документ после команды /import, чтобы восстановить подписки.
+import-message =
+    Отправьте документ, экспортированный командой /export.
+
+    Подписки из документа будут добавлены в этот чат, настройки существующих будут заменены.
+import-send-document = Отправьте экспортированный документ или используйте команду /cancel, чтобы отменить импорт.
+import-too-large = Документ слишком большой.
+import-invalid-document = Документ некорректен: { $error }
+import-too-many = Слишком много подписок, импортируйте не больше { $max } за раз.
+import-invalid-alert-times = неверное время предупреждений
+import-invalid-max-frequency = неверная максимальная частота сообщений
+import-settings-restored = подписка уже есть, настройки восстановлены
+import-report = Импортировано подписок: { $imported } из { $total }:
+import-cancel-message =
+    Вы отменили импорт.
+
+    Ваши подписки на валидаторов не изменились.
+
+    используйте команду /help, чтобы получить инструкцию по использованию бота.
+
+## Chat settings
+
+timezone-message =
+    Текущий часовой пояс: { $timezone }
+
+    Отправьте /timezone со смещением вашего местного времени от UTC, чтобы изменить его, например: /timezone +03:00 или /timezone -5.
+timezone-invalid = Введите смещение от UTC от -12:00 до +14:00, например: /timezone +03:00
+timezone-updated = Часовой пояс изменён: { $timezone }
+quiet-hours-message =
+    Текущие тихие часы: { $hours } ({ $timezone })
+
+    В тихие часы повторные уведомления о потере статуса и ранние предупреждения об истечении откладываются до их окончания. Первое уведомление о потере статуса и последнее предупреждение об истечении отправляются всегда.
+
+    Отправьте /quiethours с диапазоном местного времени, чтобы изменить их, например: /quiethours 23:00-07:00, или /quiethours off, чтобы их отключить.
+quiet-hours-invalid = Введите диапазон местного времени, например: /quiethours 23:00-07:00
+quiet-hours-updated = Тихие часы изменены: { $hours } ({ $timezone })
+
+language-auto = { $language } (по настройкам Telegram)
+language-message =
+    Текущий язык: { $current }
+
+    Отправьте /language с кодом языка, чтобы изменить его, или /language auto, чтобы определять его по настройкам Telegram:
+    { $languages }
+language-invalid =
+    Введите один из поддерживаемых языков:
+    { $languages }
+language-updated = Язык изменён: { $language }
+
+## Notification templates
+
+template-message =
+    Свои тексты уведомлений:
+    { $templates }
+
+    Отправьте /template с видом уведомления и текстом, чтобы изменить его, например:
+    /template lost {"{name}"} недоступен, @oncall см. https://wiki.example.com/runbook
+
+    Отправьте /template с видом уведомления, чтобы показать его текст, или с видом и reset, чтобы вернуть текст по умолчанию, например /template lost reset.
+    Пишите {"{{"} и {"}}"} для самих фигурных скобок.
+
+    Виды уведомлений и их подстановки:
+    { $kinds }
+template-none = нет, используются тексты по умолчанию
+template-default = текст по умолчанию
+template-invalid-kind =
+    Введите один из видов уведомлений:
+    { $kinds }
+template-current =
+    Текст уведомлений { $kind }: { $template }
+
+    Подстановки: { $placeholders }
+template-reset = Текст уведомлений { $kind } по умолчанию восстановлен.
+template-empty = Текст не может быть пустым.
+template-too-long = Текст не может быть длиннее { $max } символов.
+template-unclosed = Подстановка не закрыта, пишите {"{{"} и {"}}"} для самих фигурных скобок.
+template-unknown-placeholder =
+    Подстановка {"{"}{ $placeholder }{"}"} недоступна для этого вида уведомлений.
+    Подстановки: { $placeholders }
+template-updated =
+    Текст уведомлений { $kind } изменён, предпросмотр:
+
+    { $preview }
+
+## Escalation
+
+escalation-message =
+    Резервные чаты, куда передаётся потеря, если она не принята вовремя:
+    { $subscriptions }
+
+    Отправьте /escalation с адресом, id резервного чата и задержкой в минутах с момента потери, чтобы изменить их, за ним может следовать чат третьего уровня, например:
+    /escalation <адрес> -1001234567890 15 -1009876543210 60
+
+    Отправьте /escalation с адресом, чтобы показать его резервные чаты, или с адресом и off, чтобы отключить передачу.
+    Бот должен быть добавлен в резервные чаты.
+escalation-none = передача отключена
+escalation-level = { $level }. { $chat_id } через { $mins } мин
+escalation-current =
+    Резервные чаты { $name }:
+    { $levels }
+escalation-invalid = Введите адрес и до { $max } пар из id резервного чата и задержки в минутах, например /escalation <адрес> -1001234567890 15
+escalation-own-chat = Потерю нельзя передать в этот же чат.
+escalation-not-ascending = Задержки должны быть от 1 до { $max_mins } минут и увеличиваться с каждым уровнем.
+escalation-unreachable-chat = Бот не может писать в чат { $chat_id }, сначала добавьте бота в него.
+escalation-not-member = Только участники чата { $chat_id } могут настроить передачу в него.
+escalation-updated =
+    Резервные чаты { $name } обновлены:
+    { $levels }
+
+## Developer subscriptions
+
+enable-affected-validator = Включить уведомления о затронутых валидаторах
+disable-affected-validator = Отключить уведомления о затронутых валидаторах
+dev-subscription-enabled =
+    Подписка успешно включена
+
+    используйте команду /help, чтобы получить инструкцию по использованию бота.
+dev-subscription-disabled =
+    Подписка успешно отключена
+
+    используйте команду /help, чтобы получить инструкцию по использованию бота.
+dev-subscriptions-cancel-message =
+    Вы отменили действие.
+
+    Ваши подписки на уведомления от разработчика не изменились.
+
+    используйте команду /help, чтобы получить инструкцию по использованию бота.
+
+## Admin
+
+admin-command-got = Команда администратора получена
+admin-sent = Текст отправлен: { $subscribers }
@@ -1,67 +1,488 @@
-use crate::Notification;
-use bioauth_logic::FailedNotification;
+use std::sync::Arc;
+
+use crate::{
+    handlers::alert_actions,
+    i18n::{tr, Language},
+    outbound::{MessageOptions, OutboundHandle, Priority},
+    templates::{self, TemplateValues},
+    Notification, NotificationRequest, SubscriptionUpdate,
+};
+use bioauth_logic::{DeliveryError, FailedNotification};
 use sp_core::crypto::{Ss58AddressFormatRegistry, Ss58Codec};
-use teloxide::{prelude::*, types::ChatId, Bot};
+use teloxide::{
+    requests::Requester,
+    types::{ChatId, ParseMode},
+    utils::html,
+    ApiError, Bot, RequestError,
+};
 
 #[derive(Debug)]
 pub struct SendNotificationError {
-    pub notification: FailedNotification,
+    pub notification: FailedNotification<[u8; 32]>,
+}
+
+/// Classify the Telegram error by whether resending the notification can succeed.
+fn delivery_error(error: &RequestError) -> DeliveryError {
+    match error {
+        RequestError::Api(
+            ApiError::BotBlocked
+            | ApiError::BotKicked
+            | ApiError::BotKickedFromSupergroup
+            | ApiError::ChatNotFound
+            | ApiError::UserNotFound
+            | ApiError::UserDeactivated
+            | ApiError::GroupDeactivated
+            | ApiError::CantInitiateConversation
+            | ApiError::CantTalkWithBots
+            | ApiError::NotEnoughRightsToPostMessages,
+        )
+        | RequestError::MigrateToChatId(_) => DeliveryError::Permanent,
+        _ => DeliveryError::Retryable,
+    }
+}
+
+/// Whether the chat is gone for the bot, so its subscriptions are useless.
+fn is_chat_unreachable(error: &RequestError) -> bool {
+    matches!(
+        error,
+        RequestError::Api(
+            ApiError::BotBlocked
+                | ApiError::BotKicked
+                | ApiError::BotKickedFromSupergroup
+                | ApiError::ChatNotFound
+                | ApiError::UserDeactivated
+                | ApiError::GroupDeactivated
+        )
+    )
+}
+
+async fn remove_unreachable_chat(
+    subscription_update_tx: &tokio::sync::mpsc::Sender<SubscriptionUpdate>,
+    chat_id: i64,
+    error: &RequestError,
+) {
+    tracing::warn!(
+        target: "audit",
+        message = "removing all subscriptions of the unreachable chat",
+        ?chat_id,
+        %error
+    );
+
+    for subscription_update in [
+        SubscriptionUpdate::RemoveAllValidatorSubscriptions { chat_id },
+        SubscriptionUpdate::RemoveDevSubscriptions { chat_id },
+    ] {
+        if let Err(error) = subscription_update_tx.send(subscription_update).await {
+            tracing::error!(message = "subscription update send error", ?error);
+        }
+    }
+}
+
+pub(crate) async fn migrate_chat(
+    subscription_update_tx: &tokio::sync::mpsc::Sender<SubscriptionUpdate>,
+    from_chat_id: i64,
+    to_chat_id: i64,
+) {
+    tracing::info!(
+        target: "audit",
+        message = "migrating the chat subscriptions",
+        ?from_chat_id,
+        ?to_chat_id
+    );
+
+    if let Err(error) = subscription_update_tx
+        .send(SubscriptionUpdate::MigrateChat {
+            from_chat_id,
+            to_chat_id,
+        })
+        .await
+    {
+        tracing::error!(message = "subscription update send error", ?error);
+    }
+}
+
+/// The validator label along with its address, or just the address if it has no label.
+pub(crate) fn display_name(address: &str, label: Option<&str>) -> String {
+    match label {
+        Some(label) => format!("{label} ({address})"),
+        None => address.to_owned(),
+    }
+}
+
+/// The HTML title of the chat linked to it if the chat is public, or its id if the chat
+/// is not available.
+async fn chat_title(bot: &Bot, chat_id: i64) -> String {
+    let chat = match bot.get_chat(ChatId(chat_id)).await {
+        Ok(chat) => chat,
+        Err(error) => {
+            tracing::warn!(message = "chat title request error", ?chat_id, %error);
+            return chat_id.to_string();
+        }
+    };
+
+    let title = chat
+        .title()
+        .or_else(|| chat.first_name())
+        .map_or_else(|| chat_id.to_string(), ToOwned::to_owned);
+    match chat.username() {
+        Some(username) => html::link(&format!("https://t.me/{username}"), &title),
+        None => html::escape(&title),
+    }
+}
+
+pub(crate) fn to_ss58(bioauth_public_key: [u8; 32]) -> String {
+    sp_core::crypto::AccountId32::new(bioauth_public_key)
+        .to_ss58check_with_version(Ss58AddressFormatRegistry::HumanodeAccount.into())
+}
+
+/// The block explorer address page URL with the `{address}` placeholder.
+#[derive(Debug, Clone)]
+pub struct ExplorerUrl(pub String);
+
+impl ExplorerUrl {
+    /// The explorer page of the address.
+    pub(crate) fn address_url(&self, address: &str) -> String {
+        self.0.replace("{address}", address)
+    }
+}
+
+pub(crate) fn format_timezone_offset(offset_in_mins: i32) -> String {
+    let sign = if offset_in_mins < 0 { '-' } else { '+' };
+    let offset_in_mins = offset_in_mins.unsigned_abs();
+    format!(
+        "UTC{sign}{:02}:{:02}",
+        offset_in_mins / 60,
+        offset_in_mins % 60
+    )
+}
+
+/// The chain moment in milliseconds as the local time of the chat along with the time left
+/// until it.
+pub(crate) fn local_time_and_time_left(
+    expires_at: u64,
+    timezone_offset_in_mins: i32,
+) -> Option<(String, chrono::TimeDelta)> {
+    let expires_at = i64::try_from(expires_at)
+        .ok()
+        .and_then(chrono::DateTime::from_timestamp_millis)?;
+    let local_time = expires_at + chrono::TimeDelta::minutes(timezone_offset_in_mins.into());
+
+    Some((
+        format!(
+            "{} {}",
+            local_time.format("%Y-%m-%d %H:%M"),
+            format_timezone_offset(timezone_offset_in_mins)
+        ),
+        expires_at - chrono::Utc::now(),
+    ))
+}
+
+/// The time left in hours and minutes, zero if it is already passed.
+pub(crate) fn format_time_left(time_left: chrono::TimeDelta) -> String {
+    let time_left = time_left.max(chrono::TimeDelta::zero());
+    format!(
+        "{}h {}m",
+        time_left.num_hours(),
+        time_left.num_minutes() % 60
+    )
+}
+
+/// Format the chain moment in milliseconds in the chat local time along with the time left
+/// until it.
+pub(crate) fn format_expires_at(
+    expires_at: u64,
+    timezone_offset_in_mins: i32,
+    language: Language,
+) -> String {
+    let Some((time, time_left)) = local_time_and_time_left(expires_at, timezone_offset_in_mins)
+    else {
+        return expires_at.to_string();
+    };
+
+    if time_left <= chrono::TimeDelta::zero() {
+        return tr!(language, "expires-at-passed", time = time);
+    }
+
+    tr!(
+        language,
+        "expires-at-in",
+        time = time,
+        hours = time_left.num_hours(),
+        mins = time_left.num_minutes() % 60
+    )
 }
 
 #[derive(Debug)]
 pub struct RunLoopParams {
     pub bot: Bot,
-    pub notification_handle_rx: tokio::sync::mpsc::Receiver<Notification>,
+    pub outbound: OutboundHandle,
+    pub bioauth_settings: Arc<crate::BioauthSettings>,
+    pub explorer_url: Arc<ExplorerUrl>,
+    pub notification_handle_rx: tokio::sync::mpsc::Receiver<NotificationRequest>,
+    pub subscription_update_tx: tokio::sync::mpsc::Sender<SubscriptionUpdate>,
 }
 pub async fn run_loop(params: RunLoopParams) -> Result<(), SendNotificationError> {
     let RunLoopParams {
         mut notification_handle_rx,
         bot,
+        outbound,
+        bioauth_settings,
+        explorer_url,
+        subscription_update_tx,
     } = params;
-    loop {
-        let notification = notification_handle_rx.recv().await;
-        if notification.is_some() {
-            tracing::info!(message = "run_loop: Got new notification", ?notification);
+    while let Some(request) = notification_handle_rx.recv().await {
+        tracing::info!(message = "run_loop: Got new notification", notification = ?request.notification);
+
+        // The requests are handled concurrently for the outbound queue to order them by priority.
+        let bot = bot.clone();
+        let outbound = outbound.clone();
+        let bioauth_settings = Arc::clone(&bioauth_settings);
+        let explorer_url = Arc::clone(&explorer_url);
+        let subscription_update_tx = subscription_update_tx.clone();
+        tokio::spawn(async move {
+            handle_request(
+                request,
+                &bot,
+                &outbound,
+                &bioauth_settings,
+                &explorer_url,
+                &subscription_update_tx,
+            )
+            .await;
+        });
+    }
+
+    Ok(())
+}
+
+fn priority(notification: &Notification) -> Priority {
+    match notification {
+        Notification::BioauthLostNotification { .. }
+        | Notification::BioauthSoonExpiredAlert { .. }
+        | Notification::BioauthLossEscalated { .. } => Priority::Alert,
+        Notification::BioauthRestored { .. }
+        | Notification::BioauthAuthenticatedNotification { .. }
+        | Notification::BioauthExpiredNotification { .. }
+        | Notification::BioauthRemovedNotification { .. } => Priority::Notification,
+    }
+}
+
+fn notification_chat_id(notification: &Notification) -> i64 {
+    match *notification {
+        Notification::BioauthLostNotification { chat_id, .. }
+        | Notification::BioauthSoonExpiredAlert { chat_id, .. }
+        | Notification::BioauthRestored { chat_id, .. }
+        | Notification::BioauthAuthenticatedNotification { chat_id, .. }
+        | Notification::BioauthExpiredNotification { chat_id, .. }
+        | Notification::BioauthRemovedNotification { chat_id, .. }
+        | Notification::BioauthLossEscalated { chat_id, .. } => chat_id,
+    }
+}
+
+/// The placeholder values of the custom notification text.
+async fn template_values(
+    notification: &Notification,
+    bioauth_settings: &crate::BioauthSettings,
+    explorer_url: &ExplorerUrl,
+    timezone_offset_in_mins: i32,
+) -> TemplateValues {
+    let (chat_id, bioauth_public_key, block_number, expires_at) = match *notification {
+        Notification::BioauthSoonExpiredAlert {
+            chat_id,
+            bioauth_public_key,
+            expires_at,
         }
-        if let Some(notification) = notification {
-            let res = match notification {
-                Notification::BioauthLostNotification {
-                    chat_id,
-                    bioauth_public_key,
-                } => {
-                    let bioauth_public_key_string =
-                        sp_core::crypto::AccountId32::new(bioauth_public_key)
-                            .to_ss58check_with_version(
-                                Ss58AddressFormatRegistry::HumanodeAccount.into(),
-                            );
-
-                    bot.send_message(
-                        ChatId(chat_id),
-                        format!("{bioauth_public_key_string} have lost bio-authentication to be an active validator."),
-                    )
-                    .await
-                }
-                Notification::BioauthSoonExpiredAlert {
-                    chat_id,
-                    bioauth_public_key,
-                } => {
-                    let bioauth_public_key_string =
-                        sp_core::crypto::AccountId32::new(bioauth_public_key)
-                            .to_ss58check_with_version(
-                                Ss58AddressFormatRegistry::HumanodeAccount.into(),
-                            );
-
-                    bot.send_message(
-                        ChatId(chat_id),
-                        format!("{bioauth_public_key_string} will lost bio-authentication soon"),
-                    )
-                    .await
+        | Notification::BioauthRestored {
+            chat_id,
+            bioauth_public_key,
+            expires_at,
+        } => (chat_id, bioauth_public_key, None, Some(expires_at)),
+        Notification::BioauthLostNotification {
+            chat_id,
+            bioauth_public_key,
+            block_number,
+        }
+        | Notification::BioauthAuthenticatedNotification {
+            chat_id,
+            bioauth_public_key,
+            block_number,
+        }
+        | Notification::BioauthExpiredNotification {
+            chat_id,
+            bioauth_public_key,
+            block_number,
+        }
+        | Notification::BioauthRemovedNotification {
+            chat_id,
+            bioauth_public_key,
+            block_number,
+        } => (chat_id, bioauth_public_key, Some(block_number), None),
+        // The label is the one of the subscription the loss is escalated from.
+        Notification::BioauthLossEscalated {
+            bioauth_public_key,
+            from_chat_id,
+            ..
+        } => (from_chat_id, bioauth_public_key, None, None),
+    };
+
+    let address = to_ss58(bioauth_public_key);
+    let label = bioauth_settings
+        .get(&(chat_id, bioauth_public_key))
+        .await
+        .label;
+    let expires_at = expires_at
+        .and_then(|expires_at| local_time_and_time_left(expires_at, timezone_offset_in_mins));
+
+    TemplateValues {
+        name: display_name(&address, label.as_deref()),
+        explorer_url: explorer_url.address_url(&address),
+        address,
+        label,
+        block_number,
+        expires_at: expires_at.as_ref().map(|(time, _)| time.clone()),
+        time_left: expires_at.map(|(_, time_left)| format_time_left(time_left)),
+    }
+}
+
+/// The catalog HTML text of the notification for the chats without the custom one, the name
+/// links to the address on the block explorer.
+///
+/// The escalated loss mentions the HTML title of the chat it is escalated from.
+fn default_text(
+    notification: &Notification,
+    values: &TemplateValues,
+    from_chat: Option<&str>,
+    timezone_offset_in_mins: i32,
+    language: Language,
+) -> String {
+    let name = html::link(&values.explorer_url, &values.name);
+    match *notification {
+        Notification::BioauthLostNotification { block_number, .. } => tr!(
+            language,
+            "notification-lost",
+            name = name,
+            block_number = block_number
+        ),
+        Notification::BioauthSoonExpiredAlert { expires_at, .. } => tr!(
+            language,
+            "notification-soon-expired",
+            name = name,
+            expires_at = format_expires_at(expires_at, timezone_offset_in_mins, language)
+        ),
+        Notification::BioauthRestored { expires_at, .. } => tr!(
+            language,
+            "notification-restored",
+            name = name,
+            expires_at = format_expires_at(expires_at, timezone_offset_in_mins, language)
+        ),
+        Notification::BioauthAuthenticatedNotification { block_number, .. } => tr!(
+            language,
+            "notification-authenticated",
+            name = name,
+            block_number = block_number
+        ),
+        Notification::BioauthExpiredNotification { block_number, .. } => tr!(
+            language,
+            "notification-expired",
+            name = name,
+            block_number = block_number
+        ),
+        Notification::BioauthRemovedNotification { block_number, .. } => tr!(
+            language,
+            "notification-removed",
+            name = name,
+            block_number = block_number
+        ),
+        Notification::BioauthLossEscalated { from_chat_id, .. } => tr!(
+            language,
+            "notification-escalated",
+            name = name,
+            from_chat = from_chat.map_or_else(|| from_chat_id.to_string(), ToOwned::to_owned)
+        ),
+    }
+}
+
+async fn handle_request(
+    request: NotificationRequest,
+    bot: &Bot,
+    outbound: &OutboundHandle,
+    bioauth_settings: &crate::BioauthSettings,
+    explorer_url: &ExplorerUrl,
+    subscription_update_tx: &tokio::sync::mpsc::Sender<SubscriptionUpdate>,
+) {
+    let NotificationRequest {
+        notification,
+        result_tx,
+    } = request;
+    let priority = priority(&notification);
+    let chat_id = notification_chat_id(&notification);
+    let language = bioauth_settings.language(chat_id).await;
+    let timezone_offset_in_mins = bioauth_settings
+        .get_chat(chat_id)
+        .await
+        .timezone_offset_in_mins;
+
+    let values = template_values(
+        &notification,
+        bioauth_settings,
+        explorer_url,
+        timezone_offset_in_mins,
+    )
+    .await;
+    let text = match bioauth_settings
+        .get_template(chat_id, notification.kind())
+        .await
+    {
+        // The custom text is sent as is, so it is escaped to be a valid HTML.
+        Some(template) => html::escape(&templates::render(&template, &values)),
+        None => {
+            let from_chat = match notification {
+                Notification::BioauthLossEscalated { from_chat_id, .. } => {
+                    Some(chat_title(bot, from_chat_id).await)
                 }
+                _ => None,
             };
+            default_text(
+                &notification,
+                &values,
+                from_chat.as_deref(),
+                timezone_offset_in_mins,
+                language,
+            )
+        }
+    };
+    let options = MessageOptions {
+        parse_mode: Some(ParseMode::Html),
+        reply_markup: Some(alert_actions::keyboard(
+            &values.address,
+            notification.kind(),
+            language,
+        )),
+    };
 
-            if let Err(error) = res {
-                tracing::error!(message = "notifier error", ?error);
-            };
-        };
+    let mut res = outbound
+        .send_message_with_options(ChatId(chat_id), text.clone(), options.clone(), priority)
+        .await;
+    if let Err(RequestError::MigrateToChatId(to_chat_id)) = res {
+        migrate_chat(subscription_update_tx, chat_id, to_chat_id).await;
+        res = outbound
+            .send_message_with_options(ChatId(to_chat_id), text, options, priority)
+            .await;
     }
+
+    let result = match res {
+        Ok(_) => Ok(()),
+        Err(error) => {
+            tracing::error!(message = "notifier error", ?error);
+            let notification = notification.failed(delivery_error(&error));
+
+            if is_chat_unreachable(&error) {
+                remove_unreachable_chat(subscription_update_tx, notification.chat_id(), &error)
+                    .await;
+            }
+
+            Err(SendNotificationError { notification })
+        }
+    };
+    let _ = result_tx.send(result);
 }
@@ -10,6 +10,8 @@ use super::{
     utils::{HandlerError, HandlerResult},
     State as GlobalState,
 };
+use crate::i18n::{tr, Language};
+use crate::outbound::{OutboundHandle, Priority};
 
 #[derive(BotCommands, Clone, Debug)]
 #[command(
@@ -26,24 +28,47 @@ async fn start(
     message: Message,
     admin_ids: Vec<i64>,
     text: String,
+    language: Language,
     rw_team_notification_subscription_map: Arc<
         tokio::sync::RwLock<dev_subscriptions::DevSubscriptionMap>,
     >,
+    outbound: OutboundHandle,
 ) -> HandlerResult {
     let admin_chat_id = message.chat.id;
 
     if admin_ids.contains(&admin_chat_id.0) {
-        bot.send_message(admin_chat_id, "Admin command got").await?;
-        let team_notification_subscription_map = rw_team_notification_subscription_map.read().await;
-        let subscriber_ids =
-            team_notification_subscription_map.get_all_enabled_team_notification_subscribers();
+        bot.send_message(admin_chat_id, tr!(language, "admin-command-got"))
+            .await?;
+        let subscriber_ids = {
+            let team_notification_subscription_map =
+                rw_team_notification_subscription_map.read().await;
+            team_notification_subscription_map.get_all_enabled_team_notification_subscribers()
+        };
+
+        // The broadcast goes after the pending alerts in the outbound queue.
+        let mut broadcasts = tokio::task::JoinSet::new();
         for chat_id in subscriber_ids.clone() {
-            bot.send_message(ChatId(chat_id), text.clone()).await?;
+            let outbound = outbound.clone();
+            let text = text.clone();
+            broadcasts.spawn(async move {
+                outbound
+                    .send_message(ChatId(chat_id), text, Priority::Broadcast)
+                    .await
+            });
+        }
+        while let Some(result) = broadcasts.join_next().await {
+            if let Ok(Err(error)) = result {
+                tracing::error!(message = "admin broadcast error", ?error);
+            }
         }
 
         bot.send_message(
             admin_chat_id,
-            format!("Sent this text to {:?}", subscriber_ids),
+            tr!(
+                language,
+                "admin-sent",
+                subscribers = format!("{:?}", subscriber_ids)
+            ),
         )
         .await?;
     }
@@ -0,0 +1,324 @@
+//! The buttons attached to the notifications.
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use bioauth_settings::NotificationKind;
+use subxt::utils::AccountId32;
+use teloxide::{
+    dispatching::UpdateHandler,
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup},
+};
+
+use super::{
+    common::status_text,
+    settings_card,
+    utils::{HandlerError, HandlerResult},
+};
+use crate::i18n::{tr, Language};
+use crate::SubscriptionUpdate;
+
+/// The prefix of the notification buttons callback data, distinguishing it from the other buttons.
+const CALLBACK_PREFIX: &str = "al:";
+
+const SNOOZE_DURATION_IN_MINS: u64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Snooze(u64),
+    Acknowledge,
+    /// Acknowledge the loss escalated to the backup chat for the chats it is escalated from.
+    AcknowledgeEscalation,
+    ShowStatus,
+    Settings,
+}
+
+impl Action {
+    fn encode(self) -> String {
+        match self {
+            Action::Snooze(in_mins) => format!("snooze{in_mins}"),
+            Action::Acknowledge => "ack".to_owned(),
+            Action::AcknowledgeEscalation => "eack".to_owned(),
+            Action::ShowStatus => "status".to_owned(),
+            Action::Settings => "settings".to_owned(),
+        }
+    }
+
+    fn decode(action: &str) -> Option<Self> {
+        // Only the offered duration is accepted as the callback data can be forged.
+        if let Some(value) = action.strip_prefix("snooze") {
+            return value
+                .parse()
+                .ok()
+                .filter(|&in_mins| in_mins == SNOOZE_DURATION_IN_MINS)
+                .map(Action::Snooze);
+        }
+        match action {
+            "ack" => Some(Action::Acknowledge),
+            "eack" => Some(Action::AcknowledgeEscalation),
+            "status" => Some(Action::ShowStatus),
+            "settings" => Some(Action::Settings),
+            _ => None,
+        }
+    }
+}
+
+fn callback_data(action: Action, address: &str) -> String {
+    format!("{CALLBACK_PREFIX}{}:{address}", action.encode())
+}
+
+fn parse_callback_data(data: &str) -> Option<(Action, String)> {
+    let (action, address) = data.strip_prefix(CALLBACK_PREFIX)?.split_once(':')?;
+    Some((Action::decode(action)?, address.to_owned()))
+}
+
+/// The buttons of the notification about the validator, the loss ones can be snoozed and
+/// acknowledged, the escalated ones can only be acknowledged as the backup chat is not
+/// subscribed.
+pub(crate) fn keyboard(
+    address: &str,
+    kind: NotificationKind,
+    language: Language,
+) -> InlineKeyboardMarkup {
+    if kind == NotificationKind::Escalated {
+        return InlineKeyboardMarkup::new([[InlineKeyboardButton::callback(
+            tr!(language, "alert-button-acknowledge"),
+            callback_data(Action::AcknowledgeEscalation, address),
+        )]]);
+    }
+
+    let mut keyboard: Vec<Vec<InlineKeyboardButton>> = vec![];
+
+    if matches!(
+        kind,
+        NotificationKind::Lost | NotificationKind::Expired | NotificationKind::Removed
+    ) {
+        keyboard.push(vec![
+            InlineKeyboardButton::callback(
+                tr!(
+                    language,
+                    "alert-button-snooze",
+                    hours = SNOOZE_DURATION_IN_MINS / 60
+                ),
+                callback_data(Action::Snooze(SNOOZE_DURATION_IN_MINS), address),
+            ),
+            InlineKeyboardButton::callback(
+                tr!(language, "alert-button-acknowledge"),
+                callback_data(Action::Acknowledge, address),
+            ),
+        ]);
+    }
+
+    keyboard.push(vec![
+        InlineKeyboardButton::callback(
+            tr!(language, "alert-button-status"),
+            callback_data(Action::ShowStatus, address),
+        ),
+        InlineKeyboardButton::callback(
+            tr!(language, "alert-button-settings"),
+            callback_data(Action::Settings, address),
+        ),
+    ]);
+
+    InlineKeyboardMarkup::new(keyboard)
+}
+
+/// Whether the validator has no active authentication at the latest processed block.
+async fn is_lost(
+    bioauth_public_key: &[u8; 32],
+    rw_active_authentications: &tokio::sync::RwLock<crate::ActiveAuthentications>,
+) -> bool {
+    let active_authentications = rw_active_authentications.read().await;
+    active_authentications.block_number.is_some()
+        && !active_authentications
+            .active_authentications_map
+            .contains_key(bioauth_public_key)
+}
+
+/// Acknowledge the escalated loss, the callback answer text if it is not applicable.
+async fn acknowledge_escalation(
+    bot: &Bot,
+    chat_id: ChatId,
+    bioauth_public_key: [u8; 32],
+    language: Language,
+    bioauth_settings: &crate::BioauthSettings,
+    rw_active_authentications: &tokio::sync::RwLock<crate::ActiveAuthentications>,
+    tx: &tokio::sync::mpsc::Sender<SubscriptionUpdate>,
+) -> Result<Option<String>, HandlerError> {
+    let address = crate::bioauth_handlers::to_ss58(bioauth_public_key);
+    let from_chat_ids = bioauth_settings
+        .get_escalating_chats(chat_id.0, bioauth_public_key)
+        .await;
+    if from_chat_ids.is_empty() {
+        return Ok(Some(tr!(
+            language,
+            "alert-not-escalated",
+            address = address
+        )));
+    }
+    if !is_lost(&bioauth_public_key, rw_active_authentications).await {
+        return Ok(Some(tr!(language, "alert-not-lost", name = address)));
+    }
+
+    for from_chat_id in from_chat_ids {
+        tx.send(SubscriptionUpdate::AcknowledgeLoss {
+            chat_id: from_chat_id,
+            bioauth_public_key,
+        })
+        .await?;
+
+        // The chat the loss is escalated from is told it is taken care of.
+        let from_language = bioauth_settings.language(from_chat_id).await;
+        let name = bioauth_settings
+            .display_name(from_chat_id, bioauth_public_key)
+            .await;
+        if let Err(error) = bot
+            .send_message(
+                ChatId(from_chat_id),
+                tr!(
+                    from_language,
+                    "alert-escalation-acknowledged",
+                    name = name,
+                    chat_id = chat_id.0.to_string()
+                ),
+            )
+            .await
+        {
+            tracing::warn!(message = "escalation acknowledgement send error", ?from_chat_id, %error);
+        }
+    }
+
+    bot.send_message(chat_id, tr!(language, "alert-acknowledged", name = address))
+        .await?;
+
+    Ok(None)
+}
+
+async fn callback_handler(
+    bot: Bot,
+    callback_query: CallbackQuery,
+    language: Language,
+    bioauth_settings: Arc<crate::BioauthSettings>,
+    rw_active_authentications: Arc<tokio::sync::RwLock<crate::ActiveAuthentications>>,
+    tx: tokio::sync::mpsc::Sender<SubscriptionUpdate>,
+) -> HandlerResult {
+    let Some((action, address)) = callback_query.data.as_deref().and_then(parse_callback_data)
+    else {
+        return Ok(());
+    };
+    let Some(Message { chat, .. }) = callback_query.message else {
+        bot.answer_callback_query(callback_query.id).await?;
+        return Ok(());
+    };
+
+    let bytes = AccountId32::from_str(&address)?.0;
+    if action == Action::AcknowledgeEscalation {
+        let answer = acknowledge_escalation(
+            &bot,
+            chat.id,
+            bytes,
+            language,
+            &bioauth_settings,
+            &rw_active_authentications,
+            &tx,
+        )
+        .await?;
+
+        let mut request = bot.answer_callback_query(callback_query.id);
+        request.text = answer;
+        request.await?;
+        return Ok(());
+    }
+
+    if !bioauth_settings
+        .get_all_subscription_keys(chat.id.0)
+        .await
+        .contains(&bytes)
+    {
+        bot.answer_callback_query(callback_query.id)
+            .text(tr!(
+                language,
+                "settings-card-not-subscribed",
+                address = address
+            ))
+            .await?;
+        return Ok(());
+    }
+
+    if matches!(action, Action::Snooze(_) | Action::Acknowledge)
+        && !is_lost(&bytes, &rw_active_authentications).await
+    {
+        let name = bioauth_settings.display_name(chat.id.0, bytes).await;
+        bot.answer_callback_query(callback_query.id)
+            .text(tr!(language, "alert-not-lost", name = name))
+            .await?;
+        return Ok(());
+    }
+    bot.answer_callback_query(callback_query.id).await?;
+
+    match action {
+        Action::Snooze(in_mins) => {
+            tx.send(SubscriptionUpdate::SnoozeLoss {
+                chat_id: chat.id.0,
+                bioauth_public_key: bytes,
+                in_mins,
+            })
+            .await?;
+
+            let name = bioauth_settings.display_name(chat.id.0, bytes).await;
+            bot.send_message(
+                chat.id,
+                tr!(
+                    language,
+                    "alert-snoozed",
+                    name = name,
+                    hours = in_mins / 60,
+                    mins = in_mins % 60
+                ),
+            )
+            .await?;
+        }
+        Action::Acknowledge => {
+            tx.send(SubscriptionUpdate::AcknowledgeLoss {
+                chat_id: chat.id.0,
+                bioauth_public_key: bytes,
+            })
+            .await?;
+
+            let name = bioauth_settings.display_name(chat.id.0, bytes).await;
+            bot.send_message(chat.id, tr!(language, "alert-acknowledged", name = name))
+                .await?;
+        }
+        // Handled above as the backup chat is not subscribed.
+        Action::AcknowledgeEscalation => {}
+        Action::ShowStatus => {
+            let text = status_text(
+                chat.id,
+                &[bytes],
+                &bioauth_settings,
+                &rw_active_authentications,
+                language,
+            )
+            .await
+            .unwrap_or_else(|| tr!(language, "status-no-block"));
+            bot.send_message(chat.id, text).await?;
+        }
+        Action::Settings => {
+            settings_card::send(&bot, chat.id, &address, &bioauth_settings, language).await?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn schema() -> UpdateHandler<HandlerError> {
+    Update::filter_callback_query()
+        .filter(|callback_query: CallbackQuery| {
+            callback_query
+                .data
+                .as_deref()
+                .is_some_and(|data| data.starts_with(CALLBACK_PREFIX))
+        })
+        .endpoint(callback_handler)
+}
@@ -0,0 +1,20 @@
+use teloxide::{dispatching::UpdateHandler, prelude::*};
+
+use super::utils::{HandlerError, HandlerResult};
+use crate::SubscriptionUpdate;
+
+async fn migrate_chat(
+    message: Message,
+    to_chat_id: ChatId,
+    tx: tokio::sync::mpsc::Sender<SubscriptionUpdate>,
+) -> HandlerResult {
+    crate::bioauth_handlers::migrate_chat(&tx, message.chat.id.0, to_chat_id.0).await;
+
+    Ok(())
+}
+
+pub fn schema() -> UpdateHandler<HandlerError> {
+    Update::filter_message()
+        .filter_map(|message: Message| message.migrate_to_chat_id())
+        .endpoint(migrate_chat)
+}
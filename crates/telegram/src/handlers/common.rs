@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use teloxide::{
     dispatching::{dialogue::ErasedStorage, UpdateHandler},
     prelude::*,
@@ -8,45 +10,125 @@ use super::{
     utils::{HandlerError, HandlerResult},
     Command, GlobalDialogue, State as GlobalState,
 };
+use crate::i18n::{self, tr, Language};
+
+async fn start(bot: Bot, message: Message, language: Language) -> HandlerResult {
+    bot.send_message(message.chat.id, tr!(language, "start-message"))
+        .await?;
+    Ok(())
+}
 
-const START_MESSAGE: &str = {
-    "
+async fn help(bot: Bot, message: Message, language: Language) -> HandlerResult {
+    bot.send_message(
+        message.chat.id,
+        i18n::help(language, Command::bot_commands()),
+    )
+    .await?;
+    Ok(())
+}
 
-!!! PAY ATTENTION !!!
-!!! THIS IS EARLY ACCESS VERSION !!!
-!!! SOME FUNCTIONALITY CAN BE UNSTABLE !!!
-!!! SOMETIMES BOT CAN BE FULLY RESET, BUT I WILL NOTIFY YOU ABOUT THAT IF YOU ACTIVATE DEVELOPER NOTIFICATION !!!
+/// The status of the validators at the latest processed block, `None` until the first block
+/// is processed.
+pub(crate) async fn status_text(
+    chat_id: ChatId,
+    bioauth_public_keys: &[[u8; 32]],
+    bioauth_settings: &crate::BioauthSettings,
+    rw_active_authentications: &tokio::sync::RwLock<crate::ActiveAuthentications>,
+    language: Language,
+) -> Option<String> {
+    let timezone_offset_in_mins = bioauth_settings
+        .get_chat(chat_id.0)
+        .await
+        .timezone_offset_in_mins;
 
-Welcome to the biostatus bot!
+    let mut names = Vec::with_capacity(bioauth_public_keys.len());
+    for bioauth_public_key in bioauth_public_keys {
+        names.push(
+            bioauth_settings
+                .display_name(chat_id.0, *bioauth_public_key)
+                .await,
+        );
+    }
 
-By using this bot, you will receive timely notifications regarding the impending loss of your validator status according to your settings.
+    let active_authentications = rw_active_authentications.read().await;
+    let block_number = active_authentications.block_number?;
 
-If your validator status is lost, or you are no longer a validator, you will be regularly notified based on your settings.
+    let lines: Vec<String> = bioauth_public_keys
+        .iter()
+        .zip(names)
+        .map(|(bioauth_public_key, name)| {
+            match active_authentications
+                .active_authentications_map
+                .get(bioauth_public_key)
+            {
+                Some(expires_at) => tr!(
+                    language,
+                    "status-active",
+                    name = name,
+                    expires_at = crate::bioauth_handlers::format_expires_at(
+                        *expires_at,
+                        timezone_offset_in_mins,
+                        language
+                    )
+                ),
+                None => tr!(language, "status-not-authenticated", name = name),
+            }
+        })
+        .collect();
 
-All bot messages are customizable to your liking, ensuring that you won't need to mute the bot and can stay informed about your status, allowing for immediate action.
+    Some(format!(
+        "{}\n\n{}",
+        tr!(language, "status-header", block_number = block_number),
+        lines.join("\n\n")
+    ))
+}
 
-It's also recommended to enable developer notifications to stay updated on any network changes that might affect your validator status, you can always unsubscribe from developer notifications if you find them irrelevant.
+async fn status(
+    bot: Bot,
+    message: Message,
+    language: Language,
+    get_all_subscriptions: Arc<crate::BioauthSettings>,
+    rw_active_authentications: Arc<tokio::sync::RwLock<crate::ActiveAuthentications>>,
+) -> HandlerResult {
+    let chat_id = message.chat.id;
 
-Use /help command to display bot usage instructions.
-"
-};
+    let mut subscriptions: Vec<[u8; 32]> = get_all_subscriptions
+        .get_all_subscription_keys(chat_id.0)
+        .await
+        .into_iter()
+        .collect();
+    if subscriptions.is_empty() {
+        bot.send_message(chat_id, tr!(language, "status-no-subscriptions"))
+            .await?;
+        return Ok(());
+    }
+    subscriptions.sort_unstable();
 
-async fn start(bot: Bot, message: Message) -> HandlerResult {
-    bot.send_message(message.chat.id, START_MESSAGE).await?;
-    Ok(())
-}
+    let text = status_text(
+        chat_id,
+        &subscriptions,
+        &get_all_subscriptions,
+        &rw_active_authentications,
+        language,
+    )
+    .await
+    .unwrap_or_else(|| tr!(language, "status-no-block"));
 
-async fn help(bot: Bot, message: Message) -> HandlerResult {
-    bot.send_message(message.chat.id, Command::descriptions().to_string())
-        .await?;
+    bot.send_message(chat_id, text).await?;
     Ok(())
 }
 
-async fn reset_state(bot: Bot, dialogue: GlobalDialogue, message: Message) -> HandlerResult {
+async fn reset_state(
+    bot: Bot,
+    dialogue: GlobalDialogue,
+    message: Message,
+    language: Language,
+) -> HandlerResult {
     let chat_id = message.chat.id;
-    bot.send_message(chat_id, "Resetting state").await?;
+    bot.send_message(chat_id, tr!(language, "reset-state"))
+        .await?;
 
-    super::transition_to_start(chat_id, &bot, dialogue).await
+    super::transition_to_start(chat_id, &bot, dialogue, language).await
 }
 
 pub fn schema() -> UpdateHandler<HandlerError> {
@@ -62,7 +144,8 @@ pub fn schema() -> UpdateHandler<HandlerError> {
                 dptree::case![GlobalState::Start]
                     .filter_command::<Command>()
                     .branch(dptree::case![Command::Start].endpoint(start))
-                    .branch(dptree::case![Command::Help].endpoint(help)),
+                    .branch(dptree::case![Command::Help].endpoint(help))
+                    .branch(dptree::case![Command::Status].endpoint(status)),
             ),
     )
 }
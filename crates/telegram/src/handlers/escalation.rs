@@ -0,0 +1,240 @@
+//! The backup chats the unacknowledged loss of the validator is escalated to.
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use bioauth_settings::EscalationLevel;
+use subxt::utils::AccountId32;
+use teloxide::{
+    dispatching::{dialogue::ErasedStorage, UpdateHandler},
+    prelude::*,
+};
+
+use super::{
+    utils::{HandlerError, HandlerResult},
+    Command as RootCommand, State as GlobalState,
+};
+use crate::i18n::{tr, Language};
+use crate::SubscriptionUpdate;
+
+/// The text that disables the escalation of the subscription.
+const DISABLE_ESCALATION_TEXT: &str = "off";
+
+fn format_escalation_levels(escalation_levels: &[EscalationLevel], language: Language) -> String {
+    if escalation_levels.is_empty() {
+        return tr!(language, "escalation-none");
+    }
+
+    escalation_levels
+        .iter()
+        .enumerate()
+        .map(|(index, level)| {
+            tr!(
+                language,
+                "escalation-level",
+                level = index + 1,
+                chat_id = level.chat_id.to_string(),
+                mins = level.after_in_mins
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parse the backup chat ids along with the delays, e.g. `-1001234567890 15 -1009876543210 60`.
+fn parse_escalation_levels(
+    text: &str,
+    chat_id: ChatId,
+    language: Language,
+) -> Result<Vec<EscalationLevel>, String> {
+    let values: Vec<&str> = text.split_whitespace().collect();
+    if values.len() % 2 != 0 || values.len() / 2 > EscalationLevel::MAX_LEVELS {
+        return Err(tr!(
+            language,
+            "escalation-invalid",
+            max = EscalationLevel::MAX_LEVELS
+        ));
+    }
+
+    let mut escalation_levels: Vec<EscalationLevel> = vec![];
+    for pair in values.chunks(2) {
+        let (Ok(backup_chat_id), Ok(after_in_mins)) = (pair[0].parse::<i64>(), pair[1].parse())
+        else {
+            return Err(tr!(
+                language,
+                "escalation-invalid",
+                max = EscalationLevel::MAX_LEVELS
+            ));
+        };
+        if backup_chat_id == chat_id.0 {
+            return Err(tr!(language, "escalation-own-chat"));
+        }
+        if !(1..=EscalationLevel::MAX_AFTER_IN_MINS).contains(&after_in_mins)
+            || escalation_levels
+                .last()
+                .is_some_and(|level| level.after_in_mins >= after_in_mins)
+        {
+            return Err(tr!(
+                language,
+                "escalation-not-ascending",
+                max_mins = EscalationLevel::MAX_AFTER_IN_MINS
+            ));
+        }
+
+        escalation_levels.push(EscalationLevel {
+            chat_id: backup_chat_id,
+            after_in_mins,
+        });
+    }
+
+    Ok(escalation_levels)
+}
+
+async fn escalation(
+    bot: Bot,
+    message: Message,
+    args: String,
+    language: Language,
+    bioauth_settings: Arc<crate::BioauthSettings>,
+    tx: tokio::sync::mpsc::Sender<SubscriptionUpdate>,
+) -> HandlerResult {
+    let chat_id = message.chat.id;
+    let args = args.trim();
+
+    if args.is_empty() {
+        let mut subscriptions = vec![];
+        for (address, label) in bioauth_settings.get_all_subscriptions(chat_id.0).await {
+            let bytes = AccountId32::from_str(&address)?.0;
+            let settings = bioauth_settings.get(&(chat_id.0, bytes)).await;
+            subscriptions.push(format!(
+                "{}:\n{}",
+                crate::bioauth_handlers::display_name(&address, label.as_deref()),
+                format_escalation_levels(&settings.escalation_levels, language)
+            ));
+        }
+        let subscriptions = if subscriptions.is_empty() {
+            tr!(language, "status-no-subscriptions")
+        } else {
+            subscriptions.join("\n\n")
+        };
+
+        bot.send_message(
+            chat_id,
+            tr!(
+                language,
+                "escalation-message",
+                subscriptions = subscriptions
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let (address, levels_text) = args
+        .split_once(char::is_whitespace)
+        .map(|(address, levels_text)| (address, levels_text.trim()))
+        .unwrap_or((args, ""));
+    let bytes = match AccountId32::from_str(address) {
+        Ok(account_id) => account_id.0,
+        Err(_) => {
+            bot.send_message(
+                chat_id,
+                tr!(
+                    language,
+                    "escalation-invalid",
+                    max = EscalationLevel::MAX_LEVELS
+                ),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+    if !bioauth_settings
+        .get_all_subscription_keys(chat_id.0)
+        .await
+        .contains(&bytes)
+    {
+        bot.send_message(
+            chat_id,
+            tr!(language, "settings-card-not-subscribed", address = address),
+        )
+        .await?;
+        return Ok(());
+    }
+    let name = bioauth_settings.display_name(chat_id.0, bytes).await;
+
+    if levels_text.is_empty() {
+        let settings = bioauth_settings.get(&(chat_id.0, bytes)).await;
+        bot.send_message(
+            chat_id,
+            tr!(
+                language,
+                "escalation-current",
+                name = name,
+                levels = format_escalation_levels(&settings.escalation_levels, language)
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let escalation_levels = if levels_text.eq_ignore_ascii_case(DISABLE_ESCALATION_TEXT) {
+        vec![]
+    } else {
+        match parse_escalation_levels(levels_text, chat_id, language) {
+            Ok(escalation_levels) => escalation_levels,
+            Err(error) => {
+                bot.send_message(chat_id, error).await?;
+                return Ok(());
+            }
+        }
+    };
+
+    // The bot has to be a member of the backup chat to escalate the loss to it, and so does
+    // the requester, so that the bot can't be made to post to the chats of others.
+    let Some(user) = message.from() else {
+        return Ok(());
+    };
+    for level in &escalation_levels {
+        let text = match bot.get_chat_member(ChatId(level.chat_id), user.id).await {
+            Ok(member) if member.kind.is_present() => continue,
+            Ok(_) => tr!(
+                language,
+                "escalation-not-member",
+                chat_id = level.chat_id.to_string()
+            ),
+            Err(_) => tr!(
+                language,
+                "escalation-unreachable-chat",
+                chat_id = level.chat_id.to_string()
+            ),
+        };
+        bot.send_message(chat_id, text).await?;
+        return Ok(());
+    }
+
+    let levels = format_escalation_levels(&escalation_levels, language);
+    tx.send(SubscriptionUpdate::UpdateEscalationLevels {
+        chat_id: chat_id.0,
+        bioauth_public_key: bytes,
+        escalation_levels,
+    })
+    .await?;
+
+    bot.send_message(
+        chat_id,
+        tr!(language, "escalation-updated", name = name, levels = levels),
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub fn schema() -> UpdateHandler<HandlerError> {
+    let root_command_handler = teloxide::filter_command::<RootCommand, _>()
+        .branch(dptree::case![RootCommand::Escalation { args }].endpoint(escalation));
+
+    Update::filter_message()
+        .enter_dialogue::<Message, ErasedStorage<GlobalState>, GlobalState>()
+        .branch(dptree::case![GlobalState::Start].branch(root_command_handler))
+}
@@ -0,0 +1,301 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use subxt::utils::AccountId32;
+use teloxide::{
+    dispatching::{dialogue::ErasedStorage, UpdateHandler},
+    net::Download,
+    prelude::*,
+    types::InputFile,
+    utils::command::BotCommands,
+};
+
+use super::{
+    rename, update_alert_before_expiration_in_mins, update_max_message_frequency_in_blocks,
+    utils::{set_local_commands, HandlerError, HandlerResult},
+    Command as RootCommand, GlobalDialogue, State as GlobalState,
+};
+use crate::i18n::{self, tr, Language};
+use crate::SubscriptionUpdate;
+
+/// The maximum size of the imported document in bytes.
+const MAX_DOCUMENT_SIZE: u32 = 1024 * 1024;
+
+/// The maximum number of the imported subscriptions.
+const MAX_SUBSCRIPTIONS: usize = 500;
+
+const EXPORT_FILE_NAME: &str = "subscriptions.json";
+
+#[derive(BotCommands, Clone, Debug)]
+#[command(
+    rename_rule = "lowercase",
+    description = "These commands are supported:"
+)]
+pub enum Command {
+    #[command(description = "display this text")]
+    Help,
+    #[command(description = "cancel the operation")]
+    Cancel,
+}
+
+/// The exported subscriptions of the chat.
+#[derive(Debug, Serialize, Deserialize)]
+struct Export {
+    subscriptions: Vec<ExportedSubscription>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ExportedSubscription {
+    address: String,
+    #[serde(default)]
+    label: Option<String>,
+    max_message_frequency_in_blocks: u32,
+    alert_before_expiration_in_mins: Vec<u64>,
+}
+
+async fn export(
+    bot: Bot,
+    message: Message,
+    language: Language,
+    bioauth_settings: Arc<crate::BioauthSettings>,
+) -> HandlerResult {
+    let chat_id = message.chat.id;
+
+    let mut subscriptions = Vec::new();
+    for bioauth_public_key in bioauth_settings.get_all_subscription_keys(chat_id.0).await {
+        let settings = bioauth_settings.get(&(chat_id.0, bioauth_public_key)).await;
+        subscriptions.push(ExportedSubscription {
+            address: crate::bioauth_handlers::to_ss58(bioauth_public_key),
+            label: settings.label,
+            max_message_frequency_in_blocks: settings.max_message_frequency_in_blocks,
+            alert_before_expiration_in_mins: settings.alert_before_expiration_in_mins,
+        });
+    }
+
+    if subscriptions.is_empty() {
+        bot.send_message(chat_id, tr!(language, "export-empty"))
+            .await?;
+        return Ok(());
+    }
+    subscriptions.sort_by(|a, b| a.address.cmp(&b.address));
+
+    let data = serde_json::to_vec_pretty(&Export { subscriptions })?;
+    bot.send_document(chat_id, InputFile::memory(data).file_name(EXPORT_FILE_NAME))
+        .caption(tr!(language, "export-caption"))
+        .await?;
+
+    Ok(())
+}
+
+async fn import(
+    bot: Bot,
+    message: Message,
+    dialogue: GlobalDialogue,
+    language: Language,
+) -> HandlerResult {
+    let chat_id = message.chat.id;
+    bot.send_message(chat_id, tr!(language, "import-message"))
+        .await?;
+
+    dialogue.update(GlobalState::Import).await?;
+    set_local_commands(chat_id, &bot, Command::bot_commands(), language).await
+}
+
+/// Validate the exported subscription and turn it into the subscription updates.
+///
+/// The existing subscription is not subscribed again, as it would reset its notification
+/// state, only its settings are restored.
+fn import_subscription(
+    chat_id: i64,
+    subscription: ExportedSubscription,
+    is_subscribed: bool,
+    language: Language,
+) -> Result<Vec<SubscriptionUpdate>, String> {
+    let bioauth_public_key = AccountId32::from_str(&subscription.address)
+        .map_err(|error| tr!(language, "invalid-address", error = error.to_string()))?
+        .0;
+
+    let in_blocks =
+        update_max_message_frequency_in_blocks::validate_max_message_frequency_in_blocks(
+            subscription.max_message_frequency_in_blocks,
+        )
+        .ok_or_else(|| tr!(language, "import-invalid-max-frequency"))?;
+
+    let in_mins = update_alert_before_expiration_in_mins::validate_alert_thresholds(
+        subscription.alert_before_expiration_in_mins,
+    )
+    .ok_or_else(|| tr!(language, "import-invalid-alert-times"))?;
+
+    let label = match subscription.label {
+        Some(label) => rename::parse_label(&label, language)?,
+        None => None,
+    };
+
+    let mut subscription_updates = Vec::with_capacity(4);
+    if !is_subscribed {
+        subscription_updates.push(SubscriptionUpdate::SubscribeToValidator {
+            chat_id,
+            bioauth_public_key,
+        });
+    }
+    subscription_updates.extend([
+        SubscriptionUpdate::UpdateSubscriptionMaxMessageFrequencyInBlocks {
+            chat_id,
+            bioauth_public_key,
+            in_blocks,
+        },
+        SubscriptionUpdate::UpdateSubscriptionAlertBeforeExpirationInMins {
+            chat_id,
+            bioauth_public_key,
+            in_mins,
+        },
+        SubscriptionUpdate::UpdateSubscriptionLabel {
+            chat_id,
+            bioauth_public_key,
+            label,
+        },
+    ]);
+
+    Ok(subscription_updates)
+}
+
+async fn receive_document(
+    bot: Bot,
+    message: Message,
+    dialogue: GlobalDialogue,
+    language: Language,
+    bioauth_settings: Arc<crate::BioauthSettings>,
+    tx: tokio::sync::mpsc::Sender<SubscriptionUpdate>,
+) -> HandlerResult {
+    let chat_id = message.chat.id;
+
+    let Some(document) = message.document() else {
+        bot.send_message(chat_id, tr!(language, "import-send-document"))
+            .await?;
+        return Ok(());
+    };
+
+    if document.file.size > MAX_DOCUMENT_SIZE {
+        bot.send_message(chat_id, tr!(language, "import-too-large"))
+            .await?;
+        return Ok(());
+    }
+
+    let file = bot.get_file(&document.file.id).await?;
+    let mut data = Vec::with_capacity(file.size as usize);
+    bot.download_file(&file.path, &mut data).await?;
+
+    let export: Export = match serde_json::from_slice(&data) {
+        Ok(export) => export,
+        Err(error) => {
+            bot.send_message(
+                chat_id,
+                tr!(
+                    language,
+                    "import-invalid-document",
+                    error = error.to_string()
+                ),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    if export.subscriptions.len() > MAX_SUBSCRIPTIONS {
+        bot.send_message(
+            chat_id,
+            tr!(language, "import-too-many", max = MAX_SUBSCRIPTIONS),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let mut subscribed = bioauth_settings.get_all_subscription_keys(chat_id.0).await;
+
+    let total = export.subscriptions.len();
+    let mut imported = 0;
+    let mut report = Vec::with_capacity(total);
+    for subscription in export.subscriptions {
+        let address = subscription.address.clone();
+        let bioauth_public_key = AccountId32::from_str(&address)
+            .ok()
+            .map(|account_id| account_id.0);
+        let is_subscribed = bioauth_public_key
+            .is_some_and(|bioauth_public_key| subscribed.contains(&bioauth_public_key));
+
+        match import_subscription(chat_id.0, subscription, is_subscribed, language) {
+            Ok(subscription_updates) => {
+                for subscription_update in subscription_updates {
+                    tx.send(subscription_update).await?;
+                }
+                subscribed.extend(bioauth_public_key);
+                imported += 1;
+                if is_subscribed {
+                    report.push(format!(
+                        "🔄 {address}: {}",
+                        tr!(language, "import-settings-restored")
+                    ));
+                } else {
+                    report.push(format!("✅ {address}"));
+                }
+            }
+            Err(error) => report.push(format!("❌ {address}: {error}")),
+        }
+    }
+
+    bot.send_message(
+        chat_id,
+        format!(
+            "{}\n\n{}",
+            tr!(
+                language,
+                "import-report",
+                imported = imported,
+                total = total
+            ),
+            report.join("\n")
+        ),
+    )
+    .await?;
+
+    super::transition_to_start(chat_id, &bot, dialogue, language).await
+}
+
+async fn help(bot: Bot, msg: Message, language: Language) -> HandlerResult {
+    bot.send_message(msg.chat.id, i18n::help(language, Command::bot_commands()))
+        .await?;
+    Ok(())
+}
+
+async fn cancel(
+    bot: Bot,
+    msg: Message,
+    dialogue: GlobalDialogue,
+    language: Language,
+) -> HandlerResult {
+    let chat_id = msg.chat.id;
+    bot.send_message(chat_id, tr!(language, "import-cancel-message"))
+        .await?;
+
+    super::transition_to_start(chat_id, &bot, dialogue, language).await
+}
+
+pub fn schema() -> UpdateHandler<HandlerError> {
+    let root_command_handler = teloxide::filter_command::<RootCommand, _>()
+        .branch(dptree::case![RootCommand::Export].endpoint(export))
+        .branch(dptree::case![RootCommand::Import].endpoint(import));
+
+    let command_handler = teloxide::filter_command::<Command, _>()
+        .branch(dptree::case![Command::Help].endpoint(help))
+        .branch(dptree::case![Command::Cancel].endpoint(cancel));
+
+    Update::filter_message()
+        .enter_dialogue::<Message, ErasedStorage<GlobalState>, GlobalState>()
+        .branch(dptree::case![GlobalState::Start].branch(root_command_handler))
+        .branch(
+            dptree::case![GlobalState::Import]
+                .branch(command_handler)
+                .endpoint(receive_document),
+        )
+}
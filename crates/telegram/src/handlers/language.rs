@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use teloxide::{
+    dispatching::{dialogue::ErasedStorage, UpdateHandler},
+    prelude::*,
+    utils::command::BotCommands,
+};
+
+use super::{
+    utils::{set_local_commands, HandlerError, HandlerResult},
+    Command as RootCommand, State as GlobalState,
+};
+use crate::i18n::{tr, Language};
+use crate::SubscriptionUpdate;
+
+/// The text that resets the language to the detected one.
+const AUTO_LANGUAGE_TEXT: &str = "auto";
+
+fn languages_list() -> String {
+    Language::ALL
+        .iter()
+        .map(|language| format!("{} — {}", language.code(), language.name()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+async fn language(
+    bot: Bot,
+    message: Message,
+    code: String,
+    language: Language,
+    bioauth_settings: Arc<crate::BioauthSettings>,
+    tx: tokio::sync::mpsc::Sender<SubscriptionUpdate>,
+) -> HandlerResult {
+    let chat_id = message.chat.id;
+    let chat_settings = bioauth_settings.get_chat(chat_id.0).await;
+    let code = code.trim();
+
+    if code.is_empty() {
+        let current = match chat_settings.language {
+            Some(_) => language.name().to_owned(),
+            None => tr!(language, "language-auto", language = language.name()),
+        };
+        bot.send_message(
+            chat_id,
+            tr!(
+                language,
+                "language-message",
+                current = current,
+                languages = languages_list()
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let (chat_language, language) = if code.eq_ignore_ascii_case(AUTO_LANGUAGE_TEXT) {
+        let user_language_code = message
+            .from()
+            .and_then(|user| user.language_code.as_deref());
+        (None, Language::resolve(None, user_language_code))
+    } else {
+        match Language::from_code(code) {
+            Some(language) => (Some(language), language),
+            None => {
+                bot.send_message(
+                    chat_id,
+                    tr!(language, "language-invalid", languages = languages_list()),
+                )
+                .await?;
+                return Ok(());
+            }
+        }
+    };
+
+    tx.send(SubscriptionUpdate::UpdateLanguage {
+        chat_id: chat_id.0,
+        language: chat_language.map(|language| language.code().to_owned()),
+    })
+    .await?;
+
+    bot.send_message(
+        chat_id,
+        tr!(language, "language-updated", language = language.name()),
+    )
+    .await?;
+
+    set_local_commands(chat_id, &bot, RootCommand::bot_commands(), language).await
+}
+
+pub fn schema() -> UpdateHandler<HandlerError> {
+    let root_command_handler = teloxide::filter_command::<RootCommand, _>()
+        .branch(dptree::case![RootCommand::Language { code }].endpoint(language));
+
+    Update::filter_message()
+        .enter_dialogue::<Message, ErasedStorage<GlobalState>, GlobalState>()
+        .branch(dptree::case![GlobalState::Start].branch(root_command_handler))
+}
@@ -11,6 +11,7 @@ use teloxide::{
 use super::utils::{set_local_commands, HandlerError, HandlerResult};
 use super::State as GlobalState;
 use super::{Command as RootCommand, GlobalDialogue};
+use crate::i18n::{self, tr, Language};
 
 #[derive(BotCommands, Clone, Debug)]
 #[command(
@@ -24,13 +25,14 @@ pub enum Command {
     Cancel,
 }
 
+/// The callback data with the catalog ids of the button text and the reply message.
 const ENABLE_AFFECTED_VALIDATOR: (&str, (&str, &str)) = (
     "enable_affected_validator",
-    ("Enable affected validator notifications", ENABLED_MESSAGE),
+    ("enable-affected-validator", "dev-subscription-enabled"),
 );
 const DISABLE_AFFECTED_VALIDATOR: (&str, (&str, &str)) = (
     "disable_affected_validator",
-    ("Disable affected validator notifications", DISABLED_MESSAGE),
+    ("disable-affected-validator", "dev-subscription-disabled"),
 );
 
 const BUTTONS: [(&str, (&str, &str)); 2] = [ENABLE_AFFECTED_VALIDATOR, DISABLE_AFFECTED_VALIDATOR];
@@ -48,28 +50,30 @@ pub async fn transition_to_choose_notifications(
     chat_id: ChatId,
     bot: &Bot,
     dialogue: GlobalDialogue,
+    language: Language,
 ) -> HandlerResult {
     dialogue
         .update(GlobalState::ManageNotificationFromDeveloper(
             State::ChooseNotifications,
         ))
         .await?;
-    set_local_commands(chat_id, bot, Command::bot_commands()).await
+    set_local_commands(chat_id, bot, Command::bot_commands(), language).await
 }
 
 fn make_subscriptions_markup(
     subscriptions: &dev_subscriptions::DevSubscriptions,
+    language: Language,
 ) -> InlineKeyboardMarkup {
     let mut keyboard: Vec<Vec<InlineKeyboardButton>> = vec![];
 
     if subscriptions.affected_validator {
         keyboard.push(vec![InlineKeyboardButton::callback(
-            DISABLE_AFFECTED_VALIDATOR.1 .0,
+            tr!(language, DISABLE_AFFECTED_VALIDATOR.1 .0),
             DISABLE_AFFECTED_VALIDATOR.0,
         )]);
     } else {
         keyboard.push(vec![InlineKeyboardButton::callback(
-            ENABLE_AFFECTED_VALIDATOR.1 .0,
+            tr!(language, ENABLE_AFFECTED_VALIDATOR.1 .0),
             ENABLE_AFFECTED_VALIDATOR.0,
         )]);
     }
@@ -81,68 +85,47 @@ async fn start(
     bot: Bot,
     msg: Message,
     dialogue: GlobalDialogue,
+    language: Language,
     rw_dev_subscription_map: Arc<tokio::sync::RwLock<dev_subscriptions::DevSubscriptionMap>>,
 ) -> HandlerResult {
     let dev_subscription_map = rw_dev_subscription_map.read().await;
     let chat_id = msg.chat.id;
     let subscriptions = dev_subscription_map.get(&chat_id.0);
-    let keyboard = make_subscriptions_markup(subscriptions);
+    let keyboard = make_subscriptions_markup(subscriptions, language);
 
-    bot.send_message(
-        chat_id,
-        "use /help command to display bot usage instructions.",
-    )
-    .reply_markup(keyboard)
-    .await?;
+    bot.send_message(chat_id, tr!(language, "help-hint"))
+        .reply_markup(keyboard)
+        .await?;
 
-    transition_to_choose_notifications(msg.chat.id, &bot, dialogue).await
+    transition_to_choose_notifications(msg.chat.id, &bot, dialogue, language).await
 }
 
 // async fn affected_validator(bot: Bot, msg: Message) -> HandlerResult {}
 
-async fn help(bot: Bot, msg: Message) -> HandlerResult {
-    bot.send_message(msg.chat.id, Command::descriptions().to_string())
+async fn help(bot: Bot, msg: Message, language: Language) -> HandlerResult {
+    bot.send_message(msg.chat.id, i18n::help(language, Command::bot_commands()))
         .await?;
     Ok(())
 }
 
-const CANCEL_MESSAGE: &str = {
-    "
-You have canceled the action.
-
-Your developer notification subscriptions remain unchanged.
-
-use /help command to display bot usage instructions.
-"
-};
-
-async fn cancel(bot: Bot, msg: Message, dialogue: GlobalDialogue) -> HandlerResult {
+async fn cancel(
+    bot: Bot,
+    msg: Message,
+    dialogue: GlobalDialogue,
+    language: Language,
+) -> HandlerResult {
     let chat_id = msg.chat.id;
-    bot.send_message(chat_id, CANCEL_MESSAGE).await?;
+    bot.send_message(chat_id, tr!(language, "dev-subscriptions-cancel-message"))
+        .await?;
 
-    super::transition_to_start(chat_id, &bot, dialogue).await
+    super::transition_to_start(chat_id, &bot, dialogue, language).await
 }
 
-const ENABLED_MESSAGE: &str = {
-    "
-Subscription successfully enabled
-
-use /help command to display bot usage instructions.
-"
-};
-
-const DISABLED_MESSAGE: &str = {
-    "
-Subscription successfully disabled
-
-use /help command to display bot usage instructions.
-"
-};
-
 async fn callback_handler(
     bot: Bot,
     dialogue: GlobalDialogue,
     callback_query: CallbackQuery,
+    language: Language,
     tx: tokio::sync::mpsc::Sender<crate::SubscriptionUpdate>,
 ) -> HandlerResult {
     if let Some(variant) = callback_query.data {
@@ -174,9 +157,10 @@ async fn callback_handler(
                 _ => {}
             };
 
-            bot.edit_message_text(chat.id, id, text.to_string()).await?;
+            bot.edit_message_text(chat.id, id, tr!(language, text))
+                .await?;
 
-            super::transition_to_start(chat.id, &bot, dialogue).await?;
+            super::transition_to_start(chat.id, &bot, dialogue, language).await?;
         }
     }
 
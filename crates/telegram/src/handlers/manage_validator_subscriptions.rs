@@ -12,10 +12,11 @@ use crate::handlers::subscription_update;
 
 use super::State as GlobalState;
 use super::{
-    subscribe,
+    settings_card, subscribe,
     utils::{set_local_commands, HandlerError, HandlerResult},
 };
 use super::{Command as RootCommand, GlobalDialogue};
+use crate::i18n::{self, tr, Language};
 
 #[derive(BotCommands, Clone, Debug)]
 #[command(
@@ -51,6 +52,9 @@ pub enum State {
     Unsubscribe {
         address: String,
     },
+    Rename {
+        address: String,
+    },
 }
 
 pub async fn transition_to_display_all_subscriptions(
@@ -58,6 +62,7 @@ pub async fn transition_to_display_all_subscriptions(
     bot: &Bot,
     message: Message,
     dialogue: GlobalDialogue,
+    language: Language,
 ) -> HandlerResult {
     dialogue
         .update(GlobalState::ManageValidatorSubscriptions(
@@ -66,7 +71,7 @@ pub async fn transition_to_display_all_subscriptions(
             },
         ))
         .await?;
-    set_local_commands(chat_id, bot, Command::bot_commands()).await
+    set_local_commands(chat_id, bot, Command::bot_commands(), language).await
 }
 
 async fn make_subscriptions_markup(
@@ -77,105 +82,98 @@ async fn make_subscriptions_markup(
 
     let subscriptions = get_all_subscriptions.get_all_subscriptions(chat_id).await;
     let subscriptions_len = subscriptions.clone().len();
-    for subscription in subscriptions {
+    for (address, label) in subscriptions {
         keyboard.push(vec![InlineKeyboardButton::callback(
-            subscription.to_owned(),
-            subscription.to_owned(),
+            crate::bioauth_handlers::display_name(&address, label.as_deref()),
+            address,
         )]);
     }
 
     (InlineKeyboardMarkup::new(keyboard), subscriptions_len)
 }
 
-const START_MESSAGE: &str = {
-    "
-You don't have any subscriptions yet,
-
-use /help command to display bot usage instructions.
-"
-};
-
 async fn start(
     bot: Bot,
     msg: Message,
     dialogue: GlobalDialogue,
+    language: Language,
     get_all_subscriptions: Arc<crate::BioauthSettings>,
 ) -> HandlerResult {
     let (keyboard, len) = make_subscriptions_markup(msg.chat.id.0, get_all_subscriptions).await;
     let chat_id = msg.chat.id;
 
     let message = if len == 0 {
-        bot.send_message(chat_id, START_MESSAGE)
+        bot.send_message(chat_id, tr!(language, "no-subscriptions-message"))
             .reply_markup(keyboard)
             .await?
     } else {
-        bot.send_message(chat_id, "Choose a subscription to manage it, or use /help command to display bot usage instructions.")
+        bot.send_message(chat_id, tr!(language, "choose-subscription"))
             .reply_markup(keyboard)
             .await?
     };
 
-    transition_to_display_all_subscriptions(msg.chat.id, &bot, message, dialogue).await
+    transition_to_display_all_subscriptions(msg.chat.id, &bot, message, dialogue, language).await
 }
 
-async fn help(bot: Bot, msg: Message) -> HandlerResult {
-    bot.send_message(msg.chat.id, Command::descriptions().to_string())
+async fn help(bot: Bot, msg: Message, language: Language) -> HandlerResult {
+    bot.send_message(msg.chat.id, i18n::help(language, Command::bot_commands()))
         .await?;
     Ok(())
 }
 
-const CANCEL_MESSAGE: &str = {
-    "
-Your validator subscriptions remain unchanged.
-
-use /help command to display bot usage instructions.
-"
-};
-
 async fn cancel(
     bot: Bot,
     msg: Message,
     message_data: (ChatId, MessageId),
     dialogue: GlobalDialogue,
+    language: Language,
 ) -> HandlerResult {
     let chat_id = msg.chat.id;
-    bot.send_message(chat_id, CANCEL_MESSAGE).await?;
+    bot.send_message(chat_id, tr!(language, "subscriptions-unchanged-message"))
+        .await?;
 
     bot.edit_message_text(
         message_data.0,
         message_data.1,
-        "You have canceled the action.",
+        tr!(language, "action-canceled"),
     )
     .await?;
 
-    super::transition_to_start(chat_id, &bot, dialogue).await
+    super::transition_to_start(chat_id, &bot, dialogue, language).await
 }
 
 async fn callback_handler(
     bot: Bot,
     dialogue: GlobalDialogue,
     callback_query: CallbackQuery,
+    language: Language,
+    bioauth_settings: Arc<crate::BioauthSettings>,
 ) -> HandlerResult {
     bot.answer_callback_query(callback_query.id).await?;
 
     if let Some(address) = callback_query.data {
-        let text = format!("Subscription {address} selected");
+        let text = tr!(
+            language,
+            "subscription-selected",
+            address = address.as_str()
+        );
 
         if let Some(Message { id, chat, .. }) = callback_query.message {
             bot.edit_message_text(chat.id, id, text).await?;
 
+            settings_card::send(&bot, chat.id, &address, &bioauth_settings, language).await?;
+
             subscription_update::transition_to_update_subscription(
                 chat.id,
                 &bot,
                 address.clone(),
                 dialogue,
+                language,
             )
             .await?;
 
-            bot.send_message(
-                chat.id,
-                "Use /help command to display bot usage instructions.",
-            )
-            .await?;
+            bot.send_message(chat.id, tr!(language, "help-hint"))
+                .await?;
         }
     }
 
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
 use teloxide::{
     dispatching::{dialogue::ErasedStorage, UpdateHandler},
@@ -7,11 +9,21 @@ use teloxide::{
 use utils::{set_local_commands, HandlerResult};
 
 use self::utils::HandlerError;
+use crate::i18n::Language;
 
 pub mod admin;
+pub mod alert_actions;
+pub mod chat_migration;
 pub mod common;
+pub mod escalation;
+pub mod export_import;
+pub mod language;
 pub mod manage_dev_subscriptions;
 pub mod manage_validator_subscriptions;
+pub mod notification_templates;
+pub mod quiet_hours;
+pub mod rename;
+pub mod settings_card;
 pub mod subscribe;
 pub mod subscription_update;
 pub mod unsubscribe;
@@ -39,6 +51,32 @@ pub enum Command {
         description = "manage notifications from the developer. Stay informed about network updates that may affect your validator status"
     )]
     ManageDevSubscriptions,
+    #[command(description = "show the current bio-authentication status of your validators")]
+    Status,
+    #[command(
+        description = "export your validator subscriptions and their settings as a document"
+    )]
+    Export,
+    #[command(description = "restore validator subscriptions from an exported document")]
+    Import,
+    #[command(
+        description = "show or set the UTC offset of your local time, e.g. /timezone +02:00"
+    )]
+    Timezone { offset: String },
+    #[command(
+        description = "show or set the local time range the repeated notifications are deferred in, e.g. /quiethours 23:00-07:00"
+    )]
+    QuietHours { hours: String },
+    #[command(description = "show or set the language of the bot, e.g. /language es")]
+    Language { code: String },
+    #[command(
+        description = "show or set the custom notification texts, e.g. /template lost {name} is down, @oncall"
+    )]
+    Template { args: String },
+    #[command(
+        description = "show or set the backup chats the unacknowledged loss is escalated to, e.g. /escalation <address> -1001234567890 15"
+    )]
+    Escalation { args: String },
     #[command(description = "#debug_command restart state.")]
     ResetState,
 }
@@ -47,9 +85,10 @@ pub async fn transition_to_start(
     chat_id: ChatId,
     bot: &Bot,
     dialogue: GlobalDialogue,
+    language: Language,
 ) -> HandlerResult {
     dialogue.update(State::Start).await?;
-    set_local_commands(chat_id, bot, Command::bot_commands()).await
+    set_local_commands(chat_id, bot, Command::bot_commands(), language).await
 }
 
 #[derive(Clone, Default, Debug, Serialize, Deserialize)]
@@ -58,12 +97,36 @@ pub enum State {
     Start,
     ManageValidatorSubscriptions(manage_validator_subscriptions::State),
     ManageNotificationFromDeveloper(manage_dev_subscriptions::State),
+    Import,
+}
+
+/// The language of the texts, chosen for the chat or detected from the user.
+async fn detect_language(
+    update: Update,
+    bioauth_settings: Arc<crate::BioauthSettings>,
+) -> Language {
+    let chat_language = match update.chat() {
+        Some(chat) => bioauth_settings.get_chat(chat.id.0).await.language,
+        None => None,
+    };
+    let user_language_code = update.user().and_then(|user| user.language_code.as_deref());
+
+    Language::resolve(chat_language.as_deref(), user_language_code)
 }
 
 pub fn schema() -> UpdateHandler<HandlerError> {
     dptree::entry()
+        .map_async(detect_language)
+        .branch(chat_migration::schema())
+        .branch(settings_card::schema())
+        .branch(alert_actions::schema())
         .branch(manage_validator_subscriptions::schema())
         .branch(manage_dev_subscriptions::schema())
+        .branch(export_import::schema())
+        .branch(quiet_hours::schema())
+        .branch(language::schema())
+        .branch(notification_templates::schema())
+        .branch(escalation::schema())
         .branch(common::schema())
         .branch(admin::schema())
 }
@@ -0,0 +1,205 @@
+use std::sync::Arc;
+
+use bioauth_settings::NotificationKind;
+use teloxide::{
+    dispatching::{dialogue::ErasedStorage, UpdateHandler},
+    prelude::*,
+};
+
+use super::{
+    utils::{HandlerError, HandlerResult},
+    Command as RootCommand, State as GlobalState,
+};
+use crate::bioauth_handlers::{self, ExplorerUrl};
+use crate::i18n::{tr, Language};
+use crate::templates::{self, Placeholder, TemplateError, TemplateValues};
+use crate::SubscriptionUpdate;
+
+/// The text that restores the default notification text.
+const RESET_TEMPLATE_TEXT: &str = "reset";
+
+/// The sample placeholder values for the preview of the template.
+async fn preview_values(
+    chat_id: ChatId,
+    bioauth_settings: &crate::BioauthSettings,
+    explorer_url: &ExplorerUrl,
+) -> TemplateValues {
+    let (address, label) = bioauth_settings
+        .get_all_subscriptions(chat_id.0)
+        .await
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| (bioauth_handlers::to_ss58([0; 32]), None));
+    let timezone_offset_in_mins = bioauth_settings
+        .get_chat(chat_id.0)
+        .await
+        .timezone_offset_in_mins;
+    let expires_at = chrono::Utc::now() + chrono::TimeDelta::hours(1);
+    let expires_at = u64::try_from(expires_at.timestamp_millis())
+        .ok()
+        .and_then(|expires_at| {
+            bioauth_handlers::local_time_and_time_left(expires_at, timezone_offset_in_mins)
+        })
+        .map(|(time, _)| time);
+
+    TemplateValues {
+        name: bioauth_handlers::display_name(&address, label.as_deref()),
+        explorer_url: explorer_url.address_url(&address),
+        address,
+        label,
+        block_number: Some(1_000_000),
+        expires_at,
+        time_left: Some(bioauth_handlers::format_time_left(
+            chrono::TimeDelta::hours(1),
+        )),
+    }
+}
+
+fn kinds_list() -> String {
+    NotificationKind::ALL
+        .iter()
+        .map(|kind| format!("{} — {}", kind.name(), Placeholder::format_available(*kind)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn template_error(error: TemplateError, kind: NotificationKind, language: Language) -> String {
+    match error {
+        TemplateError::Empty => tr!(language, "template-empty"),
+        TemplateError::TooLong => tr!(
+            language,
+            "template-too-long",
+            max = templates::MAX_TEMPLATE_LEN
+        ),
+        TemplateError::Unclosed => tr!(language, "template-unclosed"),
+        TemplateError::UnknownPlaceholder(placeholder) => tr!(
+            language,
+            "template-unknown-placeholder",
+            placeholder = placeholder,
+            placeholders = Placeholder::format_available(kind)
+        ),
+    }
+}
+
+async fn template(
+    bot: Bot,
+    message: Message,
+    args: String,
+    language: Language,
+    bioauth_settings: Arc<crate::BioauthSettings>,
+    tx: tokio::sync::mpsc::Sender<SubscriptionUpdate>,
+    explorer_url: Arc<ExplorerUrl>,
+) -> HandlerResult {
+    let chat_id = message.chat.id;
+    let args = args.trim();
+
+    if args.is_empty() {
+        let mut templates = vec![];
+        for kind in NotificationKind::ALL {
+            if let Some(template) = bioauth_settings.get_template(chat_id.0, kind).await {
+                templates.push(format!("{}: {template}", kind.name()));
+            }
+        }
+        let templates = if templates.is_empty() {
+            tr!(language, "template-none")
+        } else {
+            templates.join("\n\n")
+        };
+
+        bot.send_message(
+            chat_id,
+            tr!(
+                language,
+                "template-message",
+                templates = templates,
+                kinds = kinds_list()
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let (kind_name, template) = args
+        .split_once(char::is_whitespace)
+        .map(|(kind_name, template)| (kind_name, template.trim()))
+        .unwrap_or((args, ""));
+    let Some(kind) = NotificationKind::from_name(&kind_name.to_ascii_lowercase()) else {
+        bot.send_message(
+            chat_id,
+            tr!(language, "template-invalid-kind", kinds = kinds_list()),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    if template.is_empty() {
+        let current = match bioauth_settings.get_template(chat_id.0, kind).await {
+            Some(template) => template,
+            None => tr!(language, "template-default"),
+        };
+        bot.send_message(
+            chat_id,
+            tr!(
+                language,
+                "template-current",
+                kind = kind.name(),
+                template = current,
+                placeholders = Placeholder::format_available(kind)
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if template.eq_ignore_ascii_case(RESET_TEMPLATE_TEXT) {
+        tx.send(SubscriptionUpdate::UpdateNotificationTemplate {
+            chat_id: chat_id.0,
+            kind,
+            template: None,
+        })
+        .await?;
+
+        bot.send_message(chat_id, tr!(language, "template-reset", kind = kind.name()))
+            .await?;
+        return Ok(());
+    }
+
+    if let Err(error) = templates::validate(kind, template) {
+        bot.send_message(chat_id, template_error(error, kind, language))
+            .await?;
+        return Ok(());
+    }
+
+    tx.send(SubscriptionUpdate::UpdateNotificationTemplate {
+        chat_id: chat_id.0,
+        kind,
+        template: Some(template.to_owned()),
+    })
+    .await?;
+
+    let preview = templates::render(
+        template,
+        &preview_values(chat_id, &bioauth_settings, &explorer_url).await,
+    );
+    bot.send_message(
+        chat_id,
+        tr!(
+            language,
+            "template-updated",
+            kind = kind.name(),
+            preview = preview
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub fn schema() -> UpdateHandler<HandlerError> {
+    let root_command_handler = teloxide::filter_command::<RootCommand, _>()
+        .branch(dptree::case![RootCommand::Template { args }].endpoint(template));
+
+    Update::filter_message()
+        .enter_dialogue::<Message, ErasedStorage<GlobalState>, GlobalState>()
+        .branch(dptree::case![GlobalState::Start].branch(root_command_handler))
+}
@@ -0,0 +1,209 @@
+use std::sync::Arc;
+
+use bioauth_settings::QuietHours;
+use teloxide::{
+    dispatching::{dialogue::ErasedStorage, UpdateHandler},
+    prelude::*,
+};
+
+use super::{
+    utils::{HandlerError, HandlerResult},
+    Command as RootCommand, State as GlobalState,
+};
+use crate::bioauth_handlers::format_timezone_offset;
+use crate::i18n::{tr, Language};
+use crate::SubscriptionUpdate;
+
+/// The supported UTC offsets range.
+const MIN_TIMEZONE_OFFSET_IN_MINS: i32 = -12 * 60;
+const MAX_TIMEZONE_OFFSET_IN_MINS: i32 = 14 * 60;
+
+/// The text that disables the quiet hours.
+const DISABLE_QUIET_HOURS_TEXT: &str = "off";
+
+/// Parse the UTC offset, e.g. `+3`, `-05:30` or `UTC+1`.
+fn parse_timezone_offset(text: &str) -> Option<i32> {
+    let text = text.trim();
+    let text = text
+        .strip_prefix("UTC")
+        .or_else(|| text.strip_prefix("GMT"))
+        .unwrap_or(text);
+    if text.is_empty() {
+        return Some(0);
+    }
+
+    let (sign, text) = match (text.strip_prefix('+'), text.strip_prefix('-')) {
+        (Some(rest), _) => (1, rest),
+        (_, Some(rest)) => (-1, rest),
+        _ => (1, text),
+    };
+
+    let (hours, mins) = match text.split_once(':') {
+        Some((hours, mins)) => (hours.parse::<i32>().ok()?, mins.parse::<i32>().ok()?),
+        None => (text.parse::<i32>().ok()?, 0),
+    };
+    if !(0..60).contains(&mins) || hours < 0 {
+        return None;
+    }
+
+    let offset_in_mins = sign * (hours * 60 + mins);
+    (MIN_TIMEZONE_OFFSET_IN_MINS..=MAX_TIMEZONE_OFFSET_IN_MINS)
+        .contains(&offset_in_mins)
+        .then_some(offset_in_mins)
+}
+
+/// Parse the local time, e.g. `23:00` or `7`.
+fn parse_local_time(text: &str) -> Option<u16> {
+    let (hours, mins) = match text.trim().split_once(':') {
+        Some((hours, mins)) => (hours.parse::<u16>().ok()?, mins.parse::<u16>().ok()?),
+        None => (text.trim().parse::<u16>().ok()?, 0),
+    };
+    if hours >= 24 || mins >= 60 {
+        return None;
+    }
+
+    Some(hours * 60 + mins)
+}
+
+fn format_local_time(time_in_mins: u16) -> String {
+    format!("{:02}:{:02}", time_in_mins / 60, time_in_mins % 60)
+}
+
+/// Parse the quiet hours range, e.g. `23:00-07:00`, `None` disables them.
+fn parse_quiet_hours(text: &str) -> Option<Option<QuietHours>> {
+    if text.trim().eq_ignore_ascii_case(DISABLE_QUIET_HOURS_TEXT) {
+        return Some(None);
+    }
+
+    let (start, end) = text.split_once('-')?;
+    let quiet_hours = QuietHours {
+        start_in_mins: parse_local_time(start)?,
+        end_in_mins: parse_local_time(end)?,
+    };
+    if quiet_hours.start_in_mins == quiet_hours.end_in_mins {
+        return None;
+    }
+
+    Some(Some(quiet_hours))
+}
+
+fn format_quiet_hours(quiet_hours: Option<QuietHours>) -> String {
+    match quiet_hours {
+        Some(quiet_hours) => format!(
+            "{}-{}",
+            format_local_time(quiet_hours.start_in_mins),
+            format_local_time(quiet_hours.end_in_mins)
+        ),
+        None => DISABLE_QUIET_HOURS_TEXT.to_owned(),
+    }
+}
+
+async fn timezone(
+    bot: Bot,
+    message: Message,
+    offset: String,
+    language: Language,
+    bioauth_settings: Arc<crate::BioauthSettings>,
+    tx: tokio::sync::mpsc::Sender<SubscriptionUpdate>,
+) -> HandlerResult {
+    let chat_id = message.chat.id;
+    let chat_settings = bioauth_settings.get_chat(chat_id.0).await;
+
+    if offset.trim().is_empty() {
+        bot.send_message(
+            chat_id,
+            tr!(
+                language,
+                "timezone-message",
+                timezone = format_timezone_offset(chat_settings.timezone_offset_in_mins)
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let Some(timezone_offset_in_mins) = parse_timezone_offset(&offset) else {
+        bot.send_message(chat_id, tr!(language, "timezone-invalid"))
+            .await?;
+        return Ok(());
+    };
+
+    tx.send(SubscriptionUpdate::UpdateTimezone {
+        chat_id: chat_id.0,
+        timezone_offset_in_mins,
+    })
+    .await?;
+
+    bot.send_message(
+        chat_id,
+        tr!(
+            language,
+            "timezone-updated",
+            timezone = format_timezone_offset(timezone_offset_in_mins)
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn quiet_hours(
+    bot: Bot,
+    message: Message,
+    hours: String,
+    language: Language,
+    bioauth_settings: Arc<crate::BioauthSettings>,
+    tx: tokio::sync::mpsc::Sender<SubscriptionUpdate>,
+) -> HandlerResult {
+    let chat_id = message.chat.id;
+    let chat_settings = bioauth_settings.get_chat(chat_id.0).await;
+
+    if hours.trim().is_empty() {
+        bot.send_message(
+            chat_id,
+            tr!(
+                language,
+                "quiet-hours-message",
+                hours = format_quiet_hours(chat_settings.quiet_hours),
+                timezone = format_timezone_offset(chat_settings.timezone_offset_in_mins)
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let Some(quiet_hours) = parse_quiet_hours(&hours) else {
+        bot.send_message(chat_id, tr!(language, "quiet-hours-invalid"))
+            .await?;
+        return Ok(());
+    };
+
+    tx.send(SubscriptionUpdate::UpdateQuietHours {
+        chat_id: chat_id.0,
+        quiet_hours,
+    })
+    .await?;
+
+    bot.send_message(
+        chat_id,
+        tr!(
+            language,
+            "quiet-hours-updated",
+            hours = format_quiet_hours(quiet_hours),
+            timezone = format_timezone_offset(chat_settings.timezone_offset_in_mins)
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub fn schema() -> UpdateHandler<HandlerError> {
+    let root_command_handler = teloxide::filter_command::<RootCommand, _>()
+        .branch(dptree::case![RootCommand::Timezone { offset }].endpoint(timezone))
+        .branch(dptree::case![RootCommand::QuietHours { hours }].endpoint(quiet_hours));
+
+    Update::filter_message()
+        .enter_dialogue::<Message, ErasedStorage<GlobalState>, GlobalState>()
+        .branch(dptree::case![GlobalState::Start].branch(root_command_handler))
+}
@@ -0,0 +1,190 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use subxt::utils::AccountId32;
+use teloxide::dispatching::dialogue::ErasedStorage;
+use teloxide::dispatching::UpdateHandler;
+use teloxide::prelude::*;
+use teloxide::utils::command::BotCommands;
+
+use super::{subscription_update, State as GlobalState};
+use crate::SubscriptionUpdate;
+
+use super::manage_validator_subscriptions;
+use super::utils::{set_local_commands, HandlerError, HandlerResult};
+use super::GlobalDialogue;
+use crate::i18n::{self, tr, Language};
+
+/// The maximum label length in characters.
+const MAX_LABEL_LEN: usize = 64;
+
+/// The text that removes the label.
+const REMOVE_LABEL_TEXT: &str = "-";
+
+#[derive(BotCommands, Clone, Debug)]
+#[command(
+    rename_rule = "lowercase",
+    description = "These commands are supported:"
+)]
+pub enum Command {
+    #[command(description = "display this text")]
+    Help,
+    #[command(description = "cancel the operation")]
+    Cancel,
+}
+
+pub async fn transition_to_rename(
+    chat_id: ChatId,
+    bot: &Bot,
+    address: String,
+    dialogue: GlobalDialogue,
+    language: Language,
+) -> HandlerResult {
+    dialogue
+        .update(GlobalState::ManageValidatorSubscriptions(
+            manage_validator_subscriptions::State::Rename { address },
+        ))
+        .await?;
+    set_local_commands(chat_id, bot, Command::bot_commands(), language).await
+}
+
+pub async fn command(
+    msg: Message,
+    bot: Bot,
+    address: String,
+    dialogue: GlobalDialogue,
+    language: Language,
+    bioauth_settings: Arc<crate::BioauthSettings>,
+) -> HandlerResult {
+    let chat_id = msg.chat.id;
+    let bytes = AccountId32::from_str(&address.clone())?.0;
+    let settings = bioauth_settings.get(&(chat_id.0, bytes)).await;
+    let current_label = match settings.label {
+        Some(label) => label,
+        None => tr!(language, "label-not-set"),
+    };
+    bot.send_message(
+        chat_id,
+        tr!(
+            language,
+            "label-current",
+            address = address.clone(),
+            label = current_label
+        ),
+    )
+    .await?;
+    bot.send_message(chat_id, tr!(language, "rename-message"))
+        .await?;
+
+    transition_to_rename(chat_id, &bot, address, dialogue, language).await
+}
+
+/// Parse the label input, `Ok(None)` means removing the label.
+pub(crate) fn parse_label(text: &str, language: Language) -> Result<Option<String>, String> {
+    let label = text.trim();
+
+    if label == REMOVE_LABEL_TEXT {
+        return Ok(None);
+    }
+
+    if label.is_empty() {
+        return Err(tr!(language, "label-empty"));
+    }
+
+    if label.chars().count() > MAX_LABEL_LEN {
+        return Err(tr!(language, "label-too-long", max = MAX_LABEL_LEN));
+    }
+
+    Ok(Some(label.to_owned()))
+}
+
+pub async fn rename(
+    msg: Message,
+    bot: Bot,
+    address: String,
+    dialogue: GlobalDialogue,
+    language: Language,
+    tx: tokio::sync::mpsc::Sender<SubscriptionUpdate>,
+) -> HandlerResult {
+    let chat_id = msg.chat.id;
+
+    match msg.text().map(|text| parse_label(text, language)) {
+        Some(Ok(label)) => {
+            let bytes = AccountId32::from_str(&address.clone())?.0;
+
+            tx.send(SubscriptionUpdate::UpdateSubscriptionLabel {
+                chat_id: chat_id.0,
+                bioauth_public_key: bytes,
+                label,
+            })
+            .await?;
+
+            subscription_update::transition_to_update_subscription(
+                chat_id,
+                &bot,
+                address.clone(),
+                dialogue,
+                language,
+            )
+            .await?;
+
+            bot.send_message(chat_id, tr!(language, "label-updated"))
+                .await?;
+        }
+        Some(Err(error)) => {
+            bot.send_message(
+                chat_id,
+                format!("{error} {}", tr!(language, "enter-new-label")),
+            )
+            .await?;
+        }
+        None => {
+            bot.send_message(chat_id, tr!(language, "enter-new-label"))
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn help(bot: Bot, msg: Message, language: Language) -> HandlerResult {
+    bot.send_message(msg.chat.id, i18n::help(language, Command::bot_commands()))
+        .await?;
+    Ok(())
+}
+
+pub async fn cancel(
+    bot: Bot,
+    msg: Message,
+    address: String,
+    dialogue: GlobalDialogue,
+    language: Language,
+) -> HandlerResult {
+    let chat_id = msg.chat.id;
+    bot.send_message(
+        chat_id,
+        tr!(language, "cancel-subscriptions-unchanged-message"),
+    )
+    .await?;
+
+    subscription_update::transition_to_update_subscription(
+        chat_id, &bot, address, dialogue, language,
+    )
+    .await
+}
+
+pub fn schema() -> UpdateHandler<HandlerError> {
+    let commands = teloxide::filter_command::<Command, _>()
+        .branch(dptree::case![Command::Help].endpoint(help))
+        .branch(dptree::case![Command::Cancel].endpoint(cancel));
+
+    Update::filter_message()
+        .enter_dialogue::<Message, ErasedStorage<GlobalState>, GlobalState>()
+        .branch(
+            dptree::case![GlobalState::ManageValidatorSubscriptions(x)].branch(
+                dptree::case![manage_validator_subscriptions::State::Rename { address }]
+                    .branch(commands)
+                    .endpoint(rename),
+            ),
+        )
+}
@@ -0,0 +1,332 @@
+//! The inline keyboard editor of the subscription settings.
+
+use std::str::FromStr;
+
+use subxt::utils::AccountId32;
+use teloxide::{
+    dispatching::UpdateHandler,
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup},
+    ApiError, RequestError,
+};
+
+use super::{
+    update_alert_before_expiration_in_mins::{format_alert_thresholds, validate_alert_thresholds},
+    update_max_message_frequency_in_blocks::{
+        validate_max_message_frequency_in_blocks, MAX_MESSAGE_FREQUENCY_IN_BLOCKS,
+    },
+    utils::{HandlerError, HandlerResult},
+};
+use crate::i18n::{tr, Language};
+use crate::SubscriptionUpdate;
+
+/// The prefix of the settings card callback data, distinguishing it from the other buttons.
+const CALLBACK_PREFIX: &str = "st:";
+
+const FREQUENCY_PRESETS_IN_BLOCKS: [u32; 4] = [1, 10, 100, 600];
+const FREQUENCY_STEP_IN_BLOCKS: u32 = 10;
+const ALERT_PRESETS_IN_MINS: [u64; 5] = [1440, 360, 60, 30, 10];
+
+/// The estimated block time.
+const BLOCK_TIME_IN_SECS: u64 = 6;
+
+/// The change of the subscription settings made with a settings card button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    SetFrequency(u32),
+    AddBlocks(u32),
+    SubtractBlocks(u32),
+    ToggleAlert(u64),
+}
+
+/// The reason the action is not applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionError {
+    NotSubscribed,
+    InvalidFrequency,
+    AlertRequired,
+    TooManyAlerts,
+}
+
+impl ActionError {
+    fn text(self, language: Language, address: &str) -> String {
+        match self {
+            ActionError::NotSubscribed => {
+                tr!(language, "settings-card-not-subscribed", address = address)
+            }
+            ActionError::InvalidFrequency => tr!(
+                language,
+                "max-frequency-invalid",
+                max = MAX_MESSAGE_FREQUENCY_IN_BLOCKS
+            ),
+            ActionError::AlertRequired => tr!(language, "settings-card-alert-required"),
+            ActionError::TooManyAlerts => tr!(language, "settings-card-too-many-alerts"),
+        }
+    }
+}
+
+impl Action {
+    fn encode(self) -> String {
+        match self {
+            Action::SetFrequency(in_blocks) => format!("f={in_blocks}"),
+            Action::AddBlocks(in_blocks) => format!("f+{in_blocks}"),
+            Action::SubtractBlocks(in_blocks) => format!("f-{in_blocks}"),
+            Action::ToggleAlert(in_mins) => format!("a{in_mins}"),
+        }
+    }
+
+    fn decode(action: &str) -> Option<Self> {
+        if let Some(value) = action.strip_prefix("f=") {
+            return value.parse().ok().map(Action::SetFrequency);
+        }
+        if let Some(value) = action.strip_prefix("f+") {
+            return value.parse().ok().map(Action::AddBlocks);
+        }
+        if let Some(value) = action.strip_prefix("f-") {
+            return value.parse().ok().map(Action::SubtractBlocks);
+        }
+        if let Some(value) = action.strip_prefix('a') {
+            return value.parse().ok().map(Action::ToggleAlert);
+        }
+        None
+    }
+
+    /// Apply the action to the current settings of the subscription.
+    pub fn apply(
+        self,
+        settings: &bioauth_settings::BioauthSettings,
+    ) -> Result<bioauth_settings::BioauthSettings, ActionError> {
+        let mut settings = settings.clone();
+        match self {
+            Action::SetFrequency(in_blocks) => {
+                settings.max_message_frequency_in_blocks =
+                    validate_max_message_frequency_in_blocks(in_blocks)
+                        .ok_or(ActionError::InvalidFrequency)?
+            }
+            Action::AddBlocks(in_blocks) => {
+                settings.max_message_frequency_in_blocks = settings
+                    .max_message_frequency_in_blocks
+                    .saturating_add(in_blocks)
+                    .min(MAX_MESSAGE_FREQUENCY_IN_BLOCKS)
+            }
+            Action::SubtractBlocks(in_blocks) => {
+                settings.max_message_frequency_in_blocks = settings
+                    .max_message_frequency_in_blocks
+                    .saturating_sub(in_blocks)
+                    .max(1)
+            }
+            Action::ToggleAlert(in_mins) => {
+                let mut alert_thresholds = settings.alert_before_expiration_in_mins.clone();
+                if alert_thresholds.contains(&in_mins) {
+                    if alert_thresholds.len() == 1 {
+                        return Err(ActionError::AlertRequired);
+                    }
+                    alert_thresholds.retain(|&value| value != in_mins);
+                } else {
+                    alert_thresholds.push(in_mins);
+                }
+
+                settings.alert_before_expiration_in_mins =
+                    validate_alert_thresholds(alert_thresholds)
+                        .ok_or(ActionError::TooManyAlerts)?;
+            }
+        }
+
+        Ok(settings)
+    }
+}
+
+fn callback_data(action: Action, address: &str) -> String {
+    format!("{CALLBACK_PREFIX}{}:{address}", action.encode())
+}
+
+fn parse_callback_data(data: &str) -> Option<(Action, String)> {
+    let (action, address) = data.strip_prefix(CALLBACK_PREFIX)?.split_once(':')?;
+    Some((Action::decode(action)?, address.to_owned()))
+}
+
+fn format_duration_in_blocks(in_blocks: u32, language: Language) -> String {
+    let in_secs = u64::from(in_blocks) * BLOCK_TIME_IN_SECS;
+    match in_secs {
+        0..=59 => tr!(language, "duration-secs", secs = in_secs),
+        60..=3599 => tr!(language, "duration-mins", mins = in_secs / 60),
+        _ => tr!(language, "duration-hours", hours = in_secs / 3600),
+    }
+}
+
+fn checked(text: String, is_checked: bool) -> String {
+    if is_checked {
+        format!("✅ {text}")
+    } else {
+        text
+    }
+}
+
+/// The text and the keyboard of the settings card.
+fn make_settings_card(
+    address: &str,
+    name: &str,
+    settings: &bioauth_settings::BioauthSettings,
+    language: Language,
+) -> (String, InlineKeyboardMarkup) {
+    let text = tr!(
+        language,
+        "settings-card",
+        name = name,
+        in_blocks = settings.max_message_frequency_in_blocks,
+        duration = format_duration_in_blocks(settings.max_message_frequency_in_blocks, language),
+        alert_times = format_alert_thresholds(&settings.alert_before_expiration_in_mins),
+    );
+
+    let mut keyboard: Vec<Vec<InlineKeyboardButton>> = vec![];
+
+    keyboard.push(
+        FREQUENCY_PRESETS_IN_BLOCKS
+            .iter()
+            .map(|&in_blocks| {
+                InlineKeyboardButton::callback(
+                    checked(
+                        format_duration_in_blocks(in_blocks, language),
+                        settings.max_message_frequency_in_blocks == in_blocks,
+                    ),
+                    callback_data(Action::SetFrequency(in_blocks), address),
+                )
+            })
+            .collect(),
+    );
+    keyboard.push(vec![
+        InlineKeyboardButton::callback(
+            tr!(
+                language,
+                "settings-card-subtract-blocks",
+                in_blocks = FREQUENCY_STEP_IN_BLOCKS
+            ),
+            callback_data(Action::SubtractBlocks(FREQUENCY_STEP_IN_BLOCKS), address),
+        ),
+        InlineKeyboardButton::callback(
+            tr!(
+                language,
+                "settings-card-add-blocks",
+                in_blocks = FREQUENCY_STEP_IN_BLOCKS
+            ),
+            callback_data(Action::AddBlocks(FREQUENCY_STEP_IN_BLOCKS), address),
+        ),
+    ]);
+
+    // The custom alert times are shown too, so that they can be removed.
+    let mut alert_times: Vec<u64> = ALERT_PRESETS_IN_MINS
+        .iter()
+        .chain(settings.alert_before_expiration_in_mins.iter())
+        .copied()
+        .collect();
+    alert_times = bioauth_settings::normalize_alert_thresholds(alert_times);
+
+    keyboard.extend(alert_times.chunks(5).map(|chunk| {
+        chunk
+            .iter()
+            .map(|&in_mins| {
+                InlineKeyboardButton::callback(
+                    checked(
+                        tr!(language, "duration-mins", mins = in_mins),
+                        settings.alert_before_expiration_in_mins.contains(&in_mins),
+                    ),
+                    callback_data(Action::ToggleAlert(in_mins), address),
+                )
+            })
+            .collect()
+    }));
+
+    (text, InlineKeyboardMarkup::new(keyboard))
+}
+
+/// Send the settings card of the subscription.
+pub async fn send(
+    bot: &Bot,
+    chat_id: ChatId,
+    address: &str,
+    bioauth_settings: &crate::BioauthSettings,
+    language: Language,
+) -> HandlerResult {
+    let bytes = AccountId32::from_str(address)?.0;
+    let settings = bioauth_settings.get(&(chat_id.0, bytes)).await;
+    let name = bioauth_settings.display_name(chat_id.0, bytes).await;
+
+    let (text, keyboard) = make_settings_card(address, &name, &settings, language);
+    bot.send_message(chat_id, text)
+        .reply_markup(keyboard)
+        .await?;
+
+    Ok(())
+}
+
+async fn callback_handler(
+    bot: Bot,
+    callback_query: CallbackQuery,
+    language: Language,
+    tx: tokio::sync::mpsc::Sender<SubscriptionUpdate>,
+) -> HandlerResult {
+    let Some((action, address)) = callback_query.data.as_deref().and_then(parse_callback_data)
+    else {
+        return Ok(());
+    };
+    let Some(Message { id, chat, .. }) = callback_query.message else {
+        bot.answer_callback_query(callback_query.id).await?;
+        return Ok(());
+    };
+
+    let bytes = AccountId32::from_str(&address)?.0;
+
+    // The action is applied by the main loop to the current settings, so that the quick
+    // consecutive taps are not applied to the same stale settings.
+    let (result_tx, result_rx) = tokio::sync::oneshot::channel();
+    tx.send(SubscriptionUpdate::ApplySettingsCardAction {
+        chat_id: chat.id.0,
+        bioauth_public_key: bytes,
+        action,
+        result_tx,
+    })
+    .await?;
+
+    let updated = match result_rx.await? {
+        Ok(updated) => updated,
+        Err(ActionError::NotSubscribed) => {
+            bot.answer_callback_query(callback_query.id).await?;
+            bot.edit_message_text(
+                chat.id,
+                id,
+                ActionError::NotSubscribed.text(language, &address),
+            )
+            .await?;
+            return Ok(());
+        }
+        Err(error) => {
+            bot.answer_callback_query(callback_query.id)
+                .text(error.text(language, &address))
+                .await?;
+            return Ok(());
+        }
+    };
+    bot.answer_callback_query(callback_query.id).await?;
+
+    let name = crate::bioauth_handlers::display_name(&address, updated.label.as_deref());
+    let (text, keyboard) = make_settings_card(&address, &name, &updated, language);
+    match bot
+        .edit_message_text(chat.id, id, text)
+        .reply_markup(keyboard)
+        .await
+    {
+        Ok(_) | Err(RequestError::Api(ApiError::MessageNotModified)) => Ok(()),
+        Err(error) => Err(error.into()),
+    }
+}
+
+pub fn schema() -> UpdateHandler<HandlerError> {
+    Update::filter_callback_query()
+        .filter(|callback_query: CallbackQuery| {
+            callback_query
+                .data
+                .as_deref()
+                .is_some_and(|data| data.starts_with(CALLBACK_PREFIX))
+        })
+        .endpoint(callback_handler)
+}
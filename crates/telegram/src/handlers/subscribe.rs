@@ -1,4 +1,6 @@
+use std::collections::HashSet;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use subxt::utils::AccountId32;
 use teloxide::dispatching::dialogue::ErasedStorage;
@@ -14,6 +16,7 @@ use crate::SubscriptionUpdate;
 use super::manage_validator_subscriptions;
 use super::utils::{set_local_commands, HandlerError, HandlerResult};
 use super::GlobalDialogue;
+use crate::i18n::{self, tr, Language};
 
 #[derive(BotCommands, Clone, Debug)]
 #[command(
@@ -31,109 +34,184 @@ pub async fn transition_to_subscribe(
     chat_id: ChatId,
     bot: &Bot,
     dialogue: GlobalDialogue,
+    language: Language,
 ) -> HandlerResult {
     dialogue
         .update(GlobalState::ManageValidatorSubscriptions(
             manage_validator_subscriptions::State::Subscribe,
         ))
         .await?;
-    set_local_commands(chat_id, bot, Command::bot_commands()).await
+    set_local_commands(chat_id, bot, Command::bot_commands(), language).await
 }
 
-const COMMAND_MESSAGE: &str = {
-    "
-Enter the validator address (must start with 'hm..'), or use /help command to display bot usage instructions.
-"
-};
-
 pub async fn command(
     bot: Bot,
     msg: Message,
     message_data: (ChatId, MessageId),
     dialogue: GlobalDialogue,
+    language: Language,
 ) -> HandlerResult {
-    bot.send_message(msg.chat.id, COMMAND_MESSAGE).await?;
+    bot.send_message(msg.chat.id, tr!(language, "subscribe-message"))
+        .await?;
 
     bot.edit_message_text(
         message_data.0,
         message_data.1,
-        "Subscription action is activated",
+        tr!(language, "subscribe-activated"),
     )
     .await?;
 
-    transition_to_subscribe(msg.chat.id, &bot, dialogue).await
+    transition_to_subscribe(msg.chat.id, &bot, dialogue, language).await
 }
 
-const SUBSCRIBED_MESSAGE: &str = {
-    "
-Validator address successfully added.
-You will now receive notifications according to your settings.
-You can manage the settings for this subscription.
+/// The maximum number of addresses accepted in a single message.
+const MAX_ADDRESSES: usize = 50;
 
-Use /help command to display bot usage instructions
-"
-};
+/// Split the message into the addresses separated by newlines, commas or spaces.
+fn split_addresses(text: &str) -> Vec<&str> {
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|address| !address.is_empty())
+        .collect()
+}
 
 pub async fn receive_address(
     msg: Message,
     bot: Bot,
     dialogue: GlobalDialogue,
+    language: Language,
+    bioauth_settings: Arc<crate::BioauthSettings>,
     tx: tokio::sync::mpsc::Sender<SubscriptionUpdate>,
 ) -> HandlerResult {
     let chat_id = msg.chat.id;
-    let text = msg.text();
-    let address = {
-        let text = match text {
-            Some(text) => text,
-            None => {
-                bot.send_message(msg.chat.id, "Enter address").await?;
+    let inputs = match msg.text().map(split_addresses) {
+        Some(inputs) if !inputs.is_empty() => inputs,
+        _ => {
+            bot.send_message(msg.chat.id, tr!(language, "enter-address"))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    if inputs.len() > MAX_ADDRESSES {
+        bot.send_message(
+            msg.chat.id,
+            tr!(language, "too-many-addresses", max = MAX_ADDRESSES),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    // Subscribing again would reset the settings of the subscription.
+    let already_subscribed = bioauth_settings.get_all_subscription_keys(chat_id.0).await;
+
+    if let [text] = inputs[..] {
+        let address = match AccountId32::from_str(text) {
+            Ok(val) => val,
+            Err(error) => {
+                bot.send_message(
+                    msg.chat.id,
+                    tr!(language, "invalid-address", error = error.to_string()),
+                )
+                .await?;
                 return Ok(());
             }
         };
+
+        if already_subscribed.contains(&address.0) {
+            bot.send_message(msg.chat.id, tr!(language, "already-subscribed-message"))
+                .await?;
+        } else {
+            tx.send(SubscriptionUpdate::SubscribeToValidator {
+                chat_id: chat_id.0,
+                bioauth_public_key: address.0,
+            })
+            .await?;
+
+            bot.send_message(msg.chat.id, tr!(language, "subscribed-message"))
+                .await?;
+        }
+        return transition_to_update_subscription(
+            chat_id,
+            &bot,
+            text.to_owned(),
+            dialogue,
+            language,
+        )
+        .await;
+    }
+
+    let mut subscribed = HashSet::new();
+    let mut has_valid_addresses = false;
+    let mut report = Vec::with_capacity(inputs.len());
+    for text in inputs {
         match AccountId32::from_str(text) {
-            Ok(val) => val,
-            Err(error) => {
-                bot.send_message(msg.chat.id, format!("Invalid address {}", error))
+            Ok(address) => {
+                has_valid_addresses = true;
+                if already_subscribed.contains(&address.0) {
+                    report.push(format!(
+                        "➖ {text}: {}",
+                        tr!(language, "already-subscribed")
+                    ));
+                } else if subscribed.insert(address.0) {
+                    tx.send(SubscriptionUpdate::SubscribeToValidator {
+                        chat_id: chat_id.0,
+                        bioauth_public_key: address.0,
+                    })
                     .await?;
-                return Ok(());
+                    report.push(format!("✅ {text}"));
+                } else {
+                    report.push(format!("➖ {text}: {}", tr!(language, "duplicate-address")));
+                }
             }
+            Err(error) => report.push(format!(
+                "❌ {text}: {}",
+                tr!(language, "invalid-address", error = error.to_string())
+            )),
         }
-    };
+    }
 
-    tx.send(SubscriptionUpdate::SubscribeToValidator {
-        chat_id: chat_id.0,
-        bioauth_public_key: address.0,
-    })
+    bot.send_message(
+        msg.chat.id,
+        format!(
+            "{}\n\n{}",
+            tr!(
+                language,
+                "subscribe-report",
+                subscribed = subscribed.len(),
+                total = report.len()
+            ),
+            report.join("\n")
+        ),
+    )
     .await?;
 
-    if let Some(address) = text {
-        bot.send_message(msg.chat.id, SUBSCRIBED_MESSAGE).await?;
-        transition_to_update_subscription(chat_id, &bot, address.to_owned(), dialogue).await?;
+    if !has_valid_addresses {
+        bot.send_message(msg.chat.id, tr!(language, "enter-valid-addresses"))
+            .await?;
+        return Ok(());
     }
 
-    Ok(())
+    super::transition_to_start(chat_id, &bot, dialogue, language).await
 }
 
-async fn help(bot: Bot, msg: Message) -> HandlerResult {
-    bot.send_message(msg.chat.id, Command::descriptions().to_string())
+async fn help(bot: Bot, msg: Message, language: Language) -> HandlerResult {
+    bot.send_message(msg.chat.id, i18n::help(language, Command::bot_commands()))
         .await?;
     Ok(())
 }
 
-const CANCEL_MESSAGE: &str = {
-    "
-You have canceled the action.
-
-use /help command to display bot usage instructions.
-"
-};
-
-pub async fn cancel(bot: Bot, msg: Message, dialogue: GlobalDialogue) -> HandlerResult {
-    bot.send_message(msg.chat.id, CANCEL_MESSAGE).await?;
+pub async fn cancel(
+    bot: Bot,
+    msg: Message,
+    dialogue: GlobalDialogue,
+    language: Language,
+) -> HandlerResult {
+    bot.send_message(msg.chat.id, tr!(language, "cancel-message"))
+        .await?;
 
     dialogue.exit().await?;
 
-    set_local_commands(msg.chat.id, &bot, super::Command::bot_commands()).await
+    set_local_commands(msg.chat.id, &bot, super::Command::bot_commands(), language).await
 }
 
 pub fn schema() -> UpdateHandler<HandlerError> {
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use teloxide::{
     dispatching::{dialogue::ErasedStorage, UpdateHandler},
     prelude::*,
@@ -5,13 +7,14 @@ use teloxide::{
 };
 
 use super::{
-    unsubscribe, update_alert_before_expiration_in_mins, update_max_message_frequency_in_blocks,
-    State as GlobalState,
+    rename, settings_card, unsubscribe, update_alert_before_expiration_in_mins,
+    update_max_message_frequency_in_blocks, State as GlobalState,
 };
 
 use super::manage_validator_subscriptions;
 use super::utils::{set_local_commands, HandlerError, HandlerResult};
 use super::GlobalDialogue;
+use crate::i18n::{self, tr, Language};
 
 #[derive(BotCommands, Clone, Debug)]
 #[command(
@@ -33,12 +36,20 @@ pub enum UpdateMaxMessageFrequencyInBlocksCommand {
 pub enum Command {
     #[command(description = "display this text")]
     Help,
+    #[command(
+        description = "show the settings of this subscription with the buttons to change them"
+    )]
+    Settings,
     #[command(
         description = "update the maximum message frequency (in block est. ~6sec per block) for this subscription"
     )]
     UpdateMaxMessageFrequency,
-    #[command(description = "adjust the alert time (in minutes) before losing validator status")]
+    #[command(
+        description = "adjust the alert times (in minutes) before losing validator status, e.g. 1440, 60, 10"
+    )]
     UpdateAlertBefore,
+    #[command(description = "set the label shown instead of the address for this subscription")]
+    Rename,
     #[command(description = "unsubscribe from this subscription")]
     Unsubscribe,
     #[command(description = "cancel the operation")]
@@ -50,6 +61,7 @@ pub async fn transition_to_update_subscription(
     bot: &Bot,
     address: String,
     dialogue: GlobalDialogue,
+    language: Language,
 ) -> HandlerResult {
     dialogue
         .update(GlobalState::ManageValidatorSubscriptions(
@@ -58,33 +70,42 @@ pub async fn transition_to_update_subscription(
             },
         ))
         .await?;
-    set_local_commands(chat_id, bot, Command::bot_commands()).await
+    set_local_commands(chat_id, bot, Command::bot_commands(), language).await
 }
 
-async fn help(bot: Bot, msg: Message) -> HandlerResult {
-    bot.send_message(msg.chat.id, Command::descriptions().to_string())
+async fn help(bot: Bot, msg: Message, language: Language) -> HandlerResult {
+    bot.send_message(msg.chat.id, i18n::help(language, Command::bot_commands()))
         .await?;
     Ok(())
 }
 
-const CANCEL_MESSAGE: &str = {
-    "
-You have canceled the action.
-
-use /help command to display bot usage instructions.
-"
-};
+async fn settings(
+    bot: Bot,
+    msg: Message,
+    address: String,
+    language: Language,
+    bioauth_settings: Arc<crate::BioauthSettings>,
+) -> HandlerResult {
+    settings_card::send(&bot, msg.chat.id, &address, &bioauth_settings, language).await
+}
 
-pub async fn cancel(bot: Bot, msg: Message, dialogue: GlobalDialogue) -> HandlerResult {
+pub async fn cancel(
+    bot: Bot,
+    msg: Message,
+    dialogue: GlobalDialogue,
+    language: Language,
+) -> HandlerResult {
     let chat_id = msg.chat.id;
-    bot.send_message(chat_id, CANCEL_MESSAGE).await?;
+    bot.send_message(chat_id, tr!(language, "cancel-message"))
+        .await?;
 
-    super::transition_to_start(chat_id, &bot, dialogue).await
+    super::transition_to_start(chat_id, &bot, dialogue, language).await
 }
 
 pub fn schema() -> UpdateHandler<HandlerError> {
     let update_commands = teloxide::filter_command::<Command, _>()
         .branch(dptree::case![Command::Help].endpoint(help))
+        .branch(dptree::case![Command::Settings].endpoint(settings))
         .branch(
             dptree::case![Command::UpdateAlertBefore]
                 .endpoint(update_alert_before_expiration_in_mins::command),
@@ -93,6 +114,7 @@ pub fn schema() -> UpdateHandler<HandlerError> {
             dptree::case![Command::UpdateMaxMessageFrequency]
                 .endpoint(update_max_message_frequency_in_blocks::command),
         )
+        .branch(dptree::case![Command::Rename].endpoint(rename::command))
         .branch(dptree::case![Command::Unsubscribe].endpoint(unsubscribe::command))
         .branch(dptree::case![Command::Cancel].endpoint(cancel));
 
@@ -109,4 +131,5 @@ pub fn schema() -> UpdateHandler<HandlerError> {
         .branch(unsubscribe::schema())
         .branch(update_alert_before_expiration_in_mins::schema())
         .branch(update_max_message_frequency_in_blocks::schema())
+        .branch(rename::schema())
 }
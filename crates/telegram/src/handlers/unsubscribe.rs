@@ -12,6 +12,7 @@ use crate::SubscriptionUpdate;
 use super::manage_validator_subscriptions;
 use super::utils::{set_local_commands, HandlerError, HandlerResult};
 use super::GlobalDialogue;
+use crate::i18n::{self, tr, Language};
 
 #[derive(BotCommands, Clone, Debug)]
 #[command(
@@ -30,13 +31,14 @@ pub async fn transition_to_unsubscribe(
     bot: &Bot,
     address: String,
     dialogue: GlobalDialogue,
+    language: Language,
 ) -> HandlerResult {
     dialogue
         .update(GlobalState::ManageValidatorSubscriptions(
             manage_validator_subscriptions::State::Unsubscribe { address },
         ))
         .await?;
-    set_local_commands(chat_id, bot, Command::bot_commands()).await
+    set_local_commands(chat_id, bot, Command::bot_commands(), language).await
 }
 
 pub async fn command(
@@ -44,17 +46,15 @@ pub async fn command(
     bot: Bot,
     address: String,
     dialogue: GlobalDialogue,
+    language: Language,
 ) -> HandlerResult {
     bot.send_message(
         msg.chat.id,
-        format!(
-            "Enter the 'agree' to confirm to unsubscribe from {},\n\nuse /help command to display bot usage instructions.",
-            address
-        ),
+        tr!(language, "unsubscribe-confirm", address = address.clone()),
     )
     .await?;
 
-    transition_to_unsubscribe(msg.chat.id, &bot, address.clone(), dialogue).await
+    transition_to_unsubscribe(msg.chat.id, &bot, address, dialogue, language).await
 }
 
 pub async fn unsubscribe(
@@ -62,6 +62,7 @@ pub async fn unsubscribe(
     bot: Bot,
     address: String,
     dialogue: GlobalDialogue,
+    language: Language,
     tx: tokio::sync::mpsc::Sender<SubscriptionUpdate>,
 ) -> HandlerResult {
     let chat_id = msg.chat.id;
@@ -80,47 +81,43 @@ pub async fn unsubscribe(
             &bot,
             address.clone(),
             dialogue,
+            language,
         )
         .await?;
-        bot.send_message(chat_id, "Successfully unsubscribed.")
+        bot.send_message(chat_id, tr!(language, "unsubscribed"))
             .await?;
     } else {
-        bot.send_message(
-            chat_id,
-            "Please enter the word 'agree' to confirm the operation.",
-        )
-        .await?;
+        bot.send_message(chat_id, tr!(language, "unsubscribe-enter-agree"))
+            .await?;
     }
 
     Ok(())
 }
 
-async fn help(bot: Bot, msg: Message) -> HandlerResult {
-    bot.send_message(msg.chat.id, Command::descriptions().to_string())
+async fn help(bot: Bot, msg: Message, language: Language) -> HandlerResult {
+    bot.send_message(msg.chat.id, i18n::help(language, Command::bot_commands()))
         .await?;
     Ok(())
 }
 
-const CANCEL_MESSAGE: &str = {
-    "
-You have canceled the action.
-
-Your validator subscriptions remain unchanged.
-
-use /help command to display bot usage instructions.
-"
-};
-
 async fn cancel(
     bot: Bot,
     msg: Message,
     address: String,
     dialogue: GlobalDialogue,
+    language: Language,
 ) -> HandlerResult {
     let chat_id = msg.chat.id;
-    bot.send_message(chat_id, CANCEL_MESSAGE).await?;
+    bot.send_message(
+        chat_id,
+        tr!(language, "cancel-subscriptions-unchanged-message"),
+    )
+    .await?;
 
-    subscription_update::transition_to_update_subscription(chat_id, &bot, address, dialogue).await
+    subscription_update::transition_to_update_subscription(
+        chat_id, &bot, address, dialogue, language,
+    )
+    .await
 }
 
 pub fn schema() -> UpdateHandler<HandlerError> {
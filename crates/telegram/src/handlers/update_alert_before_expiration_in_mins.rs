@@ -13,6 +13,7 @@ use crate::SubscriptionUpdate;
 use super::manage_validator_subscriptions;
 use super::utils::{set_local_commands, HandlerError, HandlerResult};
 use super::GlobalDialogue;
+use crate::i18n::{self, tr, Language};
 
 #[derive(BotCommands, Clone, Debug)]
 #[command(
@@ -31,26 +32,63 @@ pub async fn transition_to_update_alert_before_expiration_in_mins(
     bot: &Bot,
     address: String,
     dialogue: GlobalDialogue,
+    language: Language,
 ) -> HandlerResult {
     dialogue
         .update(GlobalState::ManageValidatorSubscriptions(
             manage_validator_subscriptions::State::UpdateAlertBeforeExpirationInMins { address },
         ))
         .await?;
-    set_local_commands(chat_id, bot, Command::bot_commands()).await
+    set_local_commands(chat_id, bot, Command::bot_commands(), language).await
 }
 
-const COMMAND_MESSAGE: &str = {
-    "
-Enter the new value for the alert time in minutes.
-"
-};
+/// The maximum number of the staged alerts per subscription.
+const MAX_ALERT_THRESHOLDS: usize = 10;
+
+/// The earliest alert, a week before the expiration.
+const MAX_ALERT_THRESHOLD_IN_MINS: u64 = 7 * 24 * 60;
+
+fn parse_alert_thresholds(text: &str) -> Option<Vec<u64>> {
+    let alert_thresholds = text
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|value| !value.is_empty())
+        .map(|value| value.parse::<u64>().ok())
+        .collect::<Option<Vec<u64>>>()?;
+
+    validate_alert_thresholds(alert_thresholds)
+}
+
+/// Normalize the alert thresholds, `None` if they are out of range or there are too many of them.
+pub(crate) fn validate_alert_thresholds(alert_thresholds: Vec<u64>) -> Option<Vec<u64>> {
+    if alert_thresholds
+        .iter()
+        .any(|in_mins| !(1..=MAX_ALERT_THRESHOLD_IN_MINS).contains(in_mins))
+    {
+        return None;
+    }
+
+    let alert_thresholds = bioauth_settings::normalize_alert_thresholds(alert_thresholds);
+    if alert_thresholds.is_empty() || alert_thresholds.len() > MAX_ALERT_THRESHOLDS {
+        return None;
+    }
+
+    Some(alert_thresholds)
+}
+
+pub fn format_alert_thresholds(alert_thresholds: &[u64]) -> String {
+    alert_thresholds
+        .iter()
+        .map(|in_mins| in_mins.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
 
 pub async fn command(
     msg: Message,
     bot: Bot,
     address: String,
     dialogue: GlobalDialogue,
+    language: Language,
     bioauth_settings: Arc<crate::BioauthSettings>,
 ) -> HandlerResult {
     let chat_id = msg.chat.id;
@@ -58,15 +96,18 @@ pub async fn command(
     let settings = bioauth_settings.get(&(chat_id.0, bytes)).await;
     bot.send_message(
         chat_id,
-        format!(
-            "Current alert time is for {}: {} mins",
-            address, settings.alert_before_expiration_in_mins,
+        tr!(
+            language,
+            "alert-times-current",
+            address = address.clone(),
+            in_mins = format_alert_thresholds(&settings.alert_before_expiration_in_mins),
         ),
     )
     .await?;
-    bot.send_message(msg.chat.id, COMMAND_MESSAGE).await?;
+    bot.send_message(msg.chat.id, tr!(language, "alert-times-message"))
+        .await?;
 
-    transition_to_update_alert_before_expiration_in_mins(chat_id, &bot, address.clone(), dialogue)
+    transition_to_update_alert_before_expiration_in_mins(chat_id, &bot, address, dialogue, language)
         .await
 }
 
@@ -75,11 +116,12 @@ async fn update_alert_before_expiration_in_mins(
     bot: Bot,
     address: String,
     dialogue: GlobalDialogue,
+    language: Language,
     tx: tokio::sync::mpsc::Sender<SubscriptionUpdate>,
 ) -> HandlerResult {
     let chat_id = msg.chat.id;
-    match msg.text().map(|text| text.parse::<u64>()) {
-        Some(Ok(in_mins)) => {
+    match msg.text().and_then(parse_alert_thresholds) {
+        Some(in_mins) => {
             let bytes = AccountId32::from_str(&address.clone())?.0;
 
             tx.send(
@@ -96,46 +138,54 @@ async fn update_alert_before_expiration_in_mins(
                 &bot,
                 address.clone(),
                 dialogue,
+                language,
             )
             .await?;
 
-            bot.send_message(chat_id, "Alert time updated.").await?;
-        }
-        _ => {
-            bot.send_message(msg.chat.id, "Enter the new value.")
+            bot.send_message(chat_id, tr!(language, "alert-times-updated"))
                 .await?;
         }
+        None => {
+            bot.send_message(
+                msg.chat.id,
+                tr!(
+                    language,
+                    "alert-times-invalid",
+                    max = MAX_ALERT_THRESHOLDS,
+                    max_mins = MAX_ALERT_THRESHOLD_IN_MINS
+                ),
+            )
+            .await?;
+        }
     }
 
     Ok(())
 }
 
-async fn help(bot: Bot, msg: Message) -> HandlerResult {
-    bot.send_message(msg.chat.id, Command::descriptions().to_string())
+async fn help(bot: Bot, msg: Message, language: Language) -> HandlerResult {
+    bot.send_message(msg.chat.id, i18n::help(language, Command::bot_commands()))
         .await?;
     Ok(())
 }
 
-const CANCEL_MESSAGE: &str = {
-    "
-You have canceled the action.
-
-Your validator subscriptions remain unchanged.
-
-use /help command to display bot usage instructions.
-"
-};
-
 async fn cancel(
     bot: Bot,
     msg: Message,
     address: String,
     dialogue: GlobalDialogue,
+    language: Language,
 ) -> HandlerResult {
     let chat_id = msg.chat.id;
-    bot.send_message(chat_id, CANCEL_MESSAGE).await?;
+    bot.send_message(
+        chat_id,
+        tr!(language, "cancel-subscriptions-unchanged-message"),
+    )
+    .await?;
 
-    subscription_update::transition_to_update_subscription(chat_id, &bot, address, dialogue).await
+    subscription_update::transition_to_update_subscription(
+        chat_id, &bot, address, dialogue, language,
+    )
+    .await
 }
 
 pub fn schema() -> UpdateHandler<HandlerError> {
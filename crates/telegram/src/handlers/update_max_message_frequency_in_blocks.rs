@@ -13,6 +13,7 @@ use crate::SubscriptionUpdate;
 use super::manage_validator_subscriptions;
 use super::utils::{set_local_commands, HandlerError, HandlerResult};
 use super::GlobalDialogue;
+use crate::i18n::{self, tr, Language};
 
 #[derive(BotCommands, Clone, Debug)]
 #[command(
@@ -26,31 +27,37 @@ pub enum Command {
     Cancel,
 }
 
+/// The rarest repeat of the notification, roughly once a day.
+pub(crate) const MAX_MESSAGE_FREQUENCY_IN_BLOCKS: u32 = 14400;
+
+/// `None` if the maximum message frequency is out of range.
+pub(crate) fn validate_max_message_frequency_in_blocks(in_blocks: u32) -> Option<u32> {
+    (1..=MAX_MESSAGE_FREQUENCY_IN_BLOCKS)
+        .contains(&in_blocks)
+        .then_some(in_blocks)
+}
+
 pub async fn transition_to_update_max_message_frequency_in_blocks(
     chat_id: ChatId,
     bot: &Bot,
     address: String,
     dialogue: GlobalDialogue,
+    language: Language,
 ) -> HandlerResult {
     dialogue
         .update(GlobalState::ManageValidatorSubscriptions(
             manage_validator_subscriptions::State::UpdateMaxMessageFrequencyInBlocks { address },
         ))
         .await?;
-    set_local_commands(chat_id, bot, Command::bot_commands()).await
+    set_local_commands(chat_id, bot, Command::bot_commands(), language).await
 }
 
-const COMMAND_MESSAGE: &str = {
-    "
-Enter the new value of the maximum message frequency in blocks (where each block is roughly equivalent to 6 seconds).
-"
-};
-
 pub async fn command(
     msg: Message,
     bot: Bot,
     address: String,
     dialogue: GlobalDialogue,
+    language: Language,
     bioauth_settings: Arc<crate::BioauthSettings>,
 ) -> HandlerResult {
     let chat_id = msg.chat.id;
@@ -58,15 +65,18 @@ pub async fn command(
     let settings = bioauth_settings.get(&(chat_id.0, bytes)).await;
     bot.send_message(
         chat_id,
-        format!(
-            "Current maximum message frequency for {}: {} blocks",
-            address, settings.max_message_frequency_in_blocks
+        tr!(
+            language,
+            "max-frequency-current",
+            address = address.clone(),
+            in_blocks = settings.max_message_frequency_in_blocks
         ),
     )
     .await?;
-    bot.send_message(chat_id, COMMAND_MESSAGE).await?;
+    bot.send_message(chat_id, tr!(language, "max-frequency-message"))
+        .await?;
 
-    transition_to_update_max_message_frequency_in_blocks(chat_id, &bot, address.clone(), dialogue)
+    transition_to_update_max_message_frequency_in_blocks(chat_id, &bot, address, dialogue, language)
         .await
 }
 
@@ -75,12 +85,17 @@ pub async fn update_max_message_frequency_in_blocks(
     bot: Bot,
     address: String,
     dialogue: GlobalDialogue,
+    language: Language,
     tx: tokio::sync::mpsc::Sender<SubscriptionUpdate>,
 ) -> HandlerResult {
     let chat_id = msg.chat.id;
 
-    match msg.text().map(|text| text.parse::<u32>()) {
-        Some(Ok(in_blocks)) => {
+    match msg
+        .text()
+        .and_then(|text| text.trim().parse::<u32>().ok())
+        .and_then(validate_max_message_frequency_in_blocks)
+    {
+        Some(in_blocks) => {
             let bytes = AccountId32::from_str(&address.clone())?.0;
 
             tx.send(
@@ -97,47 +112,53 @@ pub async fn update_max_message_frequency_in_blocks(
                 &bot,
                 address.clone(),
                 dialogue,
+                language,
             )
             .await?;
 
-            bot.send_message(chat_id, "Maximum message frequency updated.")
+            bot.send_message(chat_id, tr!(language, "max-frequency-updated"))
                 .await?;
         }
-        _ => {
-            bot.send_message(msg.chat.id, "Enter the new value.")
-                .await?;
+        None => {
+            bot.send_message(
+                msg.chat.id,
+                tr!(
+                    language,
+                    "max-frequency-invalid",
+                    max = MAX_MESSAGE_FREQUENCY_IN_BLOCKS
+                ),
+            )
+            .await?;
         }
     }
 
     Ok(())
 }
 
-async fn help(bot: Bot, msg: Message) -> HandlerResult {
-    bot.send_message(msg.chat.id, Command::descriptions().to_string())
+async fn help(bot: Bot, msg: Message, language: Language) -> HandlerResult {
+    bot.send_message(msg.chat.id, i18n::help(language, Command::bot_commands()))
         .await?;
     Ok(())
 }
 
-const CANCEL_MESSAGE: &str = {
-    "
-You have canceled the action.
-
-Your validator subscriptions remain unchanged.
-
-use /help command to display bot usage instructions.
-"
-};
-
 pub async fn cancel(
     bot: Bot,
     msg: Message,
     address: String,
     dialogue: GlobalDialogue,
+    language: Language,
 ) -> HandlerResult {
     let chat_id = msg.chat.id;
-    bot.send_message(chat_id, CANCEL_MESSAGE).await?;
+    bot.send_message(
+        chat_id,
+        tr!(language, "cancel-subscriptions-unchanged-message"),
+    )
+    .await?;
 
-    subscription_update::transition_to_update_subscription(chat_id, &bot, address, dialogue).await
+    subscription_update::transition_to_update_subscription(
+        chat_id, &bot, address, dialogue, language,
+    )
+    .await
 }
 
 pub fn schema() -> UpdateHandler<HandlerError> {
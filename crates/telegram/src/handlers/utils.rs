@@ -4,6 +4,8 @@ use teloxide::{
     Bot,
 };
 
+use crate::i18n::{self, Language};
+
 pub type HandlerError = Box<dyn std::error::Error + Send + Sync>;
 pub type HandlerResult = Result<(), HandlerError>;
 
@@ -12,9 +14,10 @@ pub async fn set_local_commands(
     chat_id: ChatId,
     bot: &Bot,
     commands: Vec<BotCommand>,
+    language: Language,
 ) -> HandlerResult {
     let chat_id = chat_id.into();
-    bot.set_my_commands(commands)
+    bot.set_my_commands(i18n::localize_commands(language, commands))
         .scope(BotCommandScope::Chat { chat_id })
         .send()
         .await?;
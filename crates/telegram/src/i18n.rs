@@ -0,0 +1,159 @@
+//! The localization of the bot texts.
+
+use std::{collections::HashMap, sync::OnceLock};
+
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource};
+use teloxide::types::BotCommand;
+
+/// Format the localized text, e.g. `tr!(language, "cancel-message")` or
+/// `tr!(language, "label-updated", label = label)`.
+macro_rules! tr {
+    ($language:expr, $id:expr $(,)?) => {
+        $crate::i18n::text($language, $id, None)
+    };
+    ($language:expr, $id:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::text($language, $id, Some(&args))
+    }};
+}
+pub(crate) use tr;
+
+/// The language of the bot texts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Language {
+    #[default]
+    En,
+    Ru,
+    Es,
+    Id,
+}
+
+impl Language {
+    pub const ALL: [Language; 4] = [Language::En, Language::Ru, Language::Es, Language::Id];
+
+    /// The IETF language tag, as sent by Telegram in the user `language_code`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::En => "en",
+            Language::Ru => "ru",
+            Language::Es => "es",
+            Language::Id => "id",
+        }
+    }
+
+    /// The name of the language in itself.
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::En => "English",
+            Language::Ru => "Русский",
+            Language::Es => "Español",
+            Language::Id => "Bahasa Indonesia",
+        }
+    }
+
+    /// The supported language by its tag, the region is ignored, e.g. `es-AR` is Spanish.
+    pub fn from_code(code: &str) -> Option<Self> {
+        let primary = code.split(['-', '_']).next()?.to_ascii_lowercase();
+        Language::ALL
+            .into_iter()
+            .find(|language| language.code() == primary)
+    }
+
+    /// The language chosen for the chat, or detected from the user, English otherwise.
+    pub fn resolve(chat_language: Option<&str>, user_language_code: Option<&str>) -> Self {
+        chat_language
+            .and_then(Language::from_code)
+            .or_else(|| user_language_code.and_then(Language::from_code))
+            .unwrap_or_default()
+    }
+
+    fn catalog(self) -> &'static str {
+        match self {
+            Language::En => include_str!("../locales/en.ftl"),
+            Language::Ru => include_str!("../locales/ru.ftl"),
+            Language::Es => include_str!("../locales/es.ftl"),
+            Language::Id => include_str!("../locales/id.ftl"),
+        }
+    }
+}
+
+type Bundle = FluentBundle<FluentResource>;
+
+static BUNDLES: OnceLock<HashMap<Language, Bundle>> = OnceLock::new();
+
+fn bundles() -> &'static HashMap<Language, Bundle> {
+    BUNDLES.get_or_init(|| {
+        Language::ALL
+            .into_iter()
+            .map(|language| {
+                let resource = FluentResource::try_new(language.catalog().to_owned())
+                    .unwrap_or_else(|(_, errors)| {
+                        panic!("invalid {} catalog: {errors:?}", language.code())
+                    });
+
+                let langid = language.code().parse().expect("the language code is valid");
+                let mut bundle = Bundle::new_concurrent(vec![langid]);
+                // The isolation marks break the addresses and the commands in the texts.
+                bundle.set_use_isolating(false);
+                bundle.add_resource(resource).unwrap_or_else(|errors| {
+                    panic!("duplicate {} catalog messages: {errors:?}", language.code())
+                });
+
+                (language, bundle)
+            })
+            .collect()
+    })
+}
+
+fn format(language: Language, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+    let bundle = bundles().get(&language)?;
+    let pattern = bundle.get_message(id)?.value()?;
+
+    let mut errors = vec![];
+    let text = bundle.format_pattern(pattern, args, &mut errors);
+    if !errors.is_empty() {
+        tracing::warn!(
+            message = "catalog message format errors",
+            ?language,
+            id,
+            ?errors
+        );
+    }
+
+    Some(text.into_owned())
+}
+
+/// The text of the catalog message, the English one if it is not translated.
+pub fn text(language: Language, id: &str, args: Option<&FluentArgs>) -> String {
+    format(language, id, args)
+        .or_else(|| format(Language::En, id, args))
+        .unwrap_or_else(|| {
+            tracing::error!(message = "missing catalog message", id);
+            id.to_owned()
+        })
+}
+
+/// Replace the command descriptions with the localized ones.
+pub fn localize_commands(language: Language, commands: Vec<BotCommand>) -> Vec<BotCommand> {
+    commands
+        .into_iter()
+        .map(|command| {
+            let id = format!("command-{}", command.command);
+            match format(language, &id, None).or_else(|| format(Language::En, &id, None)) {
+                Some(description) => BotCommand::new(command.command, description),
+                None => command,
+            }
+        })
+        .collect()
+}
+
+/// The localized list of the commands, the replacement of `BotCommands::descriptions`.
+pub fn help(language: Language, commands: Vec<BotCommand>) -> String {
+    let lines: Vec<String> = localize_commands(language, commands)
+        .into_iter()
+        .map(|command| format!("/{} — {}", command.command, command.description))
+        .collect();
+
+    format!("{}\n\n{}", tr!(language, "help-header"), lines.join("\n"))
+}
@@ -7,11 +7,19 @@
 
 pub mod bioauth_handlers;
 mod handlers;
+mod i18n;
+pub mod outbound;
+mod templates;
 
 use bioauth_handlers::SendNotificationError;
+use bioauth_logic::{DeliveryError, FailedNotification};
 use derivative::Derivative;
+pub use handlers::settings_card::{
+    Action as SettingsCardAction, ActionError as SettingsCardActionError,
+};
 use handlers::State as GlobalState;
 use sp_core::crypto::{Ss58AddressFormatRegistry, Ss58Codec};
+use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::sync::Arc;
 use teloxide::dispatching::dialogue::ErasedStorage;
@@ -32,7 +40,19 @@ pub struct Telegram {
     pub rw_bioauth_settings_map:
         Arc<tokio::sync::RwLock<bioauth_settings::BioauthSettingsMap<[u8; 32]>>>,
     pub rw_dev_subscriptions_map: Arc<tokio::sync::RwLock<dev_subscriptions::DevSubscriptionMap>>,
+    pub rw_active_authentications: Arc<tokio::sync::RwLock<ActiveAuthentications>>,
     pub admin_chat_ids: Vec<i64>,
+    /// The block explorer address page URL with the `{address}` placeholder.
+    pub explorer_url: String,
+}
+
+/// The active authentications of the latest processed block, shared by the main loop.
+#[derive(Debug, Default)]
+pub struct ActiveAuthentications {
+    /// The block the snapshot is taken at, `None` until the first block is processed.
+    pub block_number: Option<u32>,
+    /// The authentication expiration moments in milliseconds by the validator public keys.
+    pub active_authentications_map: HashMap<[u8; 32], u64>,
 }
 
 #[derive(Debug)]
@@ -48,13 +68,19 @@ pub enum SubscriptionUpdate {
     UpdateSubscriptionAlertBeforeExpirationInMins {
         chat_id: i64,
         bioauth_public_key: [u8; 32],
-        in_mins: u64,
+        in_mins: Vec<u64>,
     },
     UpdateSubscriptionMaxMessageFrequencyInBlocks {
         chat_id: i64,
         bioauth_public_key: [u8; 32],
         in_blocks: u32,
     },
+    /// Set the validator label, `None` removes it.
+    UpdateSubscriptionLabel {
+        chat_id: i64,
+        bioauth_public_key: [u8; 32],
+        label: Option<String>,
+    },
     RemoveAllValidatorSubscriptions {
         chat_id: i64,
     },
@@ -64,6 +90,62 @@ pub enum SubscriptionUpdate {
     AffectedValidatorDisable {
         chat_id: i64,
     },
+    RemoveDevSubscriptions {
+        chat_id: i64,
+    },
+    /// The group chat is migrated to a supergroup with the new id.
+    MigrateChat {
+        from_chat_id: i64,
+        to_chat_id: i64,
+    },
+    /// Set the offset of the chat local time from UTC.
+    UpdateTimezone {
+        chat_id: i64,
+        timezone_offset_in_mins: i32,
+    },
+    /// Set the quiet hours of the chat, `None` disables them.
+    UpdateQuietHours {
+        chat_id: i64,
+        quiet_hours: Option<bioauth_settings::QuietHours>,
+    },
+    /// Set the language of the chat, `None` detects it from the user.
+    UpdateLanguage {
+        chat_id: i64,
+        language: Option<String>,
+    },
+    /// Set the backup chats the unacknowledged loss is escalated to, empty disables escalation.
+    UpdateEscalationLevels {
+        chat_id: i64,
+        bioauth_public_key: [u8; 32],
+        escalation_levels: Vec<bioauth_settings::EscalationLevel>,
+    },
+    /// Set the custom text of the chat notifications, `None` restores the default one.
+    UpdateNotificationTemplate {
+        chat_id: i64,
+        kind: bioauth_settings::NotificationKind,
+        template: Option<String>,
+    },
+    /// Stop repeating the lost notification until the validator is restored.
+    AcknowledgeLoss {
+        chat_id: i64,
+        bioauth_public_key: [u8; 32],
+    },
+    /// Defer the repeated lost notification.
+    SnoozeLoss {
+        chat_id: i64,
+        bioauth_public_key: [u8; 32],
+        in_mins: u64,
+    },
+    /// Apply the settings card action to the current subscription settings,
+    /// the updated settings are sent back.
+    ApplySettingsCardAction {
+        chat_id: i64,
+        bioauth_public_key: [u8; 32],
+        action: SettingsCardAction,
+        result_tx: tokio::sync::oneshot::Sender<
+            Result<bioauth_settings::BioauthSettings, SettingsCardActionError>,
+        >,
+    },
 }
 
 #[derive(Debug)]
@@ -77,21 +159,113 @@ impl SubscriptionUpdateHandle {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Notification {
     BioauthLostNotification {
         chat_id: i64,
         bioauth_public_key: [u8; 32],
+        block_number: u32,
     },
     BioauthSoonExpiredAlert {
         chat_id: i64,
         bioauth_public_key: [u8; 32],
+        expires_at: u64,
+    },
+    BioauthRestored {
+        chat_id: i64,
+        bioauth_public_key: [u8; 32],
+        expires_at: u64,
+    },
+    BioauthAuthenticatedNotification {
+        chat_id: i64,
+        bioauth_public_key: [u8; 32],
+        block_number: u32,
+    },
+    BioauthExpiredNotification {
+        chat_id: i64,
+        bioauth_public_key: [u8; 32],
+        block_number: u32,
+    },
+    BioauthRemovedNotification {
+        chat_id: i64,
+        bioauth_public_key: [u8; 32],
+        block_number: u32,
     },
+    /// The loss is not acknowledged in time and is sent to the backup chat.
+    BioauthLossEscalated {
+        chat_id: i64,
+        bioauth_public_key: [u8; 32],
+        from_chat_id: i64,
+    },
+}
+
+impl Notification {
+    pub fn kind(&self) -> bioauth_settings::NotificationKind {
+        match self {
+            Notification::BioauthLostNotification { .. } => {
+                bioauth_settings::NotificationKind::Lost
+            }
+            Notification::BioauthSoonExpiredAlert { .. } => {
+                bioauth_settings::NotificationKind::SoonExpired
+            }
+            Notification::BioauthRestored { .. } => bioauth_settings::NotificationKind::Restored,
+            Notification::BioauthAuthenticatedNotification { .. } => {
+                bioauth_settings::NotificationKind::Authenticated
+            }
+            Notification::BioauthExpiredNotification { .. } => {
+                bioauth_settings::NotificationKind::Expired
+            }
+            Notification::BioauthRemovedNotification { .. } => {
+                bioauth_settings::NotificationKind::Removed
+            }
+            Notification::BioauthLossEscalated { .. } => {
+                bioauth_settings::NotificationKind::Escalated
+            }
+        }
+    }
+
+    /// The failure to report to the bioauth logic if the notification is not delivered.
+    pub fn failed(&self, error: DeliveryError) -> FailedNotification<[u8; 32]> {
+        match *self {
+            Notification::BioauthLostNotification {
+                chat_id,
+                bioauth_public_key,
+                ..
+            } => FailedNotification::BioauthLostNotificationFailed {
+                chat_id,
+                bioauth_public_key,
+                error,
+            },
+            Notification::BioauthSoonExpiredAlert {
+                chat_id,
+                bioauth_public_key,
+                ..
+            } => FailedNotification::BioauthSoonExpiredAlertFailed {
+                chat_id,
+                bioauth_public_key,
+                error,
+            },
+            Notification::BioauthRestored { chat_id, .. }
+            | Notification::BioauthAuthenticatedNotification { chat_id, .. }
+            | Notification::BioauthExpiredNotification { chat_id, .. }
+            | Notification::BioauthRemovedNotification { chat_id, .. }
+            | Notification::BioauthLossEscalated { chat_id, .. } => {
+                FailedNotification::OtherNotificationFailed { chat_id, error }
+            }
+        }
+    }
+}
+
+/// The notification along with the channel to report its delivery result to.
+#[derive(Debug)]
+pub struct NotificationRequest {
+    pub notification: Notification,
+    pub result_tx: tokio::sync::oneshot::Sender<Result<(), SendNotificationError>>,
 }
 
 #[derive(Debug, Clone)]
 pub struct NotificationHandle {
-    tx: tokio::sync::mpsc::Sender<Notification>,
+    tx: tokio::sync::mpsc::Sender<NotificationRequest>,
 }
 
 #[derive(Debug)]
@@ -106,19 +280,74 @@ impl BioauthSettings {
         bioauth_settings_map.get(key).to_owned()
     }
 
-    async fn get_all_subscriptions(&self, chat_id: i64) -> Vec<String> {
-        let subscriptions = {
+    async fn get_chat(&self, chat_id: i64) -> bioauth_settings::ChatSettings {
+        let bioauth_settings_map = self.rw_bioauth_settings_map.read().await;
+        bioauth_settings_map.get_chat(chat_id).to_owned()
+    }
+
+    /// The language of the chat notifications, English unless it is chosen for the chat.
+    async fn language(&self, chat_id: i64) -> i18n::Language {
+        let chat_settings = self.get_chat(chat_id).await;
+        i18n::Language::resolve(chat_settings.language.as_deref(), None)
+    }
+
+    async fn get_template(
+        &self,
+        chat_id: i64,
+        kind: bioauth_settings::NotificationKind,
+    ) -> Option<String> {
+        let bioauth_settings_map = self.rw_bioauth_settings_map.read().await;
+        bioauth_settings_map
+            .get_template(chat_id, kind)
+            .map(ToOwned::to_owned)
+    }
+
+    /// The chats escalating the loss of the validator to the backup chat.
+    async fn get_escalating_chats(
+        &self,
+        backup_chat_id: i64,
+        bioauth_public_key: [u8; 32],
+    ) -> Vec<i64> {
+        let bioauth_settings_map = self.rw_bioauth_settings_map.read().await;
+        bioauth_settings_map.get_escalating_chats(&bioauth_public_key, backup_chat_id)
+    }
+
+    async fn get_all_subscription_keys(&self, chat_id: i64) -> HashSet<[u8; 32]> {
+        let bioauth_settings_map = self.rw_bioauth_settings_map.read().await;
+        bioauth_settings_map.get_all_subscriptions_by_id(chat_id)
+    }
+
+    /// The validator label along with its address, or just the address if it has no label.
+    async fn display_name(&self, chat_id: i64, bioauth_public_key: [u8; 32]) -> String {
+        let settings = self.get(&(chat_id, bioauth_public_key)).await;
+        bioauth_handlers::display_name(
+            &bioauth_handlers::to_ss58(bioauth_public_key),
+            settings.label.as_deref(),
+        )
+    }
+
+    /// The addresses of the chat subscriptions along with their labels.
+    async fn get_all_subscriptions(&self, chat_id: i64) -> Vec<(String, Option<String>)> {
+        let subscriptions: Vec<([u8; 32], Option<String>)> = {
             let bioauth_settings_map = self.rw_bioauth_settings_map.read().await;
-            bioauth_settings_map.get_all_subscriptions_by_id(chat_id)
+            bioauth_settings_map
+                .get_all_subscriptions_by_id(chat_id)
+                .into_iter()
+                .map(|bytes| {
+                    let label = bioauth_settings_map.get(&(chat_id, bytes)).label.clone();
+                    (bytes, label)
+                })
+                .collect()
         };
 
         tracing::info!(message = "get_all_subscriptions", ?subscriptions);
 
         subscriptions
-            .iter()
-            .map(|bytes| {
-                sp_core::crypto::AccountId32::new(*bytes)
-                    .to_ss58check_with_version(Ss58AddressFormatRegistry::HumanodeAccount.into())
+            .into_iter()
+            .map(|(bytes, label)| {
+                let address = sp_core::crypto::AccountId32::new(bytes)
+                    .to_ss58check_with_version(Ss58AddressFormatRegistry::HumanodeAccount.into());
+                (address, label)
             })
             .collect()
     }
@@ -126,27 +355,56 @@ impl BioauthSettings {
 
 impl NotificationHandle {
     #[cfg(any(test, feature = "test-utils"))]
-    pub fn mock(tx: tokio::sync::mpsc::Sender<Notification>) -> Self {
+    pub fn mock(tx: tokio::sync::mpsc::Sender<NotificationRequest>) -> Self {
         Self { tx }
     }
 
+    /// Send the notification and wait for its delivery result.
     pub async fn send_notification(
         &self,
         notification: Notification,
     ) -> Result<(), SendNotificationError> {
-        self.tx.send(notification).await.unwrap();
+        // Reported if the run loop is gone and the notification is never handled.
+        let unhandled = SendNotificationError {
+            notification: notification.failed(DeliveryError::Retryable),
+        };
 
-        Ok(())
+        let (result_tx, result_rx) = tokio::sync::oneshot::channel();
+        if self
+            .tx
+            .send(NotificationRequest {
+                notification,
+                result_tx,
+            })
+            .await
+            .is_err()
+        {
+            return Err(unhandled);
+        }
+
+        result_rx.await.unwrap_or(Err(unhandled))
     }
 }
 
 impl Telegram {
-    /// Set bot commands.
+    /// Set bot commands, the default English ones along with the localized ones.
     pub async fn set_commands(&self) -> Result<(), anyhow::Error> {
+        let commands = handlers::Command::bot_commands();
+
         self.bot
-            .set_my_commands(handlers::Command::bot_commands())
+            .set_my_commands(i18n::localize_commands(
+                i18n::Language::default(),
+                commands.clone(),
+            ))
             .await?;
 
+        for language in i18n::Language::ALL {
+            self.bot
+                .set_my_commands(i18n::localize_commands(language, commands.clone()))
+                .language_code(language.code())
+                .await?;
+        }
+
         Ok(())
     }
 
@@ -164,31 +422,43 @@ impl Telegram {
             storage,
             rw_bioauth_settings_map,
             rw_dev_subscriptions_map,
+            rw_active_authentications,
             admin_chat_ids,
+            explorer_url,
         } = self;
 
         let get_all_subscriptions = BioauthSettings {
             rw_bioauth_settings_map,
         };
         let get_all_subscriptions = Arc::new(get_all_subscriptions);
+        let explorer_url = Arc::new(bioauth_handlers::ExplorerUrl(explorer_url));
 
         let (subscription_update_tx, subscription_update_rx) =
             tokio::sync::mpsc::channel::<SubscriptionUpdate>(1000);
         let (notification_handle_tx, notification_handle_rx) =
-            tokio::sync::mpsc::channel::<Notification>(1000);
+            tokio::sync::mpsc::channel::<NotificationRequest>(1000);
         let subscription_update_handle = SubscriptionUpdateHandle {
             rx: subscription_update_rx,
         };
         let notification_handle = NotificationHandle {
             tx: notification_handle_tx,
         };
+        let outbound = outbound::spawn(bot.clone(), outbound::RateLimits::default());
         {
+            let outbound = outbound.clone();
+            let subscription_update_tx = subscription_update_tx.clone();
+            let bioauth_settings = Arc::clone(&get_all_subscriptions);
+            let explorer_url = Arc::clone(&explorer_url);
             let bot = bot.clone();
 
             tokio::spawn(async move {
                 if let Err(error) = bioauth_handlers::run_loop(bioauth_handlers::RunLoopParams {
                     bot,
+                    outbound,
+                    bioauth_settings,
+                    explorer_url,
                     notification_handle_rx,
+                    subscription_update_tx,
                 })
                 .await
                 {
@@ -203,7 +473,10 @@ impl Telegram {
                 subscription_update_tx,
                 rw_dev_subscriptions_map,
                 admin_chat_ids,
-                storage
+                storage,
+                outbound,
+                rw_active_authentications,
+                explorer_url
             ])
             .build();
 
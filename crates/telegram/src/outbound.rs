@@ -0,0 +1,556 @@
+//! The outbound message queue respecting the Telegram rate limits.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+use teloxide::{
+    prelude::*,
+    types::{ChatId, InlineKeyboardMarkup, ParseMode},
+    RequestError,
+};
+use tokio::time::Instant;
+
+/// The order the queued messages are sent in, the most urgent first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Priority {
+    /// The bioauth expiration alerts and lost notifications.
+    Alert,
+    /// The other bioauth notifications.
+    Notification,
+    /// The admin broadcasts.
+    Broadcast,
+}
+
+const PRIORITIES: [Priority; 3] = [Priority::Alert, Priority::Notification, Priority::Broadcast];
+
+/// The message hitting the flood control more times is given up with the error.
+const MAX_RETRIES: u32 = 5;
+
+/// The token bucket parameters: the burst size and the sustained rate.
+#[derive(Debug, Clone, Copy)]
+pub struct Limit {
+    pub burst: u32,
+    pub per_second: f64,
+}
+
+/// The Telegram rate limits the outbound queue respects.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimits {
+    /// The limit of the bot across all the chats.
+    pub global: Limit,
+    /// The limit in a private chat.
+    pub private_chat: Limit,
+    /// The limit in a group chat.
+    pub group_chat: Limit,
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self {
+            global: Limit {
+                burst: 30,
+                per_second: 30.0,
+            },
+            private_chat: Limit {
+                burst: 1,
+                per_second: 1.0,
+            },
+            group_chat: Limit {
+                burst: 20,
+                per_second: 20.0 / 60.0,
+            },
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    limit: Limit,
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: Limit, now: Instant) -> Self {
+        Self {
+            limit,
+            tokens: f64::from(limit.burst),
+            updated_at: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * self.limit.per_second).min(f64::from(self.limit.burst));
+        self.updated_at = now;
+    }
+
+    /// The time to wait until a token is available, `None` if it is available now.
+    fn wait_time(&mut self, now: Instant) -> Option<Duration> {
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            return None;
+        }
+
+        Some(Duration::from_secs_f64(
+            (1.0 - self.tokens) / self.limit.per_second,
+        ))
+    }
+
+    fn take(&mut self) {
+        self.tokens -= 1.0;
+    }
+
+    fn is_full(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        self.tokens >= f64::from(self.limit.burst)
+    }
+}
+
+/// The formatting and the buttons of the outbound message.
+#[derive(Debug, Clone, Default)]
+pub struct MessageOptions {
+    pub parse_mode: Option<ParseMode>,
+    pub reply_markup: Option<InlineKeyboardMarkup>,
+}
+
+#[derive(Debug)]
+struct OutboundMessage {
+    chat_id: ChatId,
+    text: String,
+    options: MessageOptions,
+    priority: Priority,
+    /// The number of times the message was requeued by the flood control.
+    retries: u32,
+    result_tx: tokio::sync::oneshot::Sender<Result<Message, RequestError>>,
+}
+
+#[derive(Debug)]
+struct Retry {
+    message: OutboundMessage,
+    after: Duration,
+}
+
+/// The handle to enqueue the outbound messages.
+#[derive(Debug, Clone)]
+pub struct OutboundHandle {
+    tx: tokio::sync::mpsc::UnboundedSender<OutboundMessage>,
+}
+
+impl OutboundHandle {
+    /// Enqueue the plain text message and wait until it is sent.
+    pub async fn send_message(
+        &self,
+        chat_id: ChatId,
+        text: String,
+        priority: Priority,
+    ) -> Result<Message, RequestError> {
+        self.send_message_with_options(chat_id, text, MessageOptions::default(), priority)
+            .await
+    }
+
+    /// Enqueue the message with the formatting and the buttons and wait until it is sent.
+    pub async fn send_message_with_options(
+        &self,
+        chat_id: ChatId,
+        text: String,
+        options: MessageOptions,
+        priority: Priority,
+    ) -> Result<Message, RequestError> {
+        let (result_tx, result_rx) = tokio::sync::oneshot::channel();
+        let message = OutboundMessage {
+            chat_id,
+            text,
+            options,
+            priority,
+            retries: 0,
+            result_tx,
+        };
+
+        let gone = || {
+            RequestError::Io(std::io::Error::new(
+                std::io::ErrorKind::BrokenPipe,
+                "the outbound queue is gone",
+            ))
+        };
+
+        self.tx.send(message).map_err(|_| gone())?;
+        result_rx.await.unwrap_or_else(|_| Err(gone()))
+    }
+}
+
+#[derive(Debug)]
+struct Dispatcher {
+    bot: Bot,
+    rate_limits: RateLimits,
+    queues: HashMap<Priority, VecDeque<OutboundMessage>>,
+    global_bucket: TokenBucket,
+    chat_buckets: HashMap<ChatId, TokenBucket>,
+    paused_until: Option<Instant>,
+    retry_tx: tokio::sync::mpsc::UnboundedSender<Retry>,
+}
+
+impl Dispatcher {
+    fn chat_limit(&self, chat_id: ChatId) -> Limit {
+        if chat_id.is_user() {
+            self.rate_limits.private_chat
+        } else {
+            self.rate_limits.group_chat
+        }
+    }
+
+    fn enqueue(&mut self, message: OutboundMessage) {
+        self.queues
+            .entry(message.priority)
+            .or_default()
+            .push_back(message);
+    }
+
+    fn retry(&mut self, retry: Retry, now: Instant) {
+        let Retry { mut message, after } = retry;
+        tracing::warn!(message = "outbound flood control", chat_id = ?message.chat_id, ?after);
+
+        if message.retries >= MAX_RETRIES {
+            tracing::error!(
+                message = "outbound message given up after retries",
+                chat_id = ?message.chat_id,
+                retries = ?message.retries
+            );
+            // The sender may have stopped waiting for the result.
+            message
+                .result_tx
+                .send(Err(RequestError::RetryAfter(after)))
+                .ok();
+            return;
+        }
+        message.retries += 1;
+
+        // The flood control applies to the whole bot, so nothing is sent until it passes.
+        let retry_at = now + after;
+        self.paused_until = Some(self.paused_until.map_or(retry_at, |at| at.max(retry_at)));
+        self.queues
+            .entry(message.priority)
+            .or_default()
+            .push_front(message);
+    }
+
+    /// Take the messages the limits allow to send at the moment, along with when to try again.
+    fn take_ready(&mut self, now: Instant) -> (Vec<OutboundMessage>, Option<Instant>) {
+        let mut ready = vec![];
+
+        if let Some(paused_until) = self.paused_until {
+            if now < paused_until {
+                return (ready, Some(paused_until));
+            }
+            self.paused_until = None;
+        }
+
+        let mut next_attempt_at: Option<Instant> = None;
+        let mut earliest = |at: Instant| {
+            next_attempt_at = Some(next_attempt_at.map_or(at, |next| next.min(at)));
+        };
+
+        'priorities: for priority in PRIORITIES {
+            let Some(mut queue) = self.queues.remove(&priority) else {
+                continue;
+            };
+
+            let mut index = 0;
+            while index < queue.len() {
+                if let Some(wait_time) = self.global_bucket.wait_time(now) {
+                    earliest(now + wait_time);
+                    self.queues.insert(priority, queue);
+                    break 'priorities;
+                }
+
+                let chat_id = queue[index].chat_id;
+                let chat_limit = self.chat_limit(chat_id);
+                let chat_bucket = self
+                    .chat_buckets
+                    .entry(chat_id)
+                    .or_insert_with(|| TokenBucket::new(chat_limit, now));
+
+                if let Some(wait_time) = chat_bucket.wait_time(now) {
+                    earliest(now + wait_time);
+                    index += 1;
+                    continue;
+                }
+
+                chat_bucket.take();
+                self.global_bucket.take();
+
+                ready.push(queue.remove(index).expect("index is in bounds"));
+            }
+
+            if !queue.is_empty() {
+                self.queues.insert(priority, queue);
+            }
+        }
+
+        self.chat_buckets
+            .retain(|_, chat_bucket| !chat_bucket.is_full(now));
+
+        (ready, next_attempt_at)
+    }
+
+    /// Send all the messages the limits allow now, return when to try again.
+    fn dispatch(&mut self) -> Option<Instant> {
+        let (ready, next_attempt_at) = self.take_ready(Instant::now());
+        for message in ready {
+            self.spawn_send(message);
+        }
+
+        next_attempt_at
+    }
+
+    fn spawn_send(&self, message: OutboundMessage) {
+        let bot = self.bot.clone();
+        let retry_tx = self.retry_tx.clone();
+
+        tokio::spawn(async move {
+            let mut request = bot.send_message(message.chat_id, message.text.clone());
+            request.parse_mode = message.options.parse_mode;
+            request.reply_markup = message.options.reply_markup.clone().map(Into::into);
+            let result = request.await;
+
+            if let Err(RequestError::RetryAfter(after)) = result {
+                if retry_tx.send(Retry { message, after }).is_err() {
+                    tracing::error!(message = "the outbound dispatcher is gone");
+                }
+                return;
+            }
+
+            // The sender may have stopped waiting for the result.
+            message.result_tx.send(result).ok();
+        });
+    }
+}
+
+/// Spawn the outbound queue dispatcher.
+pub fn spawn(bot: Bot, rate_limits: RateLimits) -> OutboundHandle {
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let (retry_tx, mut retry_rx) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut dispatcher = Dispatcher {
+        bot,
+        rate_limits,
+        queues: HashMap::new(),
+        global_bucket: TokenBucket::new(rate_limits.global, Instant::now()),
+        chat_buckets: HashMap::new(),
+        paused_until: None,
+        retry_tx,
+    };
+
+    tokio::spawn(async move {
+        loop {
+            let next_attempt_at = dispatcher.dispatch();
+
+            tokio::select! {
+                Some(message) = rx.recv() => dispatcher.enqueue(message),
+                Some(retry) = retry_rx.recv() => dispatcher.retry(retry, Instant::now()),
+                _ = tokio::time::sleep_until(next_attempt_at.unwrap_or_else(Instant::now)), if next_attempt_at.is_some() => {}
+            }
+        }
+    });
+
+    OutboundHandle { tx }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIVATE_CHAT_ID: ChatId = ChatId(1);
+    const GROUP_CHAT_ID: ChatId = ChatId(-1);
+
+    fn message(chat_id: ChatId, text: &str, priority: Priority) -> OutboundMessage {
+        let (result_tx, _) = tokio::sync::oneshot::channel();
+        OutboundMessage {
+            chat_id,
+            text: text.to_owned(),
+            options: MessageOptions::default(),
+            priority,
+            retries: 0,
+            result_tx,
+        }
+    }
+
+    fn dispatcher(rate_limits: RateLimits, now: Instant) -> Dispatcher {
+        let (retry_tx, _) = tokio::sync::mpsc::unbounded_channel();
+        Dispatcher {
+            bot: Bot::new("token"),
+            rate_limits,
+            queues: HashMap::new(),
+            global_bucket: TokenBucket::new(rate_limits.global, now),
+            chat_buckets: HashMap::new(),
+            paused_until: None,
+            retry_tx,
+        }
+    }
+
+    fn texts(messages: &[OutboundMessage]) -> Vec<&str> {
+        messages
+            .iter()
+            .map(|message| message.text.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn token_bucket_burst_and_refill() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(
+            Limit {
+                burst: 3,
+                per_second: 2.0,
+            },
+            now,
+        );
+
+        for _ in 0..3 {
+            assert_eq!(bucket.wait_time(now), None);
+            bucket.take();
+        }
+        assert_eq!(bucket.wait_time(now), Some(Duration::from_millis(500)));
+        assert!(!bucket.is_full(now));
+
+        let now = now + Duration::from_millis(250);
+        assert_eq!(bucket.wait_time(now), Some(Duration::from_millis(250)));
+
+        let now = now + Duration::from_millis(250);
+        assert_eq!(bucket.wait_time(now), None);
+
+        // The refill never exceeds the burst.
+        let now = now + Duration::from_secs(60);
+        assert!(bucket.is_full(now));
+        for _ in 0..3 {
+            assert_eq!(bucket.wait_time(now), None);
+            bucket.take();
+        }
+        assert!(bucket.wait_time(now).is_some());
+    }
+
+    #[test]
+    fn dispatch_in_priority_order() {
+        let now = Instant::now();
+        let mut dispatcher = dispatcher(RateLimits::default(), now);
+
+        dispatcher.enqueue(message(ChatId(1), "broadcast", Priority::Broadcast));
+        dispatcher.enqueue(message(ChatId(2), "notification", Priority::Notification));
+        dispatcher.enqueue(message(ChatId(3), "alert", Priority::Alert));
+
+        let (ready, next_attempt_at) = dispatcher.take_ready(now);
+        assert_eq!(texts(&ready), ["alert", "notification", "broadcast"]);
+        assert_eq!(next_attempt_at, None);
+    }
+
+    #[test]
+    fn chat_limit_defers_only_its_chat() {
+        let now = Instant::now();
+        let mut dispatcher = dispatcher(RateLimits::default(), now);
+
+        dispatcher.enqueue(message(PRIVATE_CHAT_ID, "first", Priority::Alert));
+        dispatcher.enqueue(message(PRIVATE_CHAT_ID, "second", Priority::Alert));
+        dispatcher.enqueue(message(GROUP_CHAT_ID, "group", Priority::Broadcast));
+
+        let (ready, next_attempt_at) = dispatcher.take_ready(now);
+        assert_eq!(texts(&ready), ["first", "group"]);
+        assert_eq!(next_attempt_at, Some(now + Duration::from_secs(1)));
+
+        let (ready, _) = dispatcher.take_ready(now + Duration::from_secs(1));
+        assert_eq!(texts(&ready), ["second"]);
+    }
+
+    #[test]
+    fn global_limit_stops_dispatch() {
+        let now = Instant::now();
+        let rate_limits = RateLimits {
+            global: Limit {
+                burst: 2,
+                per_second: 1.0,
+            },
+            ..RateLimits::default()
+        };
+        let mut dispatcher = dispatcher(rate_limits, now);
+
+        dispatcher.enqueue(message(ChatId(1), "alert", Priority::Alert));
+        dispatcher.enqueue(message(ChatId(2), "notification", Priority::Notification));
+        dispatcher.enqueue(message(ChatId(3), "broadcast", Priority::Broadcast));
+
+        let (ready, next_attempt_at) = dispatcher.take_ready(now);
+        assert_eq!(texts(&ready), ["alert", "notification"]);
+        assert_eq!(next_attempt_at, Some(now + Duration::from_secs(1)));
+
+        let (ready, _) = dispatcher.take_ready(now + Duration::from_secs(1));
+        assert_eq!(texts(&ready), ["broadcast"]);
+    }
+
+    #[test]
+    fn retry_after_pauses_dispatch() {
+        let now = Instant::now();
+        let mut dispatcher = dispatcher(RateLimits::default(), now);
+
+        dispatcher.enqueue(message(ChatId(2), "queued", Priority::Alert));
+        dispatcher.retry(
+            Retry {
+                message: message(ChatId(1), "retried", Priority::Alert),
+                after: Duration::from_secs(5),
+            },
+            now,
+        );
+
+        let (ready, next_attempt_at) = dispatcher.take_ready(now + Duration::from_secs(1));
+        assert!(ready.is_empty());
+        assert_eq!(next_attempt_at, Some(now + Duration::from_secs(5)));
+
+        // The retried message goes first once the pause is over.
+        let (ready, next_attempt_at) = dispatcher.take_ready(now + Duration::from_secs(5));
+        assert_eq!(texts(&ready), ["retried", "queued"]);
+        assert_eq!(next_attempt_at, None);
+    }
+
+    #[test]
+    fn retry_after_gives_up_after_max_retries() {
+        let now = Instant::now();
+        let mut dispatcher = dispatcher(RateLimits::default(), now);
+
+        let (result_tx, mut result_rx) = tokio::sync::oneshot::channel();
+        let mut message = OutboundMessage {
+            result_tx,
+            ..message(ChatId(1), "flooded", Priority::Alert)
+        };
+
+        let mut now = now;
+        for retries in 1..=MAX_RETRIES {
+            dispatcher.retry(
+                Retry {
+                    message,
+                    after: Duration::from_secs(1),
+                },
+                now,
+            );
+            now += Duration::from_secs(1);
+            let (mut ready, _) = dispatcher.take_ready(now);
+            assert_eq!(texts(&ready), ["flooded"]);
+            message = ready.remove(0);
+            assert_eq!(message.retries, retries);
+            assert!(result_rx.try_recv().is_err());
+        }
+
+        dispatcher.retry(
+            Retry {
+                message,
+                after: Duration::from_secs(1),
+            },
+            now,
+        );
+        assert!(matches!(
+            result_rx.try_recv(),
+            Ok(Err(RequestError::RetryAfter(_)))
+        ));
+        let (ready, _) = dispatcher.take_ready(now + Duration::from_secs(1));
+        assert!(ready.is_empty());
+    }
+}
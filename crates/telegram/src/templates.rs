@@ -0,0 +1,302 @@
+//! The custom notification texts with the `{placeholder}` values.
+
+use bioauth_settings::NotificationKind;
+
+/// The maximum template length in characters.
+pub const MAX_TEMPLATE_LEN: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placeholder {
+    Address,
+    Label,
+    Name,
+    /// The address page on the block explorer.
+    ExplorerUrl,
+    BlockNumber,
+    ExpiresAt,
+    TimeLeft,
+}
+
+impl Placeholder {
+    pub fn name(self) -> &'static str {
+        match self {
+            Placeholder::Address => "address",
+            Placeholder::Label => "label",
+            Placeholder::Name => "name",
+            Placeholder::ExplorerUrl => "explorer_url",
+            Placeholder::BlockNumber => "block_number",
+            Placeholder::ExpiresAt => "expires_at",
+            Placeholder::TimeLeft => "time_left",
+        }
+    }
+
+    /// The placeholders with the values in the notifications of the kind.
+    pub fn available(kind: NotificationKind) -> &'static [Placeholder] {
+        const COMMON: [Placeholder; 4] = [
+            Placeholder::Address,
+            Placeholder::Label,
+            Placeholder::Name,
+            Placeholder::ExplorerUrl,
+        ];
+        const WITH_EXPIRES_AT: [Placeholder; 6] = [
+            Placeholder::Address,
+            Placeholder::Label,
+            Placeholder::Name,
+            Placeholder::ExplorerUrl,
+            Placeholder::ExpiresAt,
+            Placeholder::TimeLeft,
+        ];
+        const WITH_BLOCK_NUMBER: [Placeholder; 5] = [
+            Placeholder::Address,
+            Placeholder::Label,
+            Placeholder::Name,
+            Placeholder::ExplorerUrl,
+            Placeholder::BlockNumber,
+        ];
+
+        match kind {
+            NotificationKind::Escalated => &COMMON,
+            NotificationKind::SoonExpired | NotificationKind::Restored => &WITH_EXPIRES_AT,
+            NotificationKind::Lost
+            | NotificationKind::Authenticated
+            | NotificationKind::Expired
+            | NotificationKind::Removed => &WITH_BLOCK_NUMBER,
+        }
+    }
+
+    /// The available placeholders as they are written in the template.
+    pub fn format_available(kind: NotificationKind) -> String {
+        Placeholder::available(kind)
+            .iter()
+            .map(|placeholder| format!("{{{}}}", placeholder.name()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    Empty,
+    TooLong,
+    /// The `{` without the closing `}`.
+    Unclosed,
+    /// The placeholder that is unknown or has no value in the notifications of the kind.
+    UnknownPlaceholder(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment<'a> {
+    Text(&'a str),
+    Placeholder(&'a str),
+}
+
+/// Split the template into the text and the placeholders, `{{` and `}}` are the escaped braces.
+fn parse(template: &str) -> Result<Vec<Segment<'_>>, TemplateError> {
+    let mut segments = vec![];
+    let mut rest = template;
+
+    while let Some(position) = rest.find(['{', '}']) {
+        let (text, tail) = rest.split_at(position);
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+
+        if let Some(tail) = tail.strip_prefix("{{") {
+            segments.push(Segment::Text("{"));
+            rest = tail;
+        } else if let Some(tail) = tail.strip_prefix("}}") {
+            segments.push(Segment::Text("}"));
+            rest = tail;
+        } else if let Some(tail) = tail.strip_prefix('{') {
+            let (name, tail) = tail.split_once('}').ok_or(TemplateError::Unclosed)?;
+            segments.push(Segment::Placeholder(name.trim()));
+            rest = tail;
+        } else {
+            // The lone closing brace is kept as is.
+            segments.push(Segment::Text("}"));
+            rest = &tail[1..];
+        }
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest));
+    }
+
+    Ok(segments)
+}
+
+/// Check the template can be rendered for the notifications of the kind.
+pub fn validate(kind: NotificationKind, template: &str) -> Result<(), TemplateError> {
+    if template.trim().is_empty() {
+        return Err(TemplateError::Empty);
+    }
+    if template.chars().count() > MAX_TEMPLATE_LEN {
+        return Err(TemplateError::TooLong);
+    }
+
+    for segment in parse(template)? {
+        if let Segment::Placeholder(name) = segment {
+            if !Placeholder::available(kind)
+                .iter()
+                .any(|placeholder| placeholder.name() == name)
+            {
+                return Err(TemplateError::UnknownPlaceholder(name.to_owned()));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The values of the notification placeholders, `None` if the notification has no such value.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateValues {
+    pub address: String,
+    pub label: Option<String>,
+    pub name: String,
+    pub explorer_url: String,
+    pub block_number: Option<u32>,
+    pub expires_at: Option<String>,
+    pub time_left: Option<String>,
+}
+
+impl TemplateValues {
+    fn get(&self, name: &str) -> Option<String> {
+        match name {
+            "address" => Some(self.address.clone()),
+            // The validator without the label has the empty one.
+            "label" => Some(self.label.clone().unwrap_or_default()),
+            "name" => Some(self.name.clone()),
+            "explorer_url" => Some(self.explorer_url.clone()),
+            "block_number" => self
+                .block_number
+                .map(|block_number| block_number.to_string()),
+            "expires_at" => self.expires_at.clone(),
+            "time_left" => self.time_left.clone(),
+            _ => None,
+        }
+    }
+}
+
+/// Render the validated template, the placeholders without the values are kept as is.
+pub fn render(template: &str, values: &TemplateValues) -> String {
+    let Ok(segments) = parse(template) else {
+        return template.to_owned();
+    };
+
+    segments
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Text(text) => text.to_owned(),
+            Segment::Placeholder(name) => values.get(name).unwrap_or_else(|| format!("{{{name}}}")),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values() -> TemplateValues {
+        TemplateValues {
+            address: "hmAddress".to_owned(),
+            label: Some("node-1".to_owned()),
+            name: "node-1 (hmAddress)".to_owned(),
+            explorer_url: "https://explorer/hmAddress".to_owned(),
+            block_number: Some(42),
+            ..TemplateValues::default()
+        }
+    }
+
+    #[test]
+    fn parse_escapes_and_placeholders() {
+        assert_eq!(
+            parse("{{a}} { name } }"),
+            Ok(vec![
+                Segment::Text("{"),
+                Segment::Text("a"),
+                Segment::Text("}"),
+                Segment::Text(" "),
+                Segment::Placeholder("name"),
+                Segment::Text(" "),
+                Segment::Text("}"),
+            ])
+        );
+        assert_eq!(parse("lost {name"), Err(TemplateError::Unclosed));
+    }
+
+    #[test]
+    fn validate_template() {
+        assert_eq!(
+            validate(NotificationKind::Lost, " \n"),
+            Err(TemplateError::Empty)
+        );
+        assert_eq!(
+            validate(NotificationKind::Lost, &"a".repeat(MAX_TEMPLATE_LEN + 1)),
+            Err(TemplateError::TooLong)
+        );
+        assert_eq!(
+            validate(NotificationKind::Lost, "{name} is lost {"),
+            Err(TemplateError::Unclosed)
+        );
+        assert_eq!(
+            validate(NotificationKind::Lost, "{unknown}"),
+            Err(TemplateError::UnknownPlaceholder("unknown".to_owned()))
+        );
+        assert_eq!(validate(NotificationKind::Lost, "{{name}} {name}"), Ok(()));
+    }
+
+    #[test]
+    fn validate_placeholders_per_kind() {
+        assert_eq!(
+            validate(NotificationKind::Escalated, "{block_number}"),
+            Err(TemplateError::UnknownPlaceholder("block_number".to_owned()))
+        );
+        assert_eq!(
+            validate(NotificationKind::Escalated, "{time_left}"),
+            Err(TemplateError::UnknownPlaceholder("time_left".to_owned()))
+        );
+        assert_eq!(
+            validate(NotificationKind::Authenticated, "{expires_at}"),
+            Err(TemplateError::UnknownPlaceholder("expires_at".to_owned()))
+        );
+        assert_eq!(
+            validate(NotificationKind::Expired, "{block_number}"),
+            Ok(())
+        );
+        assert_eq!(validate(NotificationKind::Lost, "{block_number}"), Ok(()));
+        assert_eq!(
+            validate(NotificationKind::SoonExpired, "{expires_at} {time_left}"),
+            Ok(())
+        );
+
+        for kind in NotificationKind::ALL {
+            assert_eq!(
+                validate(kind, "{address} {label} {name} {explorer_url}"),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn render_template() {
+        assert_eq!(
+            render("{name} at {block_number}: {explorer_url}", &values()),
+            "node-1 (hmAddress) at 42: https://explorer/hmAddress"
+        );
+        assert_eq!(render("{{label}} = {label}", &values()), "{label} = node-1");
+
+        // The missing values are kept as placeholders, the empty label is rendered empty.
+        let values = TemplateValues {
+            label: None,
+            block_number: None,
+            ..values()
+        };
+        assert_eq!(
+            render("[{label}] {block_number} {time_left}", &values),
+            "[] {block_number} {time_left}"
+        );
+
+        // The unclosed template is kept as is.
+        assert_eq!(render("{name", &values), "{name");
+    }
+}